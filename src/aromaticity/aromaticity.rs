@@ -0,0 +1,391 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use gamma::graph::Error;
+
+use super::Model;
+use crate::molecule::{Element, Molecule};
+use crate::rings::Rings;
+
+// Largest number of SSSR rings combined when testing a fused system.
+const MAX_FUSED: usize = 6;
+
+/// Aromatic atoms and bonds of a Molecule, perceived from its Kekulé
+/// bonds. An SSSR ring is aromatic if every atom contributes pi electrons
+/// under the Model and their sum is 4n + 2. Fused combinations of up to
+/// six rings sharing bonds are tested the same way, so that systems such
+/// as azulene are found.
+#[derive(Debug, PartialEq)]
+pub struct Aromaticity {
+    atoms: HashSet<usize>,
+    bonds: HashSet<(usize, usize)>,
+}
+
+impl Aromaticity {
+    pub fn new<M: Molecule>(
+        molecule: &M,
+        model: &Model,
+    ) -> Result<Self, Error> {
+        let rings = Rings::new(molecule)?;
+        let mut electrons = HashMap::new();
+
+        for id in molecule.ids() {
+            if rings.is_in_ring(id) {
+                if let Some(count) = pi_electrons(molecule, id, &rings, model)?
+                {
+                    electrons.insert(id, count);
+                }
+            }
+        }
+
+        let candidates = rings
+            .sssr()
+            .iter()
+            .filter(|ring| ring.iter().all(|id| electrons.contains_key(id)))
+            .collect::<Vec<_>>();
+        let mut result = Self {
+            atoms: HashSet::new(),
+            bonds: HashSet::new(),
+        };
+
+        for subset in fused_subsets(&candidates) {
+            let atoms = subset
+                .iter()
+                .flat_map(|&index| candidates[index].iter())
+                .collect::<HashSet<_>>();
+            let sum =
+                atoms.iter().map(|id| electrons[id] as usize).sum::<usize>();
+
+            if sum % 4 == 2 {
+                for &index in subset.iter() {
+                    result.add(candidates[index]);
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub fn is_aromatic_atom(&self, id: usize) -> bool {
+        self.atoms.contains(&id)
+    }
+
+    pub fn is_aromatic_bond(&self, sid: usize, tid: usize) -> bool {
+        self.bonds.contains(&key(sid, tid))
+    }
+
+    /// Returns the aromatic atom ids in ascending order.
+    pub fn atoms(&self) -> Vec<usize> {
+        let mut result = self.atoms.iter().cloned().collect::<Vec<_>>();
+
+        result.sort_unstable();
+
+        result
+    }
+
+    fn add(&mut self, ring: &[usize]) {
+        for (i, &sid) in ring.iter().enumerate() {
+            self.atoms.insert(sid);
+            self.bonds.insert(key(sid, ring[(i + 1) % ring.len()]));
+        }
+    }
+}
+
+// Returns the pi electrons contributed by a ring atom, or None if it
+// can't take part in an aromatic ring.
+fn pi_electrons<M: Molecule>(
+    molecule: &M,
+    id: usize,
+    rings: &Rings,
+    model: &Model,
+) -> Result<Option<u8>, Error> {
+    let atom = molecule.atom(id)?;
+    let element = match &atom.element {
+        Some(element) => element,
+        None => return Ok(None),
+    };
+    let mut ring_double = 0;
+    let mut exocyclic = Vec::new();
+    let mut degree = atom.hydrogens as usize;
+
+    for neighbor in molecule.neighbors(id)? {
+        let bond = match molecule.bond(id, neighbor)? {
+            Some(bond) => bond,
+            None => return Err(Error::MissingEdge(id, neighbor)),
+        };
+
+        degree += 1;
+
+        match bond.electrons {
+            2 => (),
+            4 => {
+                if rings.is_bond_in_ring(id, neighbor) {
+                    ring_double += 1
+                } else {
+                    exocyclic.push(neighbor)
+                }
+            }
+            _ => return Ok(None),
+        }
+    }
+
+    if ring_double + exocyclic.len() > 1 {
+        return Ok(None);
+    }
+
+    Ok(match model {
+        Model::Mdl => match element {
+            Element::C | Element::N if ring_double == 1 => Some(1),
+            _ => None,
+        },
+        Model::Daylight => {
+            if ring_double == 1 {
+                Some(1)
+            } else if let Some(&neighbor) = exocyclic.first() {
+                match molecule.atom(neighbor)?.element {
+                    Some(Element::N) | Some(Element::O) | Some(Element::S) => {
+                        Some(0)
+                    }
+                    _ => None,
+                }
+            } else if degree > 3 {
+                None
+            } else if atom.electrons >= 2 {
+                Some(2)
+            } else if atom.electrons == 0 && degree == 3 {
+                Some(0)
+            } else {
+                None
+            }
+        }
+    })
+}
+
+// Returns each ring alone, then each connected combination of rings
+// sharing a bond, up to MAX_FUSED rings.
+fn fused_subsets(rings: &[&Vec<usize>]) -> Vec<BTreeSet<usize>> {
+    let bonds = rings
+        .iter()
+        .map(|ring| {
+            (0..ring.len())
+                .map(|i| key(ring[i], ring[(i + 1) % ring.len()]))
+                .collect::<HashSet<_>>()
+        })
+        .collect::<Vec<_>>();
+    let mut result = Vec::new();
+    let mut seen = HashSet::new();
+    let mut layer = (0..rings.len())
+        .map(|index| vec![index].into_iter().collect::<BTreeSet<_>>())
+        .collect::<Vec<_>>();
+
+    while !layer.is_empty() {
+        let mut next = Vec::new();
+
+        for subset in layer.into_iter() {
+            if subset.len() < MAX_FUSED {
+                for other in 0..rings.len() {
+                    if subset.contains(&other)
+                        || subset.iter().all(|&index| {
+                            bonds[index].is_disjoint(&bonds[other])
+                        })
+                    {
+                        continue;
+                    }
+
+                    let mut grown = subset.clone();
+
+                    grown.insert(other);
+
+                    if seen.insert(grown.clone()) {
+                        next.push(grown);
+                    }
+                }
+            }
+
+            result.push(subset);
+        }
+
+        layer = next;
+    }
+
+    result
+}
+
+fn key(sid: usize, tid: usize) -> (usize, usize) {
+    if sid < tid {
+        (sid, tid)
+    } else {
+        (tid, sid)
+    }
+}
+
+#[cfg(test)]
+mod daylight {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::read_smiles;
+
+    fn aromatic(smiles: &str) -> Vec<usize> {
+        let molecule = read_smiles(smiles, None).unwrap();
+
+        Aromaticity::new(&molecule, &Model::Daylight)
+            .unwrap()
+            .atoms()
+    }
+
+    #[test]
+    fn cyclohexane() {
+        assert_eq!(aromatic("C1CCCCC1"), vec![])
+    }
+
+    #[test]
+    fn kekule_benzene() {
+        assert_eq!(aromatic("C1=CC=CC=C1"), vec![0, 1, 2, 3, 4, 5])
+    }
+
+    #[test]
+    fn aromatic_benzene() {
+        assert_eq!(aromatic("c1ccccc1"), vec![0, 1, 2, 3, 4, 5])
+    }
+
+    #[test]
+    fn benzene_bonds() {
+        let molecule = read_smiles("C1=CC=CC=C1C", None).unwrap();
+        let aromaticity =
+            Aromaticity::new(&molecule, &Model::Daylight).unwrap();
+
+        assert!(aromaticity.is_aromatic_bond(0, 1));
+        assert!(aromaticity.is_aromatic_bond(5, 0));
+        assert!(!aromaticity.is_aromatic_bond(5, 6));
+        assert!(!aromaticity.is_aromatic_atom(6))
+    }
+
+    #[test]
+    fn pyridine() {
+        assert_eq!(aromatic("C1=CC=NC=C1"), vec![0, 1, 2, 3, 4, 5])
+    }
+
+    #[test]
+    fn pyrrole() {
+        assert_eq!(aromatic("C1=CNC=C1"), vec![0, 1, 2, 3, 4])
+    }
+
+    #[test]
+    fn furan() {
+        assert_eq!(aromatic("C1=COC=C1"), vec![0, 1, 2, 3, 4])
+    }
+
+    #[test]
+    fn thiophene() {
+        assert_eq!(aromatic("C1=CSC=C1"), vec![0, 1, 2, 3, 4])
+    }
+
+    #[test]
+    fn cyclopentadiene() {
+        assert_eq!(aromatic("C1=CCC=C1"), vec![])
+    }
+
+    #[test]
+    fn cyclopentadienyl_anion() {
+        assert_eq!(aromatic("[CH-]1C=CC=C1"), vec![0, 1, 2, 3, 4])
+    }
+
+    #[test]
+    fn tropylium() {
+        assert_eq!(aromatic("[CH+]1C=CC=CC=C1"), vec![0, 1, 2, 3, 4, 5, 6])
+    }
+
+    #[test]
+    fn cyclooctatetraene() {
+        assert_eq!(aromatic("C1=CC=CC=CC=C1"), vec![])
+    }
+
+    #[test]
+    fn naphthalene() {
+        assert_eq!(aromatic("C1=CC=C2C=CC=CC2=C1").len(), 10)
+    }
+
+    #[test]
+    fn azulene() {
+        assert_eq!(aromatic("C1=CC2=CC=CC=CC2=C1").len(), 10)
+    }
+
+    #[test]
+    fn pyridone() {
+        assert_eq!(aromatic("O=C1C=CC=CN1"), vec![1, 2, 3, 4, 5, 6])
+    }
+
+    #[test]
+    fn benzoquinone() {
+        assert_eq!(aromatic("O=C1C=CC(=O)C=C1"), vec![])
+    }
+
+    #[test]
+    fn methylene_cyclohexadiene() {
+        assert_eq!(aromatic("C=C1C=CC=CC1"), vec![])
+    }
+
+    #[test]
+    fn biphenyl() {
+        let molecule = read_smiles("C1=CC=CC=C1C1=CC=CC=C1", None).unwrap();
+        let aromaticity =
+            Aromaticity::new(&molecule, &Model::Daylight).unwrap();
+
+        assert_eq!(aromaticity.atoms().len(), 12);
+        assert!(!aromaticity.is_aromatic_bond(5, 6))
+    }
+
+    #[test]
+    fn indane() {
+        assert_eq!(aromatic("C1CC2=CC=CC=C2C1"), vec![2, 3, 4, 5, 6, 7])
+    }
+}
+
+#[cfg(test)]
+mod mdl {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::read_smiles;
+
+    fn aromatic(smiles: &str) -> Vec<usize> {
+        let molecule = read_smiles(smiles, None).unwrap();
+
+        Aromaticity::new(&molecule, &Model::Mdl).unwrap().atoms()
+    }
+
+    #[test]
+    fn benzene() {
+        assert_eq!(aromatic("C1=CC=CC=C1"), vec![0, 1, 2, 3, 4, 5])
+    }
+
+    #[test]
+    fn pyridine() {
+        assert_eq!(aromatic("C1=CC=NC=C1"), vec![0, 1, 2, 3, 4, 5])
+    }
+
+    #[test]
+    fn pyrrole() {
+        assert_eq!(aromatic("C1=CNC=C1"), vec![])
+    }
+
+    #[test]
+    fn tropylium() {
+        assert_eq!(aromatic("[CH+]1C=CC=CC=C1"), vec![])
+    }
+
+    #[test]
+    fn pyridone() {
+        assert_eq!(aromatic("O=C1C=CC=CN1"), vec![])
+    }
+
+    #[test]
+    fn azulene() {
+        assert_eq!(aromatic("C1=CC2=CC=CC=CC2=C1").len(), 10)
+    }
+
+    #[test]
+    fn indole() {
+        assert_eq!(aromatic("C1=CC=C2C(=C1)C=CN2"), vec![0, 1, 2, 3, 4, 5])
+    }
+}
@@ -0,0 +1,6 @@
+#[allow(clippy::module_inception)]
+mod aromaticity;
+mod model;
+
+pub use aromaticity::Aromaticity;
+pub use model::Model;
@@ -0,0 +1,15 @@
+/// Rules deciding which ring atoms may take part in an aromatic system,
+/// and how many pi electrons each contributes.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Model {
+    /// Hückel rule over rings and fused ring systems. Atoms with a ring
+    /// double bond contribute one electron, and atoms with a lone pair
+    /// and no double bond (pyrrole N, furan O, carbanions) contribute
+    /// two. Atoms with an empty p orbital (carbocations, boranes) or an
+    /// exocyclic double bond to N, O or S contribute none.
+    Daylight,
+    /// Only C and N atoms with a ring double bond and no exocyclic double
+    /// bond, each contributing one electron. Five-membered rings are
+    /// therefore aromatic only as part of a fused system such as azulene.
+    Mdl,
+}
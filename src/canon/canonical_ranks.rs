@@ -0,0 +1,620 @@
+use std::collections::{HashMap, HashSet};
+
+use gamma::graph::Error;
+
+use crate::aromaticity::{Aromaticity, Model};
+use crate::molecule::{Configuration, Molecule, Parity};
+use crate::stereo::{allene_substituents, is_cis};
+
+/// Returns a unique rank from 0 to order - 1 for each atom in molecule.
+/// Ranks depend only on structure, so two Molecules that differ only in
+/// atom order receive ranks that differ only by the same relabeling.
+///
+/// Atoms are first partitioned by degree, element, isotope, electrons and
+/// hydrogens. Partitions are then refined using neighbor ranks, bond
+/// electrons, and stereo configurations expressed relative to neighbor
+/// ranks. Bonds aromatic under the Daylight model count alike in any
+/// Kekulé form. Ties are broken by trying each tied atom in turn and keeping
+/// the labeling with the lowest form. Branches found to be symmetric with
+/// one already explored are skipped.
+pub fn canonical_ranks<M: Molecule>(
+    molecule: &M,
+) -> Result<HashMap<usize, usize>, Error> {
+    let table = Table::new(molecule)?;
+    let mut search = Search::new(&table);
+
+    search.run(table.initial_ranks(), &mut Vec::new())?;
+
+    Ok(match search.best {
+        Some(best) => table.ids.iter().cloned().zip(best.ranks).collect(),
+        None => HashMap::new(),
+    })
+}
+
+type Key = (usize, u8, Vec<(usize, u8, u8)>);
+
+// Atoms and bonds of a Molecule, indexed from zero in the order of ids.
+struct Table<'a, M: Molecule> {
+    molecule: &'a M,
+    ids: Vec<usize>,
+    indices: HashMap<usize, usize>,
+    invariants: Vec<(usize, u8, u16, u8, u8, bool, u8)>,
+    hydrogens: Vec<bool>,
+    neighbors: Vec<Vec<Neighbor>>,
+}
+
+struct Neighbor {
+    index: usize,
+    electrons: u8,
+    parity: bool,
+}
+
+impl<'a, M: Molecule> Table<'a, M> {
+    fn new(molecule: &'a M) -> Result<Self, Error> {
+        let ids = molecule.ids().collect::<Vec<_>>();
+        let indices = ids
+            .iter()
+            .enumerate()
+            .map(|(index, &id)| (id, index))
+            .collect::<HashMap<_, _>>();
+        let aromaticity = Aromaticity::new(molecule, &Model::Daylight)?;
+        let mut invariants = Vec::new();
+        let mut hydrogens = Vec::new();
+        let mut neighbors = Vec::new();
+
+        for &id in ids.iter() {
+            let atom = molecule.atom(id)?;
+            let mut outs = Vec::new();
+
+            for neighbor in molecule.neighbors(id)? {
+                let bond = match molecule.bond(id, neighbor)? {
+                    Some(bond) => bond,
+                    None => return Err(Error::MissingEdge(id, neighbor)),
+                };
+
+                // aromatic bonds rank alike in every Kekulé form
+                let electrons = if aromaticity.is_aromatic_bond(id, neighbor) {
+                    3
+                } else {
+                    bond.electrons
+                };
+
+                outs.push(Neighbor {
+                    index: indices[&neighbor],
+                    electrons,
+                    parity: bond.parity.is_some(),
+                })
+            }
+
+            invariants.push((
+                outs.len(),
+                atom.element
+                    .as_ref()
+                    .map_or(0, |element| element.atomic_number()),
+                atom.isotope.unwrap_or(0),
+                atom.electrons,
+                atom.hydrogens,
+                atom.parity.is_some(),
+                match &atom.configuration {
+                    None => 0,
+                    Some(Configuration::Allene(_)) => 1,
+                    Some(Configuration::SquarePlanar(_)) => 2,
+                    Some(Configuration::TrigonalBipyramidal(_)) => 3,
+                    Some(Configuration::Octahedral(_)) => 4,
+                },
+            ));
+            hydrogens.push(atom.hydrogens > 0);
+            neighbors.push(outs);
+        }
+
+        Ok(Self {
+            molecule,
+            ids,
+            indices,
+            invariants,
+            hydrogens,
+            neighbors,
+        })
+    }
+
+    fn initial_ranks(&self) -> Vec<usize> {
+        rank_by(&self.invariants)
+    }
+
+    fn key(&self, index: usize, ranks: &[usize]) -> Result<Key, Error> {
+        let mut neighbors = Vec::new();
+
+        for neighbor in self.neighbors[index].iter() {
+            let stereo = if neighbor.parity {
+                self.double_stereo(index, neighbor.index, ranks)?
+            } else {
+                0
+            };
+
+            neighbors.push((ranks[neighbor.index], neighbor.electrons, stereo))
+        }
+
+        neighbors.sort_unstable();
+
+        Ok((ranks[index], self.center_stereo(index, ranks)?, neighbors))
+    }
+
+    // Returns 0 if index has neither parity nor configuration, 1 if the
+    // atoms they refer to can't yet be told apart, or else a code for the
+    // parity or configuration relative to those atoms in ascending rank.
+    fn center_stereo(
+        &self,
+        index: usize,
+        ranks: &[usize],
+    ) -> Result<u8, Error> {
+        let atom = self.molecule.atom(self.ids[index])?;
+
+        if let Some(configuration) = &atom.configuration {
+            return self.configuration_stereo(index, configuration, ranks);
+        }
+
+        let parity = match &atom.parity {
+            Some(parity) => parity,
+            None => return Ok(0),
+        };
+        // Parity is relative to neighbors, led by any implicit hydrogen,
+        // which ranks below every atom.
+        let mut reference = Vec::new();
+
+        if self.hydrogens[index] {
+            reference.push(None)
+        }
+
+        for neighbor in self.neighbors[index].iter() {
+            reference.push(Some(ranks[neighbor.index]))
+        }
+
+        if reference.iter().collect::<HashSet<_>>().len() != reference.len() {
+            return Ok(1);
+        }
+
+        let mut swaps = 0;
+
+        for (i, first) in reference.iter().enumerate() {
+            for second in reference.iter().skip(i + 1) {
+                if first > second {
+                    swaps += 1;
+                }
+            }
+        }
+
+        let parity = if swaps % 2 == 0 {
+            parity.clone()
+        } else {
+            parity.negate()
+        };
+
+        Ok(match parity {
+            Parity::Positive => 2,
+            Parity::Negative => 3,
+        })
+    }
+
+    // Allene substituents are keyed by the rank of their terminal, with any
+    // implicit hydrogen leading. Other configurations refer to neighbors
+    // as tetrahedral parity does.
+    fn configuration_stereo(
+        &self,
+        index: usize,
+        configuration: &Configuration,
+        ranks: &[usize],
+    ) -> Result<u8, Error> {
+        let reference = match configuration {
+            Configuration::Allene(_) => {
+                let indices = &self.indices;
+
+                match allene_substituents(self.ids[index], self.molecule)? {
+                    Some(substituents) => substituents
+                        .into_iter()
+                        .map(|(terminal, neighbor)| {
+                            (
+                                ranks[indices[&terminal]],
+                                neighbor.map(|id| ranks[indices[&id]]),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                    None => return Ok(1),
+                }
+            }
+            _ => {
+                let hydrogens = self.molecule.atom(self.ids[index])?.hydrogens;
+
+                (0..hydrogens)
+                    .map(|_| (ranks[index], None))
+                    .chain(self.neighbors[index].iter().map(|neighbor| {
+                        (ranks[index], Some(ranks[neighbor.index]))
+                    }))
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        if reference.iter().collect::<HashSet<_>>().len() != reference.len() {
+            return Ok(1);
+        }
+
+        let mut sorted = reference.clone();
+
+        sorted.sort_unstable();
+
+        Ok(match configuration.permute(&reference, &sorted) {
+            Some(Configuration::Allene(Parity::Positive)) => 2,
+            Some(Configuration::Allene(Parity::Negative)) => 3,
+            Some(Configuration::SquarePlanar(class))
+            | Some(Configuration::TrigonalBipyramidal(class))
+            | Some(Configuration::Octahedral(class)) => class + 1,
+            None => 1,
+        })
+    }
+
+    // Returns 1 if the substituents at either end of the bond can't yet be
+    // told apart, 2 if the highest ranked substituents are cis, or 3 if
+    // they are trans.
+    fn double_stereo(
+        &self,
+        source: usize,
+        target: usize,
+        ranks: &[usize],
+    ) -> Result<u8, Error> {
+        let left = match self.highest(source, target, ranks) {
+            Some(left) => left,
+            None => return Ok(1),
+        };
+        let right = match self.highest(target, source, ranks) {
+            Some(right) => right,
+            None => return Ok(1),
+        };
+        let ids = &self.ids;
+
+        Ok(
+            match is_cis(
+                self.molecule,
+                ids[source],
+                ids[target],
+                ids[left],
+                ids[right],
+            )? {
+                Some(true) => 2,
+                Some(false) => 3,
+                None => 0,
+            },
+        )
+    }
+
+    // Returns the highest-ranked neighbor of index other than partner, or
+    // None if no neighbor ranks uniquely highest.
+    fn highest(
+        &self,
+        index: usize,
+        partner: usize,
+        ranks: &[usize],
+    ) -> Option<usize> {
+        let mut result = None;
+        let mut tied = false;
+
+        for neighbor in self.neighbors[index].iter() {
+            if neighbor.index == partner {
+                continue;
+            }
+
+            match result {
+                Some(highest) if ranks[highest] > ranks[neighbor.index] => (),
+                Some(highest) if ranks[highest] == ranks[neighbor.index] => {
+                    tied = true
+                }
+                _ => {
+                    result = Some(neighbor.index);
+                    tied = false;
+                }
+            }
+        }
+
+        if tied {
+            None
+        } else {
+            result
+        }
+    }
+
+    fn refine(
+        &self,
+        mut ranks: Vec<usize>,
+    ) -> Result<(Vec<usize>, Vec<Key>), Error> {
+        let mut count = classes(&ranks);
+
+        loop {
+            let mut keys = Vec::with_capacity(ranks.len());
+
+            for index in 0..ranks.len() {
+                keys.push(self.key(index, &ranks)?);
+            }
+
+            let next = rank_by(&keys);
+            let next_count = classes(&next);
+
+            if next_count == count {
+                break Ok((ranks, keys));
+            }
+
+            ranks = next;
+            count = next_count;
+        }
+    }
+}
+
+struct Leaf {
+    form: Vec<Key>,
+    ranks: Vec<usize>,
+}
+
+struct Search<'a, M: Molecule> {
+    table: &'a Table<'a, M>,
+    first: Option<(Leaf, Vec<usize>)>,
+    best: Option<Leaf>,
+    automorphisms: Vec<Vec<usize>>,
+}
+
+impl<'a, M: Molecule> Search<'a, M> {
+    fn new(table: &'a Table<'a, M>) -> Self {
+        Self {
+            table,
+            first: None,
+            best: None,
+            automorphisms: Vec::new(),
+        }
+    }
+
+    // Explores the tree below the node reached by individualizing each
+    // atom in path. Returns the depth to unwind to, if any.
+    fn run(
+        &mut self,
+        ranks: Vec<usize>,
+        path: &mut Vec<usize>,
+    ) -> Result<Option<usize>, Error> {
+        let (ranks, keys) = self.table.refine(ranks)?;
+        let tied = match lowest_tie(&ranks) {
+            Some(tied) => tied,
+            None => {
+                let mut form = keys;
+
+                form.sort_unstable();
+
+                return Ok(self.visit(Leaf { form, ranks }, path));
+            }
+        };
+        let mut explored = Vec::new();
+        let mut orbits = Vec::new();
+        let mut known = None;
+
+        for &chosen in tied.iter() {
+            if known != Some(self.automorphisms.len()) {
+                orbits = self.orbits(path);
+                known = Some(self.automorphisms.len());
+            }
+
+            if explored
+                .iter()
+                .any(|&index| orbits[index] == orbits[chosen])
+            {
+                continue;
+            }
+
+            let mut next = ranks.clone();
+
+            for &index in tied.iter().filter(|&&index| index != chosen) {
+                next[index] += 1;
+            }
+
+            explored.push(chosen);
+            path.push(chosen);
+
+            let unwind = self.run(next, path)?;
+
+            path.pop();
+
+            if let Some(depth) = unwind {
+                if depth < path.len() {
+                    return Ok(Some(depth));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    // A leaf with the same form as the first leaf yields an automorphism.
+    // The subtree where the two paths diverge needs no further search.
+    fn visit(&mut self, leaf: Leaf, path: &[usize]) -> Option<usize> {
+        let (first, first_path) = match &self.first {
+            Some(first) => first,
+            None => {
+                self.best = Some(Leaf {
+                    form: leaf.form.clone(),
+                    ranks: leaf.ranks.clone(),
+                });
+                self.first = Some((leaf, path.to_vec()));
+
+                return None;
+            }
+        };
+
+        if leaf.form == first.form {
+            let depth = path
+                .iter()
+                .zip(first_path.iter())
+                .take_while(|(left, right)| left == right)
+                .count();
+
+            self.automorphisms
+                .push(automorphism(&leaf.ranks, &first.ranks));
+
+            return Some(depth);
+        }
+
+        let best = self.best.as_ref().expect("best");
+
+        if leaf.form == best.form {
+            self.automorphisms
+                .push(automorphism(&leaf.ranks, &best.ranks));
+        } else if leaf.form < best.form {
+            self.best = Some(leaf);
+        }
+
+        None
+    }
+
+    // Returns a representative for each index, shared by every index it
+    // can be mapped onto by automorphisms fixing each atom in path.
+    fn orbits(&self, path: &[usize]) -> Vec<usize> {
+        let mut parents = (0..self.table.ids.len()).collect::<Vec<_>>();
+
+        for automorphism in self.automorphisms.iter() {
+            if path.iter().any(|&index| automorphism[index] != index) {
+                continue;
+            }
+
+            for (source, &target) in automorphism.iter().enumerate() {
+                let source = find(&mut parents, source);
+                let target = find(&mut parents, target);
+
+                parents[source] = target;
+            }
+        }
+
+        (0..parents.len())
+            .map(|index| find(&mut parents, index))
+            .collect()
+    }
+}
+
+// Maps each index to the index holding the same rank in target.
+fn automorphism(source: &[usize], target: &[usize]) -> Vec<usize> {
+    let mut indices = vec![0; target.len()];
+
+    for (index, &rank) in target.iter().enumerate() {
+        indices[rank] = index;
+    }
+
+    source.iter().map(|&rank| indices[rank]).collect()
+}
+
+fn find(parents: &mut [usize], index: usize) -> usize {
+    let parent = parents[index];
+
+    if parent == index {
+        return index;
+    }
+
+    let root = find(parents, parent);
+
+    parents[index] = root;
+
+    root
+}
+
+// Ranks each index by the number of indices with a smaller key.
+pub(super) fn rank_by<K: Ord>(keys: &[K]) -> Vec<usize> {
+    let mut sorted = (0..keys.len()).collect::<Vec<_>>();
+    let mut result = vec![0; keys.len()];
+
+    sorted.sort_unstable_by(|&left, &right| keys[left].cmp(&keys[right]));
+
+    for (position, &index) in sorted.iter().enumerate().skip(1) {
+        let previous = sorted[position - 1];
+
+        result[index] = if keys[previous] == keys[index] {
+            result[previous]
+        } else {
+            position
+        };
+    }
+
+    result
+}
+
+fn classes(ranks: &[usize]) -> usize {
+    ranks.iter().collect::<HashSet<_>>().len()
+}
+
+// Returns the indices sharing the lowest tied rank, in ascending order.
+fn lowest_tie(ranks: &[usize]) -> Option<Vec<usize>> {
+    let mut counts = vec![0; ranks.len()];
+
+    for &rank in ranks.iter() {
+        counts[rank] += 1;
+    }
+
+    let rank = counts.iter().position(|&count| count > 1)?;
+
+    Some(
+        (0..ranks.len())
+            .filter(|&index| ranks[index] == rank)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use gamma::graph::Graph;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::read_smiles;
+    use crate::molecule::DefaultMolecule;
+
+    fn sorted_ranks(molecule: &DefaultMolecule) -> Vec<usize> {
+        let ranks = canonical_ranks(molecule).unwrap();
+
+        molecule.ids().map(|id| ranks[&id]).collect()
+    }
+
+    #[test]
+    fn empty() {
+        let molecule = DefaultMolecule::new(vec![]);
+
+        assert_eq!(sorted_ranks(&molecule), vec![])
+    }
+
+    #[test]
+    fn ethanol() {
+        let molecule = read_smiles("CCO", None).unwrap();
+
+        assert_eq!(sorted_ranks(&molecule), vec![0, 2, 1])
+    }
+
+    #[test]
+    fn ethanol_reversed() {
+        let molecule = read_smiles("OCC", None).unwrap();
+
+        assert_eq!(sorted_ranks(&molecule), vec![1, 2, 0])
+    }
+
+    #[test]
+    fn cyclohexane_unique() {
+        let molecule = read_smiles("C1CCCCC1", None).unwrap();
+        let mut ranks = sorted_ranks(&molecule);
+
+        ranks.sort();
+
+        assert_eq!(ranks, vec![0, 1, 2, 3, 4, 5])
+    }
+
+    #[test]
+    fn bond_electrons() {
+        let molecule = read_smiles("C=CCC", None).unwrap();
+
+        assert_eq!(sorted_ranks(&molecule), vec![0, 2, 3, 1])
+    }
+
+    #[test]
+    fn tetrahedral_enantiomers() {
+        let left = read_smiles("F[C@](Cl)(Br)C", None).unwrap();
+        let right = read_smiles("F[C@@](Cl)(Br)C", None).unwrap();
+
+        assert_eq!(sorted_ranks(&left), sorted_ranks(&right))
+    }
+}
@@ -0,0 +1,5 @@
+mod canonical_ranks;
+mod symmetry_classes;
+
+pub use canonical_ranks::canonical_ranks;
+pub use symmetry_classes::symmetry_classes;
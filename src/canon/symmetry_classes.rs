@@ -0,0 +1,141 @@
+use std::collections::{HashMap, HashSet};
+
+use gamma::graph::Error;
+
+use super::canonical_ranks::rank_by;
+use crate::aromaticity::{Aromaticity, Model};
+use crate::molecule::Molecule;
+
+/// Returns a symmetry class for each atom in molecule. Atoms are first
+/// partitioned by degree, element, isotope, electrons and hydrogens, then
+/// refined by the classes of their neighbors and the electrons of the bonds
+/// to them until no class splits. Bonds perceived as aromatic under the
+/// Daylight model are treated alike, so that Kekulé structures don't break
+/// ring symmetry. Stereo configurations are ignored.
+///
+/// Each class is numbered by the count of atoms in lower classes, so
+/// equivalent atoms share a number. Refinement may fail to separate atoms
+/// that are not related by any symmetry, as in some regular graphs.
+pub fn symmetry_classes<M: Molecule>(
+    molecule: &M,
+) -> Result<HashMap<usize, usize>, Error> {
+    let ids = molecule.ids().collect::<Vec<_>>();
+    let indices = ids
+        .iter()
+        .enumerate()
+        .map(|(index, &id)| (id, index))
+        .collect::<HashMap<_, _>>();
+    let aromaticity = Aromaticity::new(molecule, &Model::Daylight)?;
+    let mut invariants = Vec::new();
+    let mut neighbors = Vec::new();
+
+    for &id in ids.iter() {
+        let atom = molecule.atom(id)?;
+        let mut outs = Vec::new();
+
+        for neighbor in molecule.neighbors(id)? {
+            let electrons = match molecule.bond(id, neighbor)? {
+                Some(_) if aromaticity.is_aromatic_bond(id, neighbor) => 3,
+                Some(bond) => bond.electrons,
+                None => return Err(Error::MissingEdge(id, neighbor)),
+            };
+
+            outs.push((indices[&neighbor], electrons))
+        }
+
+        invariants.push((
+            outs.len(),
+            atom.element
+                .as_ref()
+                .map_or(0, |element| element.atomic_number()),
+            atom.isotope.unwrap_or(0),
+            atom.electrons,
+            atom.hydrogens,
+        ));
+        neighbors.push(outs);
+    }
+
+    let mut classes = rank_by(&invariants);
+    let mut count = count_classes(&classes);
+
+    loop {
+        let keys = neighbors
+            .iter()
+            .enumerate()
+            .map(|(index, outs)| {
+                let mut key = outs
+                    .iter()
+                    .map(|&(neighbor, electrons)| {
+                        (classes[neighbor], electrons)
+                    })
+                    .collect::<Vec<_>>();
+
+                key.sort_unstable();
+
+                (classes[index], key)
+            })
+            .collect::<Vec<_>>();
+        let next = rank_by(&keys);
+        let next_count = count_classes(&next);
+
+        if next_count == count {
+            break;
+        }
+
+        classes = next;
+        count = next_count;
+    }
+
+    Ok(ids.into_iter().zip(classes).collect())
+}
+
+fn count_classes(classes: &[usize]) -> usize {
+    classes.iter().collect::<HashSet<_>>().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use gamma::graph::Graph;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::read_smiles;
+
+    fn classes(smiles: &str) -> Vec<usize> {
+        let molecule = read_smiles(smiles, None).unwrap();
+        let classes = symmetry_classes(&molecule).unwrap();
+
+        molecule.ids().map(|id| classes[&id]).collect()
+    }
+
+    #[test]
+    fn propane() {
+        assert_eq!(classes("CCC"), vec![0, 2, 0])
+    }
+
+    #[test]
+    fn butan_2_ol() {
+        let classes = classes("CC(O)CC");
+
+        assert_eq!(classes.iter().collect::<HashSet<_>>().len(), 5)
+    }
+
+    #[test]
+    fn benzene() {
+        assert_eq!(classes("C1=CC=CC=C1"), vec![0; 6])
+    }
+
+    #[test]
+    fn toluene() {
+        let classes = classes("CC1=CC=CC=C1");
+
+        assert_eq!(classes[2], classes[6]);
+        assert_eq!(classes[3], classes[5]);
+        assert_ne!(classes[2], classes[3])
+    }
+
+    #[test]
+    fn stereo_ignored() {
+        assert_eq!(classes("F[C@H](F)Cl"), classes("FC(F)Cl"))
+    }
+}
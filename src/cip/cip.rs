@@ -0,0 +1,326 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use gamma::graph::Error;
+
+use super::digraph::{Digraph, Node};
+use super::Descriptor;
+use crate::molecule::{Molecule, Parity};
+use crate::stereo::is_cis;
+
+/// CIP descriptors for the stereo atoms and double bonds of a Molecule.
+///
+/// Ligands are ranked over the hierarchical digraph of the 2013 IUPAC
+/// recommendations by Rules 1a (atomic number), 1b (root distance of
+/// duplicates) and 2 (atomic mass). Each sphere is explored in full before
+/// the next. Atoms with a parity receive R or S, and double bonds with a
+/// parity E or Z, provided the ligands can be told apart. Rules 3 to 5,
+/// which rank ligands by their own stereo configurations, are not applied,
+/// so pseudoasymmetric centers receive no descriptor.
+#[derive(Debug, PartialEq)]
+pub struct Cip {
+    atoms: HashMap<usize, Descriptor>,
+    bonds: HashMap<(usize, usize), Descriptor>,
+}
+
+impl Cip {
+    pub fn new<M: Molecule>(molecule: &M) -> Result<Self, Error> {
+        let digraph = Digraph::new(molecule);
+        let mut atoms = HashMap::new();
+        let mut bonds = HashMap::new();
+
+        for id in molecule.ids() {
+            if let Some(parity) = &molecule.atom(id)?.parity {
+                if let Some(descriptor) = tetrahedral(id, parity, &digraph)? {
+                    atoms.insert(id, descriptor);
+                }
+            }
+        }
+
+        for (sid, tid) in molecule.edges() {
+            let has_parity = match molecule.bond(sid, tid)? {
+                Some(bond) => bond.parity.is_some(),
+                None => return Err(Error::MissingEdge(sid, tid)),
+            };
+
+            if has_parity {
+                if let Some(descriptor) = double(sid, tid, molecule, &digraph)?
+                {
+                    bonds.insert(key(sid, tid), descriptor);
+                }
+            }
+        }
+
+        Ok(Self { atoms, bonds })
+    }
+
+    /// Returns the R or S descriptor of the atom at id, if any.
+    pub fn atom(&self, id: usize) -> Option<&Descriptor> {
+        self.atoms.get(&id)
+    }
+
+    /// Returns the E or Z descriptor of the bond joining sid and tid, if
+    /// any.
+    pub fn bond(&self, sid: usize, tid: usize) -> Option<&Descriptor> {
+        self.bonds.get(&key(sid, tid))
+    }
+}
+
+// Parity is relative to the ligands led by any implicit hydrogen. Placing
+// the lowest-ranked ligand first, the others run counterclockwise
+// (Negative) in decreasing rank for R.
+fn tetrahedral<M: Molecule>(
+    id: usize,
+    parity: &Parity,
+    digraph: &Digraph<M>,
+) -> Result<Option<Descriptor>, Error> {
+    let ligands = digraph.ligands(id, &[])?;
+
+    if ligands.len() != 4 {
+        return Ok(None);
+    }
+
+    let ranks = match ranks(&ligands, digraph)? {
+        Some(ranks) => ranks,
+        None => return Ok(None),
+    };
+    // lowest, then highest to second-lowest
+    let order = [0, 3, 2, 1]
+        .iter()
+        .map(|&rank| ranks.iter().position(|&other| other == rank))
+        .collect::<Option<Vec<_>>>()
+        .expect("rank");
+    let mut swaps = 0;
+
+    for (i, left) in order.iter().enumerate() {
+        for right in order.iter().skip(i + 1) {
+            if left > right {
+                swaps += 1;
+            }
+        }
+    }
+
+    let parity = if swaps % 2 == 0 {
+        parity.clone()
+    } else {
+        parity.negate()
+    };
+
+    Ok(Some(match parity {
+        Parity::Negative => Descriptor::R,
+        Parity::Positive => Descriptor::S,
+    }))
+}
+
+fn double<M: Molecule>(
+    sid: usize,
+    tid: usize,
+    molecule: &M,
+    digraph: &Digraph<M>,
+) -> Result<Option<Descriptor>, Error> {
+    let left = match highest(sid, tid, molecule, digraph)? {
+        Some(left) => left,
+        None => return Ok(None),
+    };
+    let right = match highest(tid, sid, molecule, digraph)? {
+        Some(right) => right,
+        None => return Ok(None),
+    };
+
+    let cis = is_cis(molecule, sid, tid, left.0, right.0)?;
+
+    Ok(cis.map(|cis| {
+        if cis == (left.1 == right.1) {
+            Descriptor::Z
+        } else {
+            Descriptor::E
+        }
+    }))
+}
+
+// Returns a neighbor of id other than partner, and whether it is the
+// highest-ranked ligand rather than the other one. None is returned if
+// the ligands tie or there is no neighbor to refer to.
+fn highest<M: Molecule>(
+    id: usize,
+    partner: usize,
+    molecule: &M,
+    digraph: &Digraph<M>,
+) -> Result<Option<(usize, bool)>, Error> {
+    let neighbors = molecule
+        .neighbors(id)?
+        .filter(|&neighbor| neighbor != partner)
+        .collect::<Vec<_>>();
+    let ligands = digraph.ligands(id, &[partner])?;
+    let first = match neighbors.first() {
+        Some(&first) => first,
+        None => return Ok(None),
+    };
+
+    match ligands.len() {
+        1 => Ok(Some((first, true))),
+        2 => {
+            let ranks = match ranks(&ligands, digraph)? {
+                Some(ranks) => ranks,
+                None => return Ok(None),
+            };
+            // the first neighbor is the last ligand if a hydrogen leads
+            let index = ligands.len() - neighbors.len();
+
+            Ok(Some((first, ranks[index] == 1)))
+        }
+        _ => Ok(None),
+    }
+}
+
+// Returns the rank of each ligand, from 0 (lowest), or None if any tie.
+fn ranks<M: Molecule>(
+    ligands: &[Node],
+    digraph: &Digraph<M>,
+) -> Result<Option<Vec<usize>>, Error> {
+    let mut result = vec![0; ligands.len()];
+
+    for (i, left) in ligands.iter().enumerate() {
+        for (j, right) in ligands.iter().enumerate().skip(i + 1) {
+            match digraph.compare(left, right)? {
+                Ordering::Greater => result[i] += 1,
+                Ordering::Less => result[j] += 1,
+                Ordering::Equal => return Ok(None),
+            }
+        }
+    }
+
+    Ok(Some(result))
+}
+
+fn key(sid: usize, tid: usize) -> (usize, usize) {
+    if sid < tid {
+        (sid, tid)
+    } else {
+        (tid, sid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::read_smiles;
+
+    fn atom(smiles: &str, id: usize) -> Option<Descriptor> {
+        let molecule = read_smiles(smiles, None).unwrap();
+
+        Cip::new(&molecule).unwrap().atom(id).cloned()
+    }
+
+    fn bond(smiles: &str, sid: usize, tid: usize) -> Option<Descriptor> {
+        let molecule = read_smiles(smiles, None).unwrap();
+
+        Cip::new(&molecule).unwrap().bond(sid, tid).cloned()
+    }
+
+    #[test]
+    fn no_parity() {
+        assert_eq!(atom("FC(Cl)Br", 1), None)
+    }
+
+    #[test]
+    fn l_alanine() {
+        assert_eq!(atom("N[C@@H](C)C(=O)O", 1), Some(Descriptor::S))
+    }
+
+    #[test]
+    fn d_alanine() {
+        assert_eq!(atom("N[C@H](C)C(=O)O", 1), Some(Descriptor::R))
+    }
+
+    #[test]
+    fn alanine_reordered() {
+        assert_eq!(atom("C[C@H](N)C(=O)O", 1), Some(Descriptor::S))
+    }
+
+    #[test]
+    fn d_glyceraldehyde() {
+        assert_eq!(atom("OC[C@@H](O)C=O", 2), Some(Descriptor::R))
+    }
+
+    #[test]
+    fn l_cysteine() {
+        // sulfur outranks the carboxyl, so L-cysteine is R
+        assert_eq!(atom("N[C@@H](CS)C(=O)O", 1), Some(Descriptor::R))
+    }
+
+    #[test]
+    fn s_butan_2_ol() {
+        assert_eq!(atom("CC[C@H](C)O", 2), Some(Descriptor::S))
+    }
+
+    #[test]
+    fn identical_ligands() {
+        assert_eq!(atom("C[C@H](C)O", 1), None)
+    }
+
+    #[test]
+    fn deep_difference() {
+        // propyl outranks ethyl only at the third sphere
+        assert_eq!(atom("CCC[C@@H](CC)O", 3), Some(Descriptor::R));
+        assert_eq!(atom("CC[C@H](CCC)O", 2), Some(Descriptor::R))
+    }
+
+    #[test]
+    fn isotope() {
+        assert_eq!(atom("[2H][C@](C)(O)[H]", 1), Some(Descriptor::R))
+    }
+
+    #[test]
+    fn isotope_enantiomer() {
+        assert_eq!(atom("[2H][C@@](C)(O)[H]", 1), Some(Descriptor::S))
+    }
+
+    #[test]
+    fn ring() {
+        // (R)-3-methylcyclohexanone
+        assert_eq!(atom("C[C@@H]1CCCC(=O)C1", 1), Some(Descriptor::R))
+    }
+
+    #[test]
+    fn e_butene() {
+        assert_eq!(bond("C/C=C/C", 1, 2), Some(Descriptor::E))
+    }
+
+    #[test]
+    fn z_butene() {
+        assert_eq!(bond("C/C=C\\C", 1, 2), Some(Descriptor::Z))
+    }
+
+    #[test]
+    fn z_bromofluoro() {
+        assert_eq!(bond("F/C(Br)=C/F", 1, 3), Some(Descriptor::Z))
+    }
+
+    #[test]
+    fn e_bromofluoro() {
+        assert_eq!(bond("F/C(Br)=C\\F", 1, 3), Some(Descriptor::E))
+    }
+
+    #[test]
+    fn reversed_bond() {
+        let molecule = read_smiles("C/C=C\\C", None).unwrap();
+
+        assert_eq!(
+            Cip::new(&molecule).unwrap().bond(2, 1),
+            Some(&Descriptor::Z)
+        )
+    }
+
+    #[test]
+    fn tied_double_bond() {
+        assert_eq!(bond("C/C(C)=C/C", 1, 3), None)
+    }
+
+    #[test]
+    fn oxime() {
+        assert_eq!(bond("C/C(F)=N/O", 1, 3), Some(Descriptor::Z))
+    }
+}
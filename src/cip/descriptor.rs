@@ -0,0 +1,9 @@
+/// A CIP stereodescriptor. Tetrahedral centers are R or S, and double
+/// bonds E or Z.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Descriptor {
+    R,
+    S,
+    E,
+    Z,
+}
@@ -0,0 +1,272 @@
+use std::cmp::Ordering;
+
+use gamma::graph::Error;
+
+use crate::molecule::{Element, Molecule};
+
+/// The sequence rules applied, in order. Each is applied to the whole
+/// digraph before the next is tried.
+const RULES: [Rule; 3] = [Rule::AtomicNumber, Rule::RootDistance, Rule::Mass];
+
+#[derive(Clone, Copy)]
+enum Rule {
+    /// Rule 1a: higher atomic number precedes lower.
+    AtomicNumber,
+    /// Rule 1b: a duplicate atom whose original is closer to the root
+    /// precedes one whose original is farther.
+    RootDistance,
+    /// Rule 2: higher atomic mass precedes lower. Atoms without an isotope
+    /// take the average mass of their element.
+    Mass,
+}
+
+/// A node of the hierarchical digraph: an atom reached along a path from
+/// the root, a duplicate standing in for a ring closure or multiple bond,
+/// or an implicit hydrogen.
+#[derive(Debug, Clone)]
+pub struct Node {
+    id: Option<usize>,
+    atomic_number: u8,
+    mass: f64,
+    // depth of this node, or of the node a duplicate stands in for
+    distance: usize,
+    duplicate: bool,
+    path: Vec<usize>,
+}
+
+/// Compares branches of the hierarchical digraph of a Molecule, exploring
+/// each sphere in full before the next.
+pub struct Digraph<'a, M: Molecule> {
+    molecule: &'a M,
+}
+
+impl<'a, M: Molecule> Digraph<'a, M> {
+    pub fn new(molecule: &'a M) -> Self {
+        Self { molecule }
+    }
+
+    /// Returns the ligands of root, led by any implicit hydrogens, then
+    /// neighbors in the order reported by the Molecule. Neighbors listed
+    /// in excluded are skipped.
+    pub fn ligands(
+        &self,
+        root: usize,
+        excluded: &[usize],
+    ) -> Result<Vec<Node>, Error> {
+        let mut result = Vec::new();
+
+        for _ in 0..self.molecule.atom(root)?.hydrogens {
+            result.push(hydrogen(1));
+        }
+
+        for neighbor in self.molecule.neighbors(root)? {
+            if !excluded.contains(&neighbor) {
+                result.push(self.node(
+                    neighbor,
+                    1,
+                    false,
+                    vec![root, neighbor],
+                )?)
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Returns the relative precedence of two branches, Greater meaning
+    /// left precedes right.
+    pub fn compare(
+        &self,
+        left: &Node,
+        right: &Node,
+    ) -> Result<Ordering, Error> {
+        for (index, _) in RULES.iter().enumerate() {
+            let result = self.explore(left, right, &RULES[..=index])?;
+
+            if result != Ordering::Equal {
+                return Ok(result);
+            }
+        }
+
+        Ok(Ordering::Equal)
+    }
+
+    fn explore(
+        &self,
+        left: &Node,
+        right: &Node,
+        rules: &[Rule],
+    ) -> Result<Ordering, Error> {
+        let result = compare_keys(&key(left, rules), &key(right, rules));
+
+        if result != Ordering::Equal {
+            return Ok(result);
+        }
+
+        let mut lefts = vec![left.clone()];
+        let mut rights = vec![right.clone()];
+
+        while !lefts.is_empty() {
+            let mut next_lefts = Vec::new();
+            let mut next_rights = Vec::new();
+
+            for (left, right) in lefts.iter().zip(rights.iter()) {
+                let left = self.children(left, rules)?;
+                let right = self.children(right, rules)?;
+                let result = compare_sets(&left, &right, rules);
+
+                if result != Ordering::Equal {
+                    return Ok(result);
+                }
+
+                next_lefts.extend(left);
+                next_rights.extend(right);
+            }
+
+            lefts = next_lefts;
+            rights = next_rights;
+        }
+
+        Ok(Ordering::Equal)
+    }
+
+    // Returns the children of node in order of decreasing precedence
+    // under rules. Multiple bonds add duplicates of the atom at each end,
+    // and ring closures end in a duplicate. Duplicates and hydrogens have
+    // no children, leaving only phantom atoms beyond them.
+    fn children(
+        &self,
+        node: &Node,
+        rules: &[Rule],
+    ) -> Result<Vec<Node>, Error> {
+        let id = match node.id {
+            Some(id) if !node.duplicate => id,
+            _ => return Ok(Vec::new()),
+        };
+        let depth = node.path.len() - 1;
+        let parent = match node.path.len() {
+            1 => None,
+            length => Some(node.path[length - 2]),
+        };
+        let mut result = Vec::new();
+
+        for neighbor in self.molecule.neighbors(id)? {
+            let order = match self.molecule.bond(id, neighbor)? {
+                Some(bond) => (bond.electrons / 2).max(1),
+                None => return Err(Error::MissingEdge(id, neighbor)),
+            };
+
+            if Some(neighbor) == parent {
+                for _ in 1..order {
+                    result.push(self.node(neighbor, depth - 1, true, vec![])?)
+                }
+            } else if let Some(distance) =
+                node.path.iter().position(|&other| other == neighbor)
+            {
+                for _ in 0..order {
+                    result.push(self.node(neighbor, distance, true, vec![])?)
+                }
+            } else {
+                let mut path = node.path.clone();
+
+                path.push(neighbor);
+                result.push(self.node(neighbor, depth + 1, false, path)?);
+
+                for _ in 1..order {
+                    result.push(self.node(neighbor, depth + 1, true, vec![])?)
+                }
+            }
+        }
+
+        for _ in 0..self.molecule.atom(id)?.hydrogens {
+            result.push(hydrogen(depth + 1));
+        }
+
+        result.sort_by(|a, b| compare_keys(&key(b, rules), &key(a, rules)));
+
+        Ok(result)
+    }
+
+    fn node(
+        &self,
+        id: usize,
+        distance: usize,
+        duplicate: bool,
+        path: Vec<usize>,
+    ) -> Result<Node, Error> {
+        let atom = self.molecule.atom(id)?;
+        let (atomic_number, mass) = match &atom.element {
+            Some(element) => (
+                element.atomic_number(),
+                match atom.isotope {
+                    Some(isotope) => f64::from(isotope),
+                    None => element.atomic_weight(),
+                },
+            ),
+            None => (0, 0.),
+        };
+
+        Ok(Node {
+            id: Some(id),
+            atomic_number,
+            mass,
+            distance,
+            duplicate,
+            path,
+        })
+    }
+}
+
+fn hydrogen(distance: usize) -> Node {
+    Node {
+        id: None,
+        atomic_number: 1,
+        mass: Element::H.atomic_weight(),
+        distance,
+        duplicate: false,
+        path: vec![],
+    }
+}
+
+fn key(node: &Node, rules: &[Rule]) -> Vec<f64> {
+    rules
+        .iter()
+        .map(|rule| match rule {
+            Rule::AtomicNumber => f64::from(node.atomic_number),
+            Rule::RootDistance => -(node.distance as f64),
+            Rule::Mass => node.mass,
+        })
+        .collect()
+}
+
+fn compare_keys(left: &[f64], right: &[f64]) -> Ordering {
+    for (left, right) in left.iter().zip(right.iter()) {
+        match left.partial_cmp(right) {
+            Some(Ordering::Equal) | None => (),
+            Some(result) => return result,
+        }
+    }
+
+    Ordering::Equal
+}
+
+// Compares sets of children in order of precedence. Missing entries are
+// phantom atoms, which rank below every other.
+fn compare_sets(left: &[Node], right: &[Node], rules: &[Rule]) -> Ordering {
+    for index in 0..left.len().max(right.len()) {
+        let result = match (left.get(index), right.get(index)) {
+            (Some(left), Some(right)) => {
+                compare_keys(&key(left, rules), &key(right, rules))
+            }
+            (Some(_), None) => Ordering::Greater,
+            (None, Some(_)) => Ordering::Less,
+            (None, None) => Ordering::Equal,
+        };
+
+        if result != Ordering::Equal {
+            return result;
+        }
+    }
+
+    Ordering::Equal
+}
@@ -0,0 +1,7 @@
+#[allow(clippy::module_inception)]
+mod cip;
+mod descriptor;
+mod digraph;
+
+pub use cip::Cip;
+pub use descriptor::Descriptor;
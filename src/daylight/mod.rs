@@ -1,4 +1,18 @@
+mod smarts;
 mod smiles;
 
+pub use smarts::exact_query;
+pub use smarts::read as read_smarts;
+pub use smarts::write as write_smarts;
+pub use smarts::Error as SmartsInputError;
+pub use smarts::WriteError as SmartsOutputError;
+pub use smarts::{
+    AtomExpr, AtomPrimitive, BondExpr, BondPrimitive, QueryBond, QueryMolecule,
+    QueryNode,
+};
 pub use smiles::read as read_smiles;
-pub use smiles::Error as SmilesInputError;
+pub use smiles::read_aromatic as read_aromatic_smiles;
+pub use smiles::write as write_smiles;
+pub use smiles::write_canonical as write_canonical_smiles;
+pub use smiles::ReadError as SmilesInputError;
+pub use smiles::WriteError as SmilesOutputError;
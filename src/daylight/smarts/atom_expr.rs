@@ -0,0 +1,178 @@
+use gamma::graph::Error;
+
+use super::QueryMolecule;
+use crate::molecule::{Element, Molecule};
+use crate::substructure::{anchored_match, Target};
+
+/// A SMARTS atom primitive. Counts given as None stand for the primitive
+/// written without a number, as in `R` or `x`, meaning at least one.
+#[derive(Debug, PartialEq, Clone)]
+pub enum AtomPrimitive {
+    /// `*`
+    Any,
+    /// `a`
+    Aromatic,
+    /// `A`
+    Aliphatic,
+    /// An element symbol, lowercase if aromatic.
+    Element(Element, bool),
+    /// `#n`
+    AtomicNumber(u8),
+    /// A leading mass number.
+    Isotope(u16),
+    /// `Dn`: explicit connections.
+    Degree(u8),
+    /// `Hn`: implicit and explicit hydrogens.
+    TotalHydrogens(u8),
+    /// `hn`: implicit hydrogens.
+    ImplicitHydrogens(Option<u8>),
+    /// `Rn`: membership in n SSSR rings.
+    RingMembership(Option<u8>),
+    /// `rn`: smallest ring size n.
+    RingSize(Option<u8>),
+    /// `xn`: ring bonds.
+    RingConnectivity(Option<u8>),
+    /// `vn`: total bond order, counting hydrogens.
+    Valence(u8),
+    /// `Xn`: total connections, counting hydrogens.
+    Connectivity(u8),
+    /// `+n` or `-n`.
+    Charge(i8),
+    /// `$(...)`: the atom begins a match of the query.
+    Recursive(Box<QueryMolecule>),
+}
+
+/// A SMARTS atom expression. `And` covers both the `&` and `;` operators,
+/// which differ only in precedence.
+#[derive(Debug, PartialEq, Clone)]
+pub enum AtomExpr {
+    Primitive(AtomPrimitive),
+    Not(Box<AtomExpr>),
+    And(Vec<AtomExpr>),
+    Or(Vec<AtomExpr>),
+}
+
+impl AtomExpr {
+    /// Returns true if the atom at id of target satisfies this expression.
+    pub fn matches<M: Molecule>(
+        &self,
+        target: &Target<M>,
+        id: usize,
+    ) -> Result<bool, Error> {
+        match self {
+            AtomExpr::Primitive(primitive) => {
+                primitive_matches(primitive, target, id)
+            }
+            AtomExpr::Not(expr) => Ok(!expr.matches(target, id)?),
+            AtomExpr::And(exprs) => {
+                for expr in exprs {
+                    if !expr.matches(target, id)? {
+                        return Ok(false);
+                    }
+                }
+
+                Ok(true)
+            }
+            AtomExpr::Or(exprs) => {
+                for expr in exprs {
+                    if expr.matches(target, id)? {
+                        return Ok(true);
+                    }
+                }
+
+                Ok(false)
+            }
+        }
+    }
+}
+
+fn primitive_matches<M: Molecule>(
+    primitive: &AtomPrimitive,
+    target: &Target<M>,
+    id: usize,
+) -> Result<bool, Error> {
+    let molecule = target.molecule();
+    let atom = molecule.atom(id)?;
+    let aromatic = target.aromaticity().is_aromatic_atom(id);
+    let rings = target.rings();
+
+    Ok(match primitive {
+        AtomPrimitive::Any => true,
+        AtomPrimitive::Aromatic => aromatic,
+        AtomPrimitive::Aliphatic => !aromatic,
+        AtomPrimitive::Element(element, lowercase) => {
+            atom.element.as_ref() == Some(element) && aromatic == *lowercase
+        }
+        AtomPrimitive::AtomicNumber(number) => {
+            atom.element.as_ref().map(Element::atomic_number) == Some(*number)
+        }
+        AtomPrimitive::Isotope(isotope) => atom.isotope == Some(*isotope),
+        AtomPrimitive::Degree(degree) => {
+            molecule.degree(id)? == usize::from(*degree)
+        }
+        AtomPrimitive::TotalHydrogens(count) => {
+            let mut hydrogens = usize::from(atom.hydrogens);
+
+            for neighbor in molecule.neighbors(id)? {
+                if molecule.atom(neighbor)?.element == Some(Element::H) {
+                    hydrogens += 1;
+                }
+            }
+
+            hydrogens == usize::from(*count)
+        }
+        AtomPrimitive::ImplicitHydrogens(count) => {
+            at_least(usize::from(atom.hydrogens), count)
+        }
+        AtomPrimitive::RingMembership(count) => {
+            at_least(rings.ring_count_of(id), count)
+        }
+        AtomPrimitive::RingSize(size) => match size {
+            Some(size) => {
+                rings.smallest_ring_size_of(id) == Some(usize::from(*size))
+            }
+            None => rings.is_in_ring(id),
+        },
+        AtomPrimitive::RingConnectivity(count) => {
+            let mut bonds = 0;
+
+            for neighbor in molecule.neighbors(id)? {
+                if rings.is_bond_in_ring(id, neighbor) {
+                    bonds += 1;
+                }
+            }
+
+            at_least(bonds, count)
+        }
+        AtomPrimitive::Valence(valence) => {
+            let mut total = usize::from(atom.hydrogens);
+
+            for neighbor in molecule.neighbors(id)? {
+                match molecule.bond(id, neighbor)? {
+                    Some(bond) => total += usize::from(bond.electrons / 2),
+                    None => return Err(Error::MissingEdge(id, neighbor)),
+                }
+            }
+
+            total == usize::from(*valence)
+        }
+        AtomPrimitive::Connectivity(count) => {
+            molecule.degree(id)? + usize::from(atom.hydrogens)
+                == usize::from(*count)
+        }
+        AtomPrimitive::Charge(charge) => {
+            molecule.charge(id)?.round() as i8 == *charge
+        }
+        AtomPrimitive::Recursive(query) => {
+            anchored_match(query.as_ref(), target, id)?
+        }
+    })
+}
+
+// An exact count, or any nonzero count if None.
+fn at_least(value: usize, count: &Option<u8>) -> bool {
+    match count {
+        Some(count) => value == usize::from(*count),
+        None => value > 0,
+    }
+}
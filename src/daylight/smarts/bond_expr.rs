@@ -0,0 +1,123 @@
+use gamma::graph::Error;
+
+use crate::molecule::Molecule;
+use crate::substructure::Target;
+
+/// A SMARTS bond primitive. Directional bonds match as single bonds, and
+/// constrain the configuration of a double bond as checked by
+/// `QueryMolecule`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum BondPrimitive {
+    /// No bond symbol: single or aromatic.
+    Elided,
+    /// `-`
+    Single,
+    /// `=`
+    Double,
+    /// `#`
+    Triple,
+    /// `:`
+    Aromatic,
+    /// `~`
+    Any,
+    /// `@`
+    Ring,
+    /// `/`
+    Up,
+    /// `\`
+    Down,
+}
+
+/// A SMARTS bond expression. `And` covers both the `&` and `;` operators,
+/// which differ only in precedence.
+#[derive(Debug, PartialEq, Clone)]
+pub enum BondExpr {
+    Primitive(BondPrimitive),
+    Not(Box<BondExpr>),
+    And(Vec<BondExpr>),
+    Or(Vec<BondExpr>),
+}
+
+impl BondExpr {
+    /// Returns this expression as read in the opposite direction, with
+    /// `Up` and `Down` exchanged.
+    pub fn reversed(&self) -> BondExpr {
+        match self {
+            BondExpr::Primitive(BondPrimitive::Up) => {
+                BondExpr::Primitive(BondPrimitive::Down)
+            }
+            BondExpr::Primitive(BondPrimitive::Down) => {
+                BondExpr::Primitive(BondPrimitive::Up)
+            }
+            BondExpr::Primitive(primitive) => {
+                BondExpr::Primitive(primitive.clone())
+            }
+            BondExpr::Not(expr) => BondExpr::Not(Box::new(expr.reversed())),
+            BondExpr::And(exprs) => {
+                BondExpr::And(exprs.iter().map(BondExpr::reversed).collect())
+            }
+            BondExpr::Or(exprs) => {
+                BondExpr::Or(exprs.iter().map(BondExpr::reversed).collect())
+            }
+        }
+    }
+
+    /// Returns true if the bond joining sid and tid in target satisfies
+    /// this expression.
+    pub fn matches<M: Molecule>(
+        &self,
+        target: &Target<M>,
+        sid: usize,
+        tid: usize,
+    ) -> Result<bool, Error> {
+        match self {
+            BondExpr::Primitive(primitive) => {
+                primitive_matches(primitive, target, sid, tid)
+            }
+            BondExpr::Not(expr) => Ok(!expr.matches(target, sid, tid)?),
+            BondExpr::And(exprs) => {
+                for expr in exprs {
+                    if !expr.matches(target, sid, tid)? {
+                        return Ok(false);
+                    }
+                }
+
+                Ok(true)
+            }
+            BondExpr::Or(exprs) => {
+                for expr in exprs {
+                    if expr.matches(target, sid, tid)? {
+                        return Ok(true);
+                    }
+                }
+
+                Ok(false)
+            }
+        }
+    }
+}
+
+fn primitive_matches<M: Molecule>(
+    primitive: &BondPrimitive,
+    target: &Target<M>,
+    sid: usize,
+    tid: usize,
+) -> Result<bool, Error> {
+    let electrons = match target.molecule().bond(sid, tid)? {
+        Some(bond) => bond.electrons,
+        None => return Err(Error::MissingEdge(sid, tid)),
+    };
+    let aromatic = target.aromaticity().is_aromatic_bond(sid, tid);
+
+    Ok(match primitive {
+        BondPrimitive::Elided => aromatic || electrons == 2,
+        BondPrimitive::Single | BondPrimitive::Up | BondPrimitive::Down => {
+            !aromatic && electrons == 2
+        }
+        BondPrimitive::Double => !aromatic && electrons == 4,
+        BondPrimitive::Triple => electrons == 6,
+        BondPrimitive::Aromatic => aromatic,
+        BondPrimitive::Any => true,
+        BondPrimitive::Ring => target.rings().is_bond_in_ring(sid, tid),
+    })
+}
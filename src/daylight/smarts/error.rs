@@ -0,0 +1,13 @@
+/// An error reading SMARTS. Each cursor is the byte offset of the
+/// offending character.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// An unexpected character.
+    Character(usize),
+    /// The input ended before an atom, bond, branch or bracket closed.
+    EndOfLine,
+    /// A ring closure left open, or closed with conflicting bonds.
+    RingClosure(usize),
+    /// Chirality on an atom without three or four neighbors.
+    Parity(usize),
+}
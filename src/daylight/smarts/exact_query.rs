@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+use gamma::graph::Error;
+
+use super::{
+    AtomExpr, AtomPrimitive, BondExpr, BondPrimitive, QueryBond, QueryMolecule,
+    QueryNode,
+};
+use crate::aromaticity::{Aromaticity, Model};
+use crate::daylight::smiles::{bond_marks, Traversal, WriteError};
+use crate::molecule::{Element, Molecule};
+
+/// Returns a query matching exactly the atoms and bonds of molecule. Each
+/// atom is matched by element, aromaticity, isotope, total hydrogen count
+/// and charge, each bond by order or aromaticity, and each tetrahedral
+/// center by its parity. Aromaticity is perceived with the Daylight model
+/// used by substructure search, so the query always matches molecule
+/// itself. Double bond parities become `/` and `\\` on the flanking single
+/// bonds, as they would be written in SMILES. If they can't all be written
+/// that way, every one is dropped.
+///
+/// Atom ids are renumbered from zero in the order reported by molecule.
+pub fn exact_query<M: Molecule>(molecule: &M) -> Result<QueryMolecule, Error> {
+    let aromaticity = Aromaticity::new(molecule, &Model::Daylight)?;
+    let indices = molecule
+        .ids()
+        .enumerate()
+        .map(|(index, id)| (id, index))
+        .collect::<HashMap<_, _>>();
+    let traversal = Traversal::new(molecule, &indices)?;
+    let marks = match bond_marks(molecule, &traversal) {
+        Ok(marks) => marks,
+        Err(WriteError::Graph(error)) => return Err(error),
+        Err(_) => HashMap::new(),
+    };
+    let mut nodes = Vec::new();
+
+    for id in molecule.ids() {
+        let atom = molecule.atom(id)?;
+        let mut exprs = Vec::new();
+        let mut bonds = Vec::new();
+        let mut hydrogens = atom.hydrogens;
+
+        if let Some(isotope) = atom.isotope {
+            exprs.push(AtomPrimitive::Isotope(isotope));
+        }
+
+        exprs.push(match &atom.element {
+            Some(element) => AtomPrimitive::Element(
+                element.clone(),
+                aromaticity.is_aromatic_atom(id),
+            ),
+            None => AtomPrimitive::Any,
+        });
+
+        for tid in molecule.neighbors(id)? {
+            let bond = match molecule.bond(id, tid)? {
+                Some(bond) => bond,
+                None => return Err(Error::MissingEdge(id, tid)),
+            };
+            let primitive = if aromaticity.is_aromatic_bond(id, tid) {
+                BondPrimitive::Aromatic
+            } else if let Some(&above) = marks.get(&(id, tid)) {
+                if above {
+                    BondPrimitive::Up
+                } else {
+                    BondPrimitive::Down
+                }
+            } else {
+                match bond.electrons {
+                    2 => BondPrimitive::Single,
+                    4 => BondPrimitive::Double,
+                    6 => BondPrimitive::Triple,
+                    _ => BondPrimitive::Any,
+                }
+            };
+
+            if molecule.atom(tid)?.element == Some(Element::H) {
+                hydrogens += 1;
+            }
+
+            bonds.push(QueryBond {
+                expr: BondExpr::Primitive(primitive),
+                tid: indices[&tid],
+            });
+        }
+
+        exprs.push(AtomPrimitive::TotalHydrogens(hydrogens));
+        exprs.push(AtomPrimitive::Charge(molecule.charge(id)?.round() as i8));
+
+        // A leading implicit hydrogen stands where a query places its
+        // unmentioned ligand.
+        let parity = match &atom.parity {
+            Some(parity)
+                if atom.hydrogens <= 1
+                    && bonds.len() + usize::from(atom.hydrogens) == 4 =>
+            {
+                Some(parity.clone())
+            }
+            _ => None,
+        };
+
+        nodes.push(QueryNode {
+            expr: AtomExpr::And(
+                exprs.into_iter().map(AtomExpr::Primitive).collect(),
+            ),
+            parity,
+            bonds,
+        })
+    }
+
+    Ok(QueryMolecule::new(nodes))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::{read_smiles, write_smarts};
+    use crate::substructure::substructure_matches;
+
+    fn count(query: &QueryMolecule, smiles: &str) -> usize {
+        let molecule = read_smiles(smiles, None).unwrap();
+
+        substructure_matches(query, &molecule).unwrap().len()
+    }
+
+    fn smarts(smiles: &str) -> String {
+        let molecule = read_smiles(smiles, None).unwrap();
+
+        write_smarts(&exact_query(&molecule).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn ethanol() {
+        assert_eq!(smarts("CCO"), "[C&H3&+0]-[C&H2&+0]-[O&H1&+0]")
+    }
+
+    #[test]
+    fn isotope_and_charge() {
+        assert_eq!(smarts("[13CH3][NH3+]"), "[13C&H3&+0]-[N&H3&+1]")
+    }
+
+    #[test]
+    fn aromatic() {
+        assert_eq!(
+            smarts("C1=CC=CC=C1"),
+            "[c&H1&+0]:1:[c&H1&+0]:[c&H1&+0]:[c&H1&+0]:[c&H1&+0]:[c&H1&+0]1"
+        )
+    }
+
+    #[test]
+    fn explicit_hydrogen() {
+        assert_eq!(smarts("[H]O[H]"), "[H&H0&+0]-[O&H2&+0]-[H&H0&+0]")
+    }
+
+    #[test]
+    fn matches_itself_only() {
+        let molecule = read_smiles("CC(=O)O", None).unwrap();
+        let query = exact_query(&molecule).unwrap();
+
+        assert_eq!(count(&query, "CC(=O)O"), 1);
+        assert_eq!(count(&query, "CC(=O)[O-]"), 0);
+        assert_eq!(count(&query, "CC(=O)OC"), 0)
+    }
+
+    #[test]
+    fn chirality() {
+        let molecule = read_smiles("N[C@@H](C)C(=O)O", None).unwrap();
+        let query = exact_query(&molecule).unwrap();
+
+        assert_eq!(count(&query, "N[C@@H](C)C(=O)O"), 1);
+        assert_eq!(count(&query, "N[C@H](C)C(=O)O"), 0);
+        assert_eq!(count(&query, "C[C@H](N)C(=O)O"), 1)
+    }
+
+    #[test]
+    fn double_bond_parity() {
+        let molecule = read_smiles("F/C=C/F", None).unwrap();
+        let query = exact_query(&molecule).unwrap();
+
+        assert_eq!(
+            smarts("F/C=C/F"),
+            "[F&H0&+0]\\[C&H1&+0]=[C&H1&+0]\\[F&H0&+0]"
+        );
+        assert_eq!(count(&query, "F/C=C/F"), 2);
+        assert_eq!(count(&query, "F\\C=C\\F"), 2);
+        assert_eq!(count(&query, "F/C=C\\F"), 0);
+        assert_eq!(count(&query, "FC=CF"), 0)
+    }
+
+    #[test]
+    fn chirality_survives_writing() {
+        let molecule = read_smiles("C[C@H](F)Cl", None).unwrap();
+        let text = write_smarts(&exact_query(&molecule).unwrap()).unwrap();
+        let query = crate::daylight::read_smarts(&text).unwrap();
+
+        assert_eq!(count(&query, "C[C@H](F)Cl"), 1);
+        assert_eq!(count(&query, "C[C@@H](F)Cl"), 0)
+    }
+}
@@ -0,0 +1,17 @@
+mod atom_expr;
+mod bond_expr;
+mod error;
+mod exact_query;
+mod query_molecule;
+mod read;
+mod write;
+mod write_error;
+
+pub use atom_expr::{AtomExpr, AtomPrimitive};
+pub use bond_expr::{BondExpr, BondPrimitive};
+pub use error::Error;
+pub use exact_query::exact_query;
+pub use query_molecule::{QueryBond, QueryMolecule, QueryNode};
+pub use read::read;
+pub use write::write;
+pub use write_error::WriteError;
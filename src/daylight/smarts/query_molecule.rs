@@ -0,0 +1,391 @@
+use std::collections::HashMap;
+
+use gamma::graph::{Error, Graph};
+
+use super::{AtomExpr, BondExpr, BondPrimitive};
+use crate::molecule::{Molecule, Parity};
+use crate::stereo::is_cis;
+use crate::substructure::{parity_matches, Query, Target};
+
+/// An atom of a QueryMolecule and the bonds leaving it.
+///
+/// Parity is relative to the order of bonds. If there are only three, it
+/// is relative to an unmentioned ligand followed by the three neighbors.
+#[derive(Debug, PartialEq, Clone)]
+pub struct QueryNode {
+    pub expr: AtomExpr,
+    pub parity: Option<Parity>,
+    pub bonds: Vec<QueryBond>,
+}
+
+/// A bond leaving a QueryNode. The expression reads from that node to
+/// tid, so `/` and `\` are exchanged in the reverse bond.
+#[derive(Debug, PartialEq, Clone)]
+pub struct QueryBond {
+    pub expr: BondExpr,
+    pub tid: usize,
+}
+
+/// A query graph, such as one read from SMARTS, for use in substructure
+/// search. Like DefaultMolecule, ids run from zero in node order and each
+/// bond is stored in both directions.
+#[derive(Debug, PartialEq, Clone)]
+pub struct QueryMolecule {
+    nodes: Vec<QueryNode>,
+    size: usize,
+}
+
+impl QueryMolecule {
+    pub fn new(nodes: Vec<QueryNode>) -> Self {
+        let size = nodes.iter().fold(0, |sum, node| sum + node.bonds.len());
+
+        assert!(size % 2 == 0, "odd bond count");
+
+        Self {
+            nodes,
+            size: size / 2,
+        }
+    }
+
+    /// Returns the expression of the atom at id, or Error if not found.
+    pub fn atom(&self, id: usize) -> Result<&AtomExpr, Error> {
+        Ok(&self.node_for(id)?.expr)
+    }
+
+    /// Returns the chirality of the atom at id, or Error if not found.
+    pub fn parity(&self, id: usize) -> Result<Option<&Parity>, Error> {
+        Ok(self.node_for(id)?.parity.as_ref())
+    }
+
+    /// Returns the expression of the bond joining sid and tid, None if no
+    /// such bond exists, or Error if either id is not found.
+    pub fn bond(
+        &self,
+        sid: usize,
+        tid: usize,
+    ) -> Result<Option<&BondExpr>, Error> {
+        let source = self.node_for(sid)?;
+
+        self.node_for(tid)?;
+
+        Ok(source
+            .bonds
+            .iter()
+            .find(|bond| bond.tid == tid)
+            .map(|bond| &bond.expr))
+    }
+
+    // Directional bonds to either end of a bond mapped to a target double
+    // bond require the configuration they describe. As in SMILES, bonds
+    // read toward the double bond at one end and away from it at the
+    // other have the same direction if trans.
+    fn directional_matches<M: Molecule>(
+        &self,
+        sid: usize,
+        tid: usize,
+        target: &Target<M>,
+        mapping: &HashMap<usize, usize>,
+    ) -> Result<bool, Error> {
+        let (source, destination) = (mapping[&sid], mapping[&tid]);
+
+        match target.molecule().bond(source, destination)? {
+            Some(bond) if bond.electrons == 4 => (),
+            Some(_) => return Ok(true),
+            None => return Err(Error::MissingEdge(source, destination)),
+        }
+
+        let mut left = None;
+        let mut right = None;
+
+        for bond in self.node_for(sid)?.bonds.iter() {
+            if bond.tid != tid && left.is_none() {
+                left = direction(self.bond(bond.tid, sid)?)
+                    .map(|up| (bond.tid, up));
+            }
+        }
+
+        for bond in self.node_for(tid)?.bonds.iter() {
+            if bond.tid != sid && right.is_none() {
+                right = direction(Some(&bond.expr)).map(|up| (bond.tid, up));
+            }
+        }
+
+        let ((left, left_up), (right, right_up)) = match (left, right) {
+            (Some(left), Some(right)) => (left, right),
+            _ => return Ok(true),
+        };
+
+        Ok(is_cis(
+            target.molecule(),
+            source,
+            destination,
+            mapping[&left],
+            mapping[&right],
+        )? == Some(left_up != right_up))
+    }
+
+    fn node_for(&self, id: usize) -> Result<&QueryNode, Error> {
+        match self.nodes.get(id) {
+            Some(node) => Ok(node),
+            None => Err(Error::UnknownId(id)),
+        }
+    }
+}
+
+// Returns true for Up, false for Down, or None for other expressions.
+fn direction(expr: Option<&BondExpr>) -> Option<bool> {
+    match expr {
+        Some(BondExpr::Primitive(BondPrimitive::Up)) => Some(true),
+        Some(BondExpr::Primitive(BondPrimitive::Down)) => Some(false),
+        _ => None,
+    }
+}
+
+impl Query for QueryMolecule {
+    fn atom_matches<M: Molecule>(
+        &self,
+        id: usize,
+        target: &Target<M>,
+        tid: usize,
+    ) -> Result<bool, Error> {
+        self.node_for(id)?.expr.matches(target, tid)
+    }
+
+    fn bond_matches<M: Molecule>(
+        &self,
+        sid: usize,
+        tid: usize,
+        target: &Target<M>,
+        source: usize,
+        destination: usize,
+    ) -> Result<bool, Error> {
+        match self.bond(sid, tid)? {
+            Some(expr) => expr.matches(target, source, destination),
+            None => Err(Error::MissingEdge(sid, tid)),
+        }
+    }
+
+    fn mapping_matches<M: Molecule>(
+        &self,
+        target: &Target<M>,
+        mapping: &HashMap<usize, usize>,
+    ) -> Result<bool, Error> {
+        for (id, node) in self.nodes.iter().enumerate() {
+            let parity = match &node.parity {
+                Some(parity) => parity,
+                None => continue,
+            };
+            let mut order = Vec::new();
+
+            if node.bonds.len() == 3 {
+                order.push(None);
+            }
+
+            for bond in node.bonds.iter() {
+                order.push(Some(mapping[&bond.tid]));
+            }
+
+            if !parity_matches(parity, &order, target.molecule(), mapping[&id])?
+            {
+                return Ok(false);
+            }
+        }
+
+        for (sid, tid) in self.edges() {
+            if !self.directional_matches(sid, tid, target, mapping)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+impl Graph for QueryMolecule {
+    fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn order(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn ids(&self) -> Box<dyn Iterator<Item = usize> + '_> {
+        Box::new(0..self.nodes.len())
+    }
+
+    fn neighbors(
+        &self,
+        id: usize,
+    ) -> Result<Box<dyn Iterator<Item = usize> + '_>, Error> {
+        Ok(Box::new(
+            self.node_for(id)?.bonds.iter().map(|bond| bond.tid),
+        ))
+    }
+
+    fn has_id(&self, id: usize) -> bool {
+        id < self.nodes.len()
+    }
+
+    fn degree(&self, id: usize) -> Result<usize, Error> {
+        Ok(self.node_for(id)?.bonds.len())
+    }
+
+    fn edges(&self) -> Box<dyn Iterator<Item = (usize, usize)> + '_> {
+        Box::new(self.nodes.iter().enumerate().flat_map(|(sid, node)| {
+            node.bonds
+                .iter()
+                .filter(move |bond| bond.tid > sid)
+                .map(move |bond| (sid, bond.tid))
+        }))
+    }
+
+    fn has_edge(&self, sid: usize, tid: usize) -> Result<bool, Error> {
+        Ok(self.bond(sid, tid)?.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::daylight::{read_smarts, read_smiles};
+    use crate::substructure::{substructure_match, substructure_matches};
+
+    fn count(smarts: &str, smiles: &str) -> usize {
+        let query = read_smarts(smarts).unwrap();
+        let molecule = read_smiles(smiles, None).unwrap();
+
+        substructure_matches(&query, &molecule).unwrap().len()
+    }
+
+    fn hit(smarts: &str, smiles: &str) -> bool {
+        let query = read_smarts(smarts).unwrap();
+        let molecule = read_smiles(smiles, None).unwrap();
+
+        substructure_match(&query, &molecule).unwrap().is_some()
+    }
+
+    #[test]
+    fn aliphatic_and_aromatic() {
+        assert_eq!(count("C", "Cc1ccccc1"), 1);
+        assert_eq!(count("c", "Cc1ccccc1"), 6);
+        assert_eq!(count("a", "c1ccncc1"), 6);
+        assert_eq!(count("A", "Cc1ccccc1"), 1)
+    }
+
+    #[test]
+    fn kekule_target() {
+        assert_eq!(count("c:c", "C1=CC=CC=C1"), 12);
+        assert_eq!(count("C=C", "C1=CC=CC=C1"), 0)
+    }
+
+    #[test]
+    fn elided_bond() {
+        assert_eq!(count("cC", "Cc1ccccc1"), 1);
+        assert_eq!(count("CC", "C=C"), 0)
+    }
+
+    #[test]
+    fn carbonyl() {
+        assert!(hit("[CX3]=[OX1]", "CC(=O)C"));
+        assert!(!hit("[CX3]=[OX1]", "CCO"))
+    }
+
+    #[test]
+    fn hydrogen_count() {
+        assert_eq!(count("[CH3]", "CCC"), 2);
+        assert_eq!(count("[CH2]", "CCC"), 1);
+        assert_eq!(count("[OH]", "CC(=O)O"), 1)
+    }
+
+    #[test]
+    fn explicit_hydrogen_counted() {
+        assert_eq!(count("[CH4]", "[H]C([H])([H])[H]"), 1)
+    }
+
+    #[test]
+    fn charge() {
+        assert_eq!(count("[N+]", "C[N+](C)(C)C"), 1);
+        assert_eq!(count("[O-]", "CC(=O)[O-]"), 1);
+        assert_eq!(count("[+0]", "C[N+](C)(C)C"), 4)
+    }
+
+    #[test]
+    fn ring_primitives() {
+        assert_eq!(count("[R]", "CC1CCC1"), 4);
+        assert_eq!(count("[R0]", "CC1CCC1"), 1);
+        assert_eq!(count("[R2]", "C1CCC2CCCCC2C1"), 2);
+        assert_eq!(count("[r4]", "CC1CCC1"), 4);
+        assert_eq!(count("[x3]", "C1CCC2CCCCC2C1"), 2);
+        assert_eq!(count("C@C", "CC1CCC1"), 8);
+        assert_eq!(count("C!@C", "CC1CCC1"), 2)
+    }
+
+    #[test]
+    fn degree_and_valence() {
+        assert_eq!(count("[D3]", "CC(C)C"), 1);
+        assert_eq!(count("[v4]", "CC(C)C"), 4);
+        assert_eq!(count("[#8]", "OC=O"), 2)
+    }
+
+    #[test]
+    fn logic() {
+        assert_eq!(count("[C,N]", "CNO"), 2);
+        assert_eq!(count("[!C]", "CNO"), 2);
+        assert_eq!(count("[C,N;H2]", "CNO"), 0);
+        assert_eq!(count("[C,N;H1]", "CNO"), 1)
+    }
+
+    #[test]
+    fn recursive() {
+        // carbons bearing a hydroxyl
+        assert_eq!(count("[C;$(C[OH])]", "OCCC(O)C"), 2);
+        assert_eq!(count("[$(C=O)]O", "CC(=O)OC"), 1)
+    }
+
+    #[test]
+    fn chirality() {
+        assert!(hit("N[C@@H](C)C(=O)O", "N[C@@H](C)C(=O)O"));
+        assert!(!hit("N[C@@H](C)C(=O)O", "N[C@H](C)C(=O)O"));
+        assert!(hit("N[C@@H](C)C(=O)O", "C[C@H](N)C(=O)O"));
+        assert!(!hit("N[C@@H](C)C(=O)O", "NC(C)C(=O)O"))
+    }
+
+    #[test]
+    fn chirality_with_unmentioned_ligand() {
+        assert!(hit("N[C@@](C)C(=O)O", "N[C@@H](C)C(=O)O"));
+        assert!(!hit("N[C@](C)C(=O)O", "N[C@@H](C)C(=O)O"))
+    }
+
+    #[test]
+    fn disconnected() {
+        assert_eq!(count("[Na+].[Cl-]", "[Na+].[Cl-]"), 1)
+    }
+
+    #[test]
+    fn directional_bonds() {
+        assert!(hit("F/C=C/F", "F/C=C/F"));
+        assert!(hit("F/C=C/F", "F\\C=C\\F"));
+        assert!(!hit("F/C=C/F", "F/C=C\\F"));
+        assert!(hit("F/C=C\\F", "F\\C=C/F"));
+        assert!(!hit("F/C=C/F", "FC=CF"));
+        assert!(hit("FC=CF", "F/C=C\\F"))
+    }
+
+    #[test]
+    fn directional_bonds_reordered() {
+        assert!(hit("C(\\F)=C/F", "F/C=C/F"));
+        assert!(!hit("C(/F)=C/F", "F/C=C/F"));
+        assert!(hit("F/C=C/1.F1", "F/C=C/F"))
+    }
+
+    #[test]
+    fn directional_bond_on_single() {
+        assert!(hit("C/C", "CC"))
+    }
+}
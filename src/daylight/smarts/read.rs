@@ -0,0 +1,985 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use super::{
+    AtomExpr, AtomPrimitive, BondExpr, BondPrimitive, Error, QueryBond,
+    QueryMolecule, QueryNode,
+};
+use crate::molecule::{Element, Parity};
+
+/// Reads a SMARTS string into a QueryMolecule. Atom ids follow the order
+/// in which atoms are written.
+///
+/// Supported are the organic subset, bracket atoms with the primitives
+/// of `AtomPrimitive`, tetrahedral chirality (`@` and `@@`), bond
+/// primitives, the operators `!`, `&`, `,` and `;` at their usual
+/// precedence, branches, ring closures (including `%nn`), components
+/// separated by `.`, and recursive SMARTS. Within a bracket, two-letter
+/// element symbols take precedence over a one-letter symbol followed by
+/// a primitive, and `H` names hydrogen only at the start, and only when
+/// no count follows.
+pub fn read(smarts: &str) -> Result<QueryMolecule, Error> {
+    let mut parser = Parser::new(smarts.as_bytes(), 0);
+
+    parser.molecule()?;
+
+    Ok(QueryMolecule::new(parser.nodes))
+}
+
+struct Open {
+    id: usize,
+    index: usize,
+    expr: Option<BondExpr>,
+    cursor: usize,
+}
+
+struct Parser<'a> {
+    chars: &'a [u8],
+    cursor: usize,
+    // position of chars within the outermost input
+    offset: usize,
+    nodes: Vec<QueryNode>,
+    rings: HashMap<u16, Open>,
+    // atom id, written chirality, cursor and whether the atom has a parent
+    chirals: Vec<(usize, Parity, usize, bool)>,
+    chirality: Option<(Parity, usize)>,
+    leading: bool,
+}
+
+impl<'a> Parser<'a> {
+    fn new(chars: &'a [u8], offset: usize) -> Self {
+        Self {
+            chars,
+            cursor: 0,
+            offset,
+            nodes: Vec::new(),
+            rings: HashMap::new(),
+            chirals: Vec::new(),
+            chirality: None,
+            leading: false,
+        }
+    }
+
+    fn molecule(&mut self) -> Result<(), Error> {
+        loop {
+            self.chain(None)?;
+
+            if self.peek() == Some(b'.') {
+                self.cursor += 1;
+            } else {
+                break;
+            }
+        }
+
+        if self.cursor < self.chars.len() {
+            return Err(self.unexpected());
+        }
+
+        if let Some(cursor) = self.rings.values().map(|open| open.cursor).min()
+        {
+            return Err(Error::RingClosure(cursor));
+        }
+
+        for (id, parity, cursor, parent) in self.chirals.drain(..) {
+            let node = &mut self.nodes[id];
+
+            // The unmentioned ligand follows the parent in SMARTS order,
+            // but leads in a QueryNode.
+            node.parity = match node.bonds.len() {
+                3 if parent => Some(parity.negate()),
+                3 | 4 => Some(parity),
+                _ => return Err(Error::Parity(cursor)),
+            };
+        }
+
+        Ok(())
+    }
+
+    fn chain(
+        &mut self,
+        parent: Option<(usize, Option<BondExpr>)>,
+    ) -> Result<(), Error> {
+        let mut id = self.atom(parent.is_some())?;
+
+        if let Some((parent, expr)) = parent {
+            self.add_bond(parent, id, expr);
+        }
+
+        loop {
+            if self.peek() == Some(b'(') {
+                self.cursor += 1;
+
+                let expr = self.bond()?;
+
+                self.chain(Some((id, expr)))?;
+                self.expect(b')')?;
+
+                continue;
+            }
+
+            let expr = self.bond()?;
+
+            match self.peek() {
+                Some(b'%') | Some(b'0'..=b'9') => self.ring(id, expr)?,
+                Some(next) if is_atom_start(next) => {
+                    let next = self.atom(true)?;
+
+                    self.add_bond(id, next, expr);
+                    id = next;
+                }
+                _ => {
+                    if expr.is_some() {
+                        return Err(self.unexpected());
+                    }
+
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn atom(&mut self, has_parent: bool) -> Result<usize, Error> {
+        let next = match self.peek() {
+            Some(next) => next,
+            None => return Err(Error::EndOfLine),
+        };
+        let expr = match next {
+            b'[' => return self.bracket(has_parent),
+            b'*' => AtomExpr::Primitive(AtomPrimitive::Any),
+            b'A' => AtomExpr::Primitive(AtomPrimitive::Aliphatic),
+            b'a' => AtomExpr::Primitive(AtomPrimitive::Aromatic),
+            b'C' if self.peek_at(1) == Some(b'l') => {
+                self.cursor += 1;
+                element(Element::Cl, false)
+            }
+            b'B' if self.peek_at(1) == Some(b'r') => {
+                self.cursor += 1;
+                element(Element::Br, false)
+            }
+            b'B' => element(Element::B, false),
+            b'C' => element(Element::C, false),
+            b'N' => element(Element::N, false),
+            b'O' => element(Element::O, false),
+            b'P' => element(Element::P, false),
+            b'S' => element(Element::S, false),
+            b'F' => element(Element::F, false),
+            b'I' => element(Element::I, false),
+            b'b' => element(Element::B, true),
+            b'c' => element(Element::C, true),
+            b'n' => element(Element::N, true),
+            b'o' => element(Element::O, true),
+            b'p' => element(Element::P, true),
+            b's' => element(Element::S, true),
+            _ => return Err(self.unexpected()),
+        };
+
+        self.cursor += 1;
+
+        Ok(self.push(expr))
+    }
+
+    fn bracket(&mut self, has_parent: bool) -> Result<usize, Error> {
+        self.cursor += 1;
+        self.leading = true;
+        self.chirality = None;
+
+        let expr = self.low_atom()?;
+
+        self.expect(b']')?;
+
+        let id = self.push(expr);
+
+        if let Some((parity, cursor)) = self.chirality.take() {
+            self.chirals.push((id, parity, cursor, has_parent));
+        }
+
+        Ok(id)
+    }
+
+    fn low_atom(&mut self) -> Result<AtomExpr, Error> {
+        let mut exprs = vec![self.or_atom()?];
+
+        while self.peek() == Some(b';') {
+            self.cursor += 1;
+            exprs.push(self.or_atom()?);
+        }
+
+        Ok(and_atom(exprs))
+    }
+
+    fn or_atom(&mut self) -> Result<AtomExpr, Error> {
+        let mut exprs = vec![self.high_atom()?];
+
+        while self.peek() == Some(b',') {
+            self.cursor += 1;
+            exprs.push(self.high_atom()?);
+        }
+
+        Ok(if exprs.len() == 1 {
+            exprs.pop().expect("expr")
+        } else {
+            AtomExpr::Or(exprs)
+        })
+    }
+
+    fn high_atom(&mut self) -> Result<AtomExpr, Error> {
+        let mut exprs = vec![self.unary_atom()?];
+
+        loop {
+            match self.peek() {
+                Some(b'&') => {
+                    self.cursor += 1;
+                    exprs.push(self.unary_atom()?);
+                }
+                Some(b';') | Some(b',') | Some(b']') | None => break,
+                Some(_) => exprs.push(self.unary_atom()?),
+            }
+        }
+
+        Ok(and_atom(exprs))
+    }
+
+    fn unary_atom(&mut self) -> Result<AtomExpr, Error> {
+        if self.peek() == Some(b'!') {
+            self.cursor += 1;
+            self.leading = false;
+
+            Ok(AtomExpr::Not(Box::new(self.unary_atom()?)))
+        } else {
+            let leading = self.leading;
+            let primitive = self.primitive(leading)?;
+
+            self.leading =
+                leading && matches!(primitive, Some(AtomPrimitive::Isotope(_)));
+
+            Ok(match primitive {
+                Some(primitive) => AtomExpr::Primitive(primitive),
+                None => AtomExpr::And(vec![]),
+            })
+        }
+    }
+
+    // Returns the primitive at the cursor, or None for chirality, which
+    // applies to the atom as a whole.
+    fn primitive(
+        &mut self,
+        leading: bool,
+    ) -> Result<Option<AtomPrimitive>, Error> {
+        let start = self.cursor;
+        let next = match self.peek() {
+            Some(next) => next,
+            None => return Err(Error::EndOfLine),
+        };
+
+        if next.is_ascii_uppercase() {
+            if let Some(second) = self.peek_at(1) {
+                if second.is_ascii_lowercase() {
+                    let symbol = [next, second];
+                    let symbol = std::str::from_utf8(&symbol).expect("ascii");
+
+                    if let Ok(element) = Element::from_str(symbol) {
+                        self.cursor += 2;
+
+                        return Ok(Some(AtomPrimitive::Element(
+                            element, false,
+                        )));
+                    }
+                }
+            }
+        }
+
+        if next.is_ascii_digit() {
+            let isotope = self.number(start)?.expect("number");
+
+            return Ok(Some(AtomPrimitive::Isotope(isotope)));
+        }
+
+        self.cursor += 1;
+
+        let primitive = match next {
+            b'*' => AtomPrimitive::Any,
+            b'A' => AtomPrimitive::Aliphatic,
+            b'a' if self.peek() == Some(b's') => {
+                self.cursor += 1;
+
+                AtomPrimitive::Element(Element::As, true)
+            }
+            b'a' => AtomPrimitive::Aromatic,
+            b's' if self.peek() == Some(b'e') => {
+                self.cursor += 1;
+
+                AtomPrimitive::Element(Element::Se, true)
+            }
+            b'b' => AtomPrimitive::Element(Element::B, true),
+            b'c' => AtomPrimitive::Element(Element::C, true),
+            b'n' => AtomPrimitive::Element(Element::N, true),
+            b'o' => AtomPrimitive::Element(Element::O, true),
+            b'p' => AtomPrimitive::Element(Element::P, true),
+            b's' => AtomPrimitive::Element(Element::S, true),
+            b'#' => match self.number(start)? {
+                Some(number) => match u8::try_from(number) {
+                    Ok(number) => AtomPrimitive::AtomicNumber(number),
+                    Err(_) => {
+                        return Err(Error::Character(self.offset + start))
+                    }
+                },
+                None => return Err(self.unexpected()),
+            },
+            b'H' if leading
+                && !matches!(self.peek(), Some(c) if c.is_ascii_digit()) =>
+            {
+                AtomPrimitive::Element(Element::H, false)
+            }
+            b'D' => AtomPrimitive::Degree(self.small(start)?.unwrap_or(1)),
+            b'H' => {
+                AtomPrimitive::TotalHydrogens(self.small(start)?.unwrap_or(1))
+            }
+            b'h' => AtomPrimitive::ImplicitHydrogens(self.small(start)?),
+            b'R' => AtomPrimitive::RingMembership(self.small(start)?),
+            b'r' => AtomPrimitive::RingSize(self.small(start)?),
+            b'x' => AtomPrimitive::RingConnectivity(self.small(start)?),
+            b'v' => AtomPrimitive::Valence(self.small(start)?.unwrap_or(1)),
+            b'X' => {
+                AtomPrimitive::Connectivity(self.small(start)?.unwrap_or(1))
+            }
+            b'+' | b'-' => AtomPrimitive::Charge(self.charge(next, start)?),
+            b'@' => {
+                let parity = if self.peek() == Some(b'@') {
+                    self.cursor += 1;
+
+                    Parity::Positive
+                } else {
+                    Parity::Negative
+                };
+
+                self.chirality = Some((parity, self.offset + start));
+
+                return Ok(None);
+            }
+            b'$' => AtomPrimitive::Recursive(Box::new(self.recursive()?)),
+            _ => match Element::from_str(
+                std::str::from_utf8(&[next]).unwrap_or_default(),
+            ) {
+                Ok(element) => AtomPrimitive::Element(element, false),
+                Err(_) => {
+                    self.cursor = start;
+
+                    return Err(self.unexpected());
+                }
+            },
+        };
+
+        Ok(Some(primitive))
+    }
+
+    fn charge(&mut self, sign: u8, start: usize) -> Result<i8, Error> {
+        let mut magnitude = 1;
+
+        if let Some(number) = self.number(start)? {
+            magnitude = number;
+        } else {
+            while self.peek() == Some(sign) {
+                self.cursor += 1;
+                magnitude += 1;
+            }
+        }
+
+        let magnitude = match i8::try_from(magnitude) {
+            Ok(magnitude) => magnitude,
+            Err(_) => return Err(Error::Character(self.offset + start)),
+        };
+
+        Ok(if sign == b'-' { -magnitude } else { magnitude })
+    }
+
+    fn recursive(&mut self) -> Result<QueryMolecule, Error> {
+        self.expect(b'(')?;
+
+        let start = self.cursor;
+        let mut depth = 1;
+
+        while depth > 0 {
+            match self.peek() {
+                Some(b'(') => depth += 1,
+                Some(b')') => depth -= 1,
+                Some(_) => (),
+                None => return Err(Error::EndOfLine),
+            }
+
+            self.cursor += 1;
+        }
+
+        let mut parser = Parser::new(
+            &self.chars[start..self.cursor - 1],
+            self.offset + start,
+        );
+
+        if parser.chars.is_empty() {
+            return Err(Error::Character(self.offset + start));
+        }
+
+        parser.molecule()?;
+
+        Ok(QueryMolecule::new(parser.nodes))
+    }
+
+    fn bond(&mut self) -> Result<Option<BondExpr>, Error> {
+        match self.peek() {
+            Some(next) if is_bond_start(next) => Ok(Some(self.low_bond()?)),
+            _ => Ok(None),
+        }
+    }
+
+    fn low_bond(&mut self) -> Result<BondExpr, Error> {
+        let mut exprs = vec![self.or_bond()?];
+
+        while self.peek() == Some(b';') {
+            self.cursor += 1;
+            exprs.push(self.or_bond()?);
+        }
+
+        Ok(and_bond(exprs))
+    }
+
+    fn or_bond(&mut self) -> Result<BondExpr, Error> {
+        let mut exprs = vec![self.high_bond()?];
+
+        while self.peek() == Some(b',') {
+            self.cursor += 1;
+            exprs.push(self.high_bond()?);
+        }
+
+        Ok(if exprs.len() == 1 {
+            exprs.pop().expect("expr")
+        } else {
+            BondExpr::Or(exprs)
+        })
+    }
+
+    fn high_bond(&mut self) -> Result<BondExpr, Error> {
+        let mut exprs = vec![self.unary_bond()?];
+
+        loop {
+            match self.peek() {
+                Some(b'&') => {
+                    self.cursor += 1;
+                    exprs.push(self.unary_bond()?);
+                }
+                Some(next) if is_bond_start(next) => {
+                    exprs.push(self.unary_bond()?)
+                }
+                _ => break,
+            }
+        }
+
+        Ok(and_bond(exprs))
+    }
+
+    fn unary_bond(&mut self) -> Result<BondExpr, Error> {
+        let next = match self.peek() {
+            Some(next) => next,
+            None => return Err(Error::EndOfLine),
+        };
+        let primitive = match next {
+            b'!' => {
+                self.cursor += 1;
+
+                return Ok(BondExpr::Not(Box::new(self.unary_bond()?)));
+            }
+            b'-' => BondPrimitive::Single,
+            b'=' => BondPrimitive::Double,
+            b'#' => BondPrimitive::Triple,
+            b':' => BondPrimitive::Aromatic,
+            b'~' => BondPrimitive::Any,
+            b'@' => BondPrimitive::Ring,
+            b'/' => BondPrimitive::Up,
+            b'\\' => BondPrimitive::Down,
+            _ => return Err(self.unexpected()),
+        };
+
+        self.cursor += 1;
+
+        Ok(BondExpr::Primitive(primitive))
+    }
+
+    fn ring(&mut self, id: usize, expr: Option<BondExpr>) -> Result<(), Error> {
+        let start = self.cursor;
+        let rnum = if self.peek() == Some(b'%') {
+            self.cursor += 1;
+
+            let mut rnum = 0;
+
+            for _ in 0..2 {
+                match self.peek() {
+                    Some(digit @ b'0'..=b'9') => {
+                        rnum = rnum * 10 + u16::from(digit - b'0');
+                        self.cursor += 1;
+                    }
+                    _ => return Err(self.unexpected()),
+                }
+            }
+
+            rnum
+        } else {
+            let digit = self.peek().expect("digit");
+
+            self.cursor += 1;
+
+            u16::from(digit - b'0')
+        };
+        let cursor = self.offset + start;
+
+        let open = match self.rings.remove(&rnum) {
+            Some(open) => open,
+            None => {
+                self.nodes[id].bonds.push(QueryBond {
+                    expr: BondExpr::Primitive(BondPrimitive::Elided),
+                    tid: usize::MAX,
+                });
+                self.rings.insert(
+                    rnum,
+                    Open {
+                        id,
+                        index: self.nodes[id].bonds.len() - 1,
+                        expr,
+                        cursor,
+                    },
+                );
+
+                return Ok(());
+            }
+        };
+        let Open {
+            id: other,
+            index,
+            expr: other_expr,
+            ..
+        } = open;
+        // a bond expression reads from the atom it follows
+        let expr = match (other_expr, expr) {
+            (None, None) => BondExpr::Primitive(BondPrimitive::Elided),
+            (Some(expr), None) => expr.reversed(),
+            (None, Some(expr)) => expr,
+            (Some(left), Some(right)) if left.reversed() == right => right,
+            _ => return Err(Error::RingClosure(cursor)),
+        };
+
+        if other == id
+            || self.nodes[id].bonds.iter().any(|bond| bond.tid == other)
+        {
+            return Err(Error::RingClosure(cursor));
+        }
+
+        self.nodes[other].bonds[index] = QueryBond {
+            expr: expr.reversed(),
+            tid: id,
+        };
+        self.nodes[id].bonds.push(QueryBond { expr, tid: other });
+
+        Ok(())
+    }
+
+    // Reads digits following start, or None if there are none.
+    fn number(&mut self, start: usize) -> Result<Option<u16>, Error> {
+        let mut result: Option<u16> = None;
+
+        while let Some(digit @ b'0'..=b'9') = self.peek() {
+            let value = result
+                .unwrap_or(0)
+                .checked_mul(10)
+                .and_then(|value| value.checked_add(u16::from(digit - b'0')));
+
+            match value {
+                Some(value) => result = Some(value),
+                None => return Err(Error::Character(self.offset + start)),
+            }
+
+            self.cursor += 1;
+        }
+
+        Ok(result)
+    }
+
+    fn small(&mut self, start: usize) -> Result<Option<u8>, Error> {
+        match self.number(start)? {
+            Some(number) => match u8::try_from(number) {
+                Ok(number) => Ok(Some(number)),
+                Err(_) => Err(Error::Character(self.offset + start)),
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn add_bond(&mut self, sid: usize, tid: usize, expr: Option<BondExpr>) {
+        let expr = expr.unwrap_or(BondExpr::Primitive(BondPrimitive::Elided));
+
+        self.nodes[sid].bonds.push(QueryBond {
+            expr: expr.clone(),
+            tid,
+        });
+        self.nodes[tid].bonds.push(QueryBond {
+            expr: expr.reversed(),
+            tid: sid,
+        });
+    }
+
+    fn push(&mut self, expr: AtomExpr) -> usize {
+        self.nodes.push(QueryNode {
+            expr,
+            parity: None,
+            bonds: vec![],
+        });
+
+        self.nodes.len() - 1
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), Error> {
+        if self.peek() == Some(expected) {
+            self.cursor += 1;
+
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.chars.get(self.cursor).cloned()
+    }
+
+    fn peek_at(&self, ahead: usize) -> Option<u8> {
+        self.chars.get(self.cursor + ahead).cloned()
+    }
+
+    fn unexpected(&self) -> Error {
+        if self.cursor < self.chars.len() {
+            Error::Character(self.offset + self.cursor)
+        } else {
+            Error::EndOfLine
+        }
+    }
+}
+
+fn element(element: Element, aromatic: bool) -> AtomExpr {
+    AtomExpr::Primitive(AtomPrimitive::Element(element, aromatic))
+}
+
+// Joins exprs by And, flattening nested Ands and leaving a single expr
+// bare.
+fn and_atom(exprs: Vec<AtomExpr>) -> AtomExpr {
+    let mut result = Vec::new();
+
+    for expr in exprs {
+        match expr {
+            AtomExpr::And(inner) => result.extend(inner),
+            expr => result.push(expr),
+        }
+    }
+
+    if result.len() == 1 {
+        result.pop().expect("expr")
+    } else {
+        AtomExpr::And(result)
+    }
+}
+
+fn and_bond(exprs: Vec<BondExpr>) -> BondExpr {
+    let mut result = Vec::new();
+
+    for expr in exprs {
+        match expr {
+            BondExpr::And(inner) => result.extend(inner),
+            expr => result.push(expr),
+        }
+    }
+
+    if result.len() == 1 {
+        result.pop().expect("expr")
+    } else {
+        BondExpr::And(result)
+    }
+}
+
+fn is_atom_start(next: u8) -> bool {
+    matches!(
+        next,
+        b'[' | b'*'
+            | b'A'
+            | b'a'
+            | b'B'
+            | b'C'
+            | b'N'
+            | b'O'
+            | b'P'
+            | b'S'
+            | b'F'
+            | b'I'
+            | b'b'
+            | b'c'
+            | b'n'
+            | b'o'
+            | b'p'
+            | b's'
+    )
+}
+
+fn is_bond_start(next: u8) -> bool {
+    matches!(
+        next,
+        b'-' | b'=' | b'#' | b':' | b'~' | b'@' | b'/' | b'\\' | b'!'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use gamma::graph::Graph;
+
+    fn atom(smarts: &str) -> AtomExpr {
+        read(smarts).unwrap().atom(0).unwrap().clone()
+    }
+
+    fn primitive(primitive: AtomPrimitive) -> AtomExpr {
+        AtomExpr::Primitive(primitive)
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(read(""), Err(Error::EndOfLine))
+    }
+
+    #[test]
+    fn unexpected_character() {
+        assert_eq!(read("C?"), Err(Error::Character(1)))
+    }
+
+    #[test]
+    fn unclosed_bracket() {
+        assert_eq!(read("[C"), Err(Error::EndOfLine))
+    }
+
+    #[test]
+    fn unclosed_branch() {
+        assert_eq!(read("C(C"), Err(Error::EndOfLine))
+    }
+
+    #[test]
+    fn trailing_bond() {
+        assert_eq!(read("C-"), Err(Error::EndOfLine))
+    }
+
+    #[test]
+    fn unclosed_ring() {
+        assert_eq!(read("CC1CC"), Err(Error::RingClosure(2)))
+    }
+
+    #[test]
+    fn conflicting_ring_bonds() {
+        assert_eq!(read("C=1CC-1"), Err(Error::RingClosure(6)))
+    }
+
+    #[test]
+    fn chirality_without_neighbors() {
+        assert_eq!(read("C[C@](F)"), Err(Error::Parity(3)))
+    }
+
+    #[test]
+    fn organic_subset() {
+        assert_eq!(
+            atom("Cl"),
+            primitive(AtomPrimitive::Element(Element::Cl, false))
+        );
+        assert_eq!(
+            atom("c"),
+            primitive(AtomPrimitive::Element(Element::C, true))
+        );
+        assert_eq!(atom("*"), primitive(AtomPrimitive::Any));
+        assert_eq!(atom("a"), primitive(AtomPrimitive::Aromatic))
+    }
+
+    #[test]
+    fn chain() {
+        let query = read("CC(=O)O").unwrap();
+
+        assert_eq!(query.order(), 4);
+        assert_eq!(query.neighbors(1).unwrap().collect::<Vec<_>>(), [0, 2, 3]);
+        assert_eq!(
+            query.bond(1, 2).unwrap(),
+            Some(&BondExpr::Primitive(BondPrimitive::Double))
+        );
+        assert_eq!(
+            query.bond(1, 3).unwrap(),
+            Some(&BondExpr::Primitive(BondPrimitive::Elided))
+        )
+    }
+
+    #[test]
+    fn ring_closure_order() {
+        let query = read("C1CC(C)C1").unwrap();
+
+        assert_eq!(query.neighbors(0).unwrap().collect::<Vec<_>>(), [4, 1]);
+        assert_eq!(query.neighbors(4).unwrap().collect::<Vec<_>>(), [2, 0])
+    }
+
+    #[test]
+    fn percent_ring_closure() {
+        let query = read("C%12CC%12").unwrap();
+
+        assert_eq!(query.has_edge(0, 2), Ok(true))
+    }
+
+    #[test]
+    fn components() {
+        let query = read("C.O").unwrap();
+
+        assert_eq!(query.order(), 2);
+        assert_eq!(query.size(), 0)
+    }
+
+    #[test]
+    fn bracket_primitives() {
+        assert_eq!(
+            atom("[CH2X4]"),
+            AtomExpr::And(vec![
+                primitive(AtomPrimitive::Element(Element::C, false)),
+                primitive(AtomPrimitive::TotalHydrogens(2)),
+                primitive(AtomPrimitive::Connectivity(4)),
+            ])
+        )
+    }
+
+    #[test]
+    fn two_letter_element() {
+        assert_eq!(
+            atom("[Na+]"),
+            AtomExpr::And(vec![
+                primitive(AtomPrimitive::Element(Element::Na, false)),
+                primitive(AtomPrimitive::Charge(1)),
+            ])
+        )
+    }
+
+    #[test]
+    fn hydrogen() {
+        assert_eq!(
+            atom("[H]"),
+            primitive(AtomPrimitive::Element(Element::H, false))
+        );
+        assert_eq!(
+            atom("[2H]"),
+            AtomExpr::And(vec![
+                primitive(AtomPrimitive::Isotope(2)),
+                primitive(AtomPrimitive::Element(Element::H, false)),
+            ])
+        );
+        assert_eq!(atom("[H2]"), primitive(AtomPrimitive::TotalHydrogens(2)));
+        assert_eq!(
+            atom("[!H]"),
+            AtomExpr::Not(Box::new(primitive(AtomPrimitive::TotalHydrogens(
+                1
+            ))))
+        )
+    }
+
+    #[test]
+    fn counts() {
+        assert_eq!(atom("[D3]"), primitive(AtomPrimitive::Degree(3)));
+        assert_eq!(atom("[R]"), primitive(AtomPrimitive::RingMembership(None)));
+        assert_eq!(
+            atom("[R0]"),
+            primitive(AtomPrimitive::RingMembership(Some(0)))
+        );
+        assert_eq!(atom("[r6]"), primitive(AtomPrimitive::RingSize(Some(6))));
+        assert_eq!(
+            atom("[x2]"),
+            primitive(AtomPrimitive::RingConnectivity(Some(2)))
+        );
+        assert_eq!(atom("[v4]"), primitive(AtomPrimitive::Valence(4)));
+        assert_eq!(
+            atom("[h]"),
+            primitive(AtomPrimitive::ImplicitHydrogens(None))
+        );
+        assert_eq!(atom("[#7]"), primitive(AtomPrimitive::AtomicNumber(7)))
+    }
+
+    #[test]
+    fn charges() {
+        assert_eq!(atom("[-]"), primitive(AtomPrimitive::Charge(-1)));
+        assert_eq!(atom("[--]"), primitive(AtomPrimitive::Charge(-2)));
+        assert_eq!(atom("[+3]"), primitive(AtomPrimitive::Charge(3)))
+    }
+
+    #[test]
+    fn precedence() {
+        let c = primitive(AtomPrimitive::Element(Element::C, false));
+        let n = primitive(AtomPrimitive::Element(Element::N, false));
+        let charge = primitive(AtomPrimitive::Charge(1));
+
+        // high-precedence and binds before or
+        assert_eq!(
+            atom("[C,N&+]"),
+            AtomExpr::Or(vec![
+                c.clone(),
+                AtomExpr::And(vec![n.clone(), charge.clone()])
+            ])
+        );
+        // low-precedence and binds after or
+        assert_eq!(
+            atom("[C,N;+]"),
+            AtomExpr::And(vec![AtomExpr::Or(vec![c, n]), charge])
+        )
+    }
+
+    #[test]
+    fn recursive() {
+        let expr = atom("[$(CO)]");
+
+        assert_eq!(
+            expr,
+            primitive(AtomPrimitive::Recursive(Box::new(read("CO").unwrap())))
+        )
+    }
+
+    #[test]
+    fn recursive_error_position() {
+        assert_eq!(read("C[$(C?)]"), Err(Error::Character(5)))
+    }
+
+    #[test]
+    fn bond_expression() {
+        let query = read("C!@;-,=C").unwrap();
+
+        assert_eq!(
+            query.bond(0, 1).unwrap(),
+            Some(&BondExpr::And(vec![
+                BondExpr::Not(Box::new(BondExpr::Primitive(
+                    BondPrimitive::Ring
+                ))),
+                BondExpr::Or(vec![
+                    BondExpr::Primitive(BondPrimitive::Single),
+                    BondExpr::Primitive(BondPrimitive::Double),
+                ])
+            ]))
+        )
+    }
+
+    #[test]
+    fn chirality() {
+        let query = read("N[C@@H](C)C(=O)O").unwrap();
+
+        // the hydrogen moves from after the parent to the front
+        assert_eq!(query.parity(1), Ok(Some(&Parity::Negative)));
+        assert_eq!(
+            query.atom(1),
+            Ok(&AtomExpr::And(vec![
+                primitive(AtomPrimitive::Element(Element::C, false)),
+                primitive(AtomPrimitive::TotalHydrogens(1)),
+            ]))
+        )
+    }
+}
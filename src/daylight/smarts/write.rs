@@ -0,0 +1,822 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+
+use gamma::graph::{Error as GraphError, Graph};
+
+use super::{
+    AtomExpr, AtomPrimitive, BondExpr, BondPrimitive, QueryMolecule, WriteError,
+};
+use crate::daylight::smiles::Traversal;
+use crate::molecule::{Element, Parity};
+
+/// Writes a SMARTS string for query. Atoms are visited depth-first in id
+/// order.
+///
+/// SMARTS has no parentheses within a bracket, so each expression is
+/// rewritten as a `;` list of `,` lists of `&` lists, pushing negation
+/// down to primitives and distributing `,` over `&` where needed. The
+/// string therefore reads back to an equivalent query, though not always
+/// an identical one. Chirality is restated for the order in which
+/// neighbors are written.
+pub fn write(query: &QueryMolecule) -> Result<String, WriteError> {
+    let ranks = query.ids().map(|id| (id, id)).collect::<HashMap<_, _>>();
+    let traversal = Traversal::new(query, &ranks)?;
+    let mut out = String::new();
+    let mut rnums = Rnums::new();
+    let mut stack = traversal
+        .roots()
+        .iter()
+        .rev()
+        .map(|&root| Unit::Root(root))
+        .collect::<Vec<_>>();
+
+    while let Some(unit) = stack.pop() {
+        let id = match unit {
+            Unit::Root(id) => {
+                if !out.is_empty() {
+                    out.push('.');
+                }
+
+                id
+            }
+            Unit::Chain(id) => {
+                let parent = traversal.parent(id).expect("parent");
+
+                write_bond(parent, id, query, &mut out)?;
+
+                id
+            }
+            Unit::Branch(id) => {
+                let parent = traversal.parent(id).expect("parent");
+
+                out.push('(');
+                write_bond(parent, id, query, &mut out)?;
+
+                id
+            }
+            Unit::Close => {
+                out.push(')');
+
+                continue;
+            }
+        };
+
+        write_atom(id, query, &traversal, &mut out)?;
+
+        let mut released = Vec::new();
+
+        for &partner in traversal.rings(id) {
+            if traversal.position(partner) < traversal.position(id) {
+                let rnum = rnums.close(partner, id);
+
+                write_rnum(rnum, &mut out);
+                released.push(rnum);
+            } else {
+                match rnums.open(id, partner) {
+                    Some(rnum) => {
+                        write_bond(id, partner, query, &mut out)?;
+                        write_rnum(rnum, &mut out)
+                    }
+                    None => return Err(WriteError::RingClosure(id)),
+                }
+            }
+        }
+
+        for rnum in released {
+            rnums.release(rnum);
+        }
+
+        let children = traversal.children(id);
+
+        if let Some((last, rest)) = children.split_last() {
+            stack.push(Unit::Chain(*last));
+
+            for &child in rest.iter().rev() {
+                stack.push(Unit::Close);
+                stack.push(Unit::Branch(child));
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+fn write_atom(
+    id: usize,
+    query: &QueryMolecule,
+    traversal: &Traversal,
+    out: &mut String,
+) -> Result<(), WriteError> {
+    let chirality = match query.parity(id)? {
+        Some(parity) => Some(written_parity(id, parity, query, traversal)?),
+        None => None,
+    };
+    let clauses = normal_form(atom_logic(query.atom(id)?, false));
+
+    if chirality.is_none() {
+        if let Some(symbol) = bare_atom(&clauses) {
+            out.push_str(symbol);
+
+            return Ok(());
+        }
+    }
+
+    let any = vec![vec![vec![(false, AtomPrimitive::Any)]]];
+    let none = vec![vec![(true, AtomPrimitive::Any)]];
+    let clauses = if clauses.is_empty() { &any } else { &clauses };
+    let mut chirality = chirality.map(|parity| match parity {
+        Parity::Positive => "@@",
+        Parity::Negative => "@",
+    });
+    // tracks the reader's notion of a leading position, where H names
+    // hydrogen rather than a hydrogen count
+    let mut leading = true;
+
+    out.push('[');
+
+    for (i, clause) in clauses.iter().enumerate() {
+        if i > 0 {
+            out.push(';');
+        }
+
+        let terms = if clause.is_empty() { &none } else { clause };
+
+        for (j, term) in terms.iter().enumerate() {
+            if j > 0 {
+                out.push(',');
+            }
+
+            let mut after_isotope = false;
+            let mut term = term.iter().collect::<Vec<_>>();
+
+            // a mass number must lead a bracket atom to read as one
+            term.sort_by_key(|(negated, primitive)| {
+                *negated || !matches!(primitive, AtomPrimitive::Isotope(_))
+            });
+
+            for (k, (negated, primitive)) in term.into_iter().enumerate() {
+                let text = atom_text(*negated, primitive, leading)?;
+                let isotope =
+                    !negated && matches!(primitive, AtomPrimitive::Isotope(_));
+
+                // a mass number may run into a following symbol, as in 13C
+                if k > 0 && (!after_isotope || starts_with_digit(&text)) {
+                    out.push('&');
+                }
+
+                out.push_str(&text);
+                leading = leading && isotope;
+                after_isotope = isotope;
+
+                if !isotope {
+                    if let Some(chirality) = chirality.take() {
+                        out.push_str(chirality);
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(chirality) = chirality {
+        out.push_str(chirality);
+    }
+
+    out.push(']');
+
+    Ok(())
+}
+
+// Returns the parity of id relative to the order its neighbors will be
+// read back in: parent, ring closures and openings, then children. As
+// in read, an unmentioned ligand follows the parent in SMARTS but leads
+// in a QueryNode.
+fn written_parity(
+    id: usize,
+    parity: &Parity,
+    query: &QueryMolecule,
+    traversal: &Traversal,
+) -> Result<Parity, WriteError> {
+    let mut stored = query.neighbors(id)?.map(Some).collect::<Vec<_>>();
+    let mut written = traversal
+        .parent(id)
+        .into_iter()
+        .chain(traversal.rings(id).iter().cloned())
+        .chain(traversal.children(id).iter().cloned())
+        .map(Some)
+        .collect::<Vec<_>>();
+
+    match stored.len() {
+        3 => {
+            stored.insert(0, None);
+            written.insert(0, None);
+        }
+        4 => (),
+        _ => return Err(WriteError::Parity(id)),
+    }
+
+    let positions = written
+        .iter()
+        .map(|ligand| stored.iter().position(|other| other == ligand))
+        .collect::<Option<Vec<_>>>()
+        .expect("written neighbor");
+    let mut swaps = 0;
+
+    for (i, left) in positions.iter().enumerate() {
+        for right in positions.iter().skip(i + 1) {
+            if left > right {
+                swaps += 1;
+            }
+        }
+    }
+
+    if stored[0].is_none() && traversal.parent(id).is_some() {
+        swaps += 1;
+    }
+
+    Ok(if swaps % 2 == 0 {
+        parity.clone()
+    } else {
+        parity.negate()
+    })
+}
+
+fn write_bond(
+    sid: usize,
+    tid: usize,
+    query: &QueryMolecule,
+    out: &mut String,
+) -> Result<(), WriteError> {
+    let expr = match query.bond(sid, tid)? {
+        Some(expr) => expr,
+        None => return Err(GraphError::MissingEdge(sid, tid).into()),
+    };
+
+    if expr == &BondExpr::Primitive(BondPrimitive::Elided) {
+        return Ok(());
+    }
+
+    let clauses = normal_form(bond_logic(expr, false));
+    let any = vec![vec![vec![(false, BondPrimitive::Any)]]];
+    let none = vec![vec![(true, BondPrimitive::Any)]];
+    let clauses = if clauses.is_empty() { &any } else { &clauses };
+
+    for (i, clause) in clauses.iter().enumerate() {
+        if i > 0 {
+            out.push(';');
+        }
+
+        let terms = if clause.is_empty() { &none } else { clause };
+
+        for (j, term) in terms.iter().enumerate() {
+            if j > 0 {
+                out.push(',');
+            }
+
+            for (k, (negated, primitive)) in term.iter().enumerate() {
+                if k > 0 {
+                    out.push('&');
+                }
+
+                if *negated {
+                    out.push('!');
+                }
+
+                out.push(match primitive {
+                    BondPrimitive::Single => '-',
+                    BondPrimitive::Double => '=',
+                    BondPrimitive::Triple => '#',
+                    BondPrimitive::Aromatic => ':',
+                    BondPrimitive::Any => '~',
+                    BondPrimitive::Ring => '@',
+                    BondPrimitive::Up => '/',
+                    BondPrimitive::Down => '\\',
+                    BondPrimitive::Elided => unreachable!("expanded"),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Returns the organic subset or wildcard symbol for a lone primitive.
+fn bare_atom(clauses: &[Clause<AtomPrimitive>]) -> Option<&'static str> {
+    let primitive = match clauses {
+        [clause] => match clause.as_slice() {
+            [term] => match term.as_slice() {
+                [(false, primitive)] => primitive,
+                _ => return None,
+            },
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    Some(match primitive {
+        AtomPrimitive::Any => "*",
+        AtomPrimitive::Aromatic => "a",
+        AtomPrimitive::Aliphatic => "A",
+        AtomPrimitive::Element(element, false) => match element {
+            Element::B => "B",
+            Element::C => "C",
+            Element::N => "N",
+            Element::O => "O",
+            Element::P => "P",
+            Element::S => "S",
+            Element::F => "F",
+            Element::Cl => "Cl",
+            Element::Br => "Br",
+            Element::I => "I",
+            _ => return None,
+        },
+        AtomPrimitive::Element(element, true) => match element {
+            Element::B => "b",
+            Element::C => "c",
+            Element::N => "n",
+            Element::O => "o",
+            Element::P => "p",
+            Element::S => "s",
+            _ => return None,
+        },
+        _ => return None,
+    })
+}
+
+fn atom_text(
+    negated: bool,
+    primitive: &AtomPrimitive,
+    leading: bool,
+) -> Result<String, WriteError> {
+    let leading = leading && !negated;
+    let text = match primitive {
+        AtomPrimitive::Any => "*".to_string(),
+        AtomPrimitive::Aromatic => "a".to_string(),
+        AtomPrimitive::Aliphatic => "A".to_string(),
+        AtomPrimitive::Element(Element::H, _) if leading => "H".to_string(),
+        AtomPrimitive::Element(Element::H, _) => "#1".to_string(),
+        AtomPrimitive::Element(element, true) => {
+            element.symbol().to_lowercase()
+        }
+        AtomPrimitive::Element(element, false) => element.symbol().to_string(),
+        AtomPrimitive::AtomicNumber(number) => format!("#{}", number),
+        AtomPrimitive::Isotope(isotope) => isotope.to_string(),
+        AtomPrimitive::Degree(degree) => format!("D{}", degree),
+        AtomPrimitive::TotalHydrogens(count) => format!("H{}", count),
+        AtomPrimitive::ImplicitHydrogens(count) => counted("h", count),
+        AtomPrimitive::RingMembership(count) => counted("R", count),
+        AtomPrimitive::RingSize(size) => counted("r", size),
+        AtomPrimitive::RingConnectivity(count) => counted("x", count),
+        AtomPrimitive::Valence(valence) => format!("v{}", valence),
+        AtomPrimitive::Connectivity(count) => format!("X{}", count),
+        AtomPrimitive::Charge(charge) => format!("{:+}", charge),
+        AtomPrimitive::Recursive(query) => format!("$({})", write(query)?),
+    };
+
+    Ok(if negated { format!("!{}", text) } else { text })
+}
+
+fn counted(symbol: &str, count: &Option<u8>) -> String {
+    match count {
+        Some(count) => format!("{}{}", symbol, count),
+        None => symbol.to_string(),
+    }
+}
+
+fn starts_with_digit(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_digit())
+}
+
+// A primitive, negated if the flag is set.
+type Literal<P> = (bool, P);
+// Literals joined by &, terms by , and clauses by ;.
+type Term<P> = Vec<Literal<P>>;
+type Clause<P> = Vec<Term<P>>;
+
+enum Logic<P> {
+    Literal(bool, P),
+    And(Vec<Logic<P>>),
+    Or(Vec<Logic<P>>),
+}
+
+fn junction<P>(items: Vec<Logic<P>>, or: bool) -> Logic<P> {
+    if or {
+        Logic::Or(items)
+    } else {
+        Logic::And(items)
+    }
+}
+
+fn atom_logic(expr: &AtomExpr, negated: bool) -> Logic<AtomPrimitive> {
+    match expr {
+        AtomExpr::Primitive(primitive) => match primitive {
+            // aromatic symbols outside those a reader accepts
+            AtomPrimitive::Element(element, true)
+                if !matches!(
+                    element,
+                    Element::B
+                        | Element::C
+                        | Element::N
+                        | Element::O
+                        | Element::P
+                        | Element::S
+                        | Element::Se
+                        | Element::As
+                ) =>
+            {
+                atom_logic(
+                    &AtomExpr::And(vec![
+                        AtomExpr::Primitive(AtomPrimitive::AtomicNumber(
+                            element.atomic_number(),
+                        )),
+                        AtomExpr::Primitive(AtomPrimitive::Aromatic),
+                    ]),
+                    negated,
+                )
+            }
+            primitive => Logic::Literal(negated, primitive.clone()),
+        },
+        AtomExpr::Not(expr) => atom_logic(expr, !negated),
+        AtomExpr::And(exprs) => junction(
+            exprs.iter().map(|expr| atom_logic(expr, negated)).collect(),
+            negated,
+        ),
+        AtomExpr::Or(exprs) => junction(
+            exprs.iter().map(|expr| atom_logic(expr, negated)).collect(),
+            !negated,
+        ),
+    }
+}
+
+fn bond_logic(expr: &BondExpr, negated: bool) -> Logic<BondPrimitive> {
+    match expr {
+        BondExpr::Primitive(BondPrimitive::Elided) => bond_logic(
+            &BondExpr::Or(vec![
+                BondExpr::Primitive(BondPrimitive::Single),
+                BondExpr::Primitive(BondPrimitive::Aromatic),
+            ]),
+            negated,
+        ),
+        BondExpr::Primitive(primitive) => {
+            Logic::Literal(negated, primitive.clone())
+        }
+        BondExpr::Not(expr) => bond_logic(expr, !negated),
+        BondExpr::And(exprs) => junction(
+            exprs.iter().map(|expr| bond_logic(expr, negated)).collect(),
+            negated,
+        ),
+        BondExpr::Or(exprs) => junction(
+            exprs.iter().map(|expr| bond_logic(expr, negated)).collect(),
+            !negated,
+        ),
+    }
+}
+
+// Returns clauses to be joined by ;. No clauses means true, and a clause
+// without terms means false.
+fn normal_form<P: Clone>(logic: Logic<P>) -> Vec<Clause<P>> {
+    match logic {
+        Logic::Literal(negated, primitive) => {
+            vec![vec![vec![(negated, primitive)]]]
+        }
+        Logic::And(items) => {
+            let clauses =
+                items.into_iter().flat_map(normal_form).collect::<Vec<_>>();
+
+            if clauses.len() > 1
+                && clauses.iter().all(|clause| clause.len() == 1)
+            {
+                vec![vec![clauses.into_iter().flatten().flatten().collect()]]
+            } else {
+                clauses
+            }
+        }
+        Logic::Or(items) => {
+            let mut result: Option<Vec<Clause<P>>> = None;
+
+            for item in items {
+                let right = normal_form(item);
+
+                result = Some(match result {
+                    None => right,
+                    Some(left) => left
+                        .iter()
+                        .flat_map(|left| {
+                            right.iter().map(move |right| {
+                                left.iter().chain(right).cloned().collect()
+                            })
+                        })
+                        .collect(),
+                });
+            }
+
+            result.unwrap_or_else(|| vec![vec![]])
+        }
+    }
+}
+
+fn write_rnum(rnum: u8, out: &mut String) {
+    if rnum < 10 {
+        write!(out, "{}", rnum).expect("write rnum")
+    } else {
+        write!(out, "%{}", rnum).expect("write rnum")
+    }
+}
+
+enum Unit {
+    Root(usize),
+    Chain(usize),
+    Branch(usize),
+    Close,
+}
+
+struct Rnums {
+    used: BTreeSet<u8>,
+    open: HashMap<(usize, usize), u8>,
+}
+
+impl Rnums {
+    fn new() -> Self {
+        Self {
+            used: BTreeSet::new(),
+            open: HashMap::new(),
+        }
+    }
+
+    fn open(&mut self, sid: usize, tid: usize) -> Option<u8> {
+        let rnum = (1..100).find(|rnum| !self.used.contains(rnum))?;
+
+        self.used.insert(rnum);
+        self.open.insert((sid, tid), rnum);
+
+        Some(rnum)
+    }
+
+    fn close(&mut self, sid: usize, tid: usize) -> u8 {
+        self.open.remove(&(sid, tid)).expect("open rnum")
+    }
+
+    fn release(&mut self, rnum: u8) {
+        self.used.remove(&rnum);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::{read_smarts, read_smiles, QueryBond, QueryNode};
+    use crate::substructure::substructure_matches;
+
+    fn round_trip(smarts: &str) -> String {
+        write(&read_smarts(smarts).unwrap()).unwrap()
+    }
+
+    fn count(query: &QueryMolecule, smiles: &str) -> usize {
+        let molecule = read_smiles(smiles, None).unwrap();
+
+        substructure_matches(query, &molecule).unwrap().len()
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(write(&QueryMolecule::new(vec![])), Ok("".to_string()))
+    }
+
+    #[test]
+    fn organic_subset() {
+        assert_eq!(round_trip("CCl.c1ccncc1*A"), "CCl.c1ccncc1*A")
+    }
+
+    #[test]
+    fn branches_and_rings() {
+        assert_eq!(round_trip("CC(C)(O)C1CC1"), "CC(C)(O)C1CC1")
+    }
+
+    #[test]
+    fn bond_expressions() {
+        assert_eq!(round_trip("C=C#C~C@C!-C-,=C:c"), "C=C#C~C@C!-C-,=C:c")
+    }
+
+    #[test]
+    fn hydrogens() {
+        assert_eq!(round_trip("[H2]"), "[H2]");
+        assert_eq!(round_trip("[H]"), "[H]");
+        assert_eq!(round_trip("[2H]"), "[2H]");
+        assert_eq!(round_trip("[2H1]"), "[2H1]");
+        assert_eq!(round_trip("[H&2]"), "[2H]")
+    }
+
+    #[test]
+    fn directional_bonds() {
+        assert_eq!(round_trip("F/C=C\\F.C/1=C/CC1"), "F/C=C\\F.C/1=C/CC1")
+    }
+
+    #[test]
+    fn elided_bond_in_expression() {
+        assert_eq!(round_trip("C!@C"), "C!@C");
+        assert_eq!(
+            write(&QueryMolecule::new(vec![
+                QueryNode {
+                    expr: AtomExpr::Primitive(AtomPrimitive::Any),
+                    parity: None,
+                    bonds: vec![QueryBond {
+                        expr: BondExpr::Not(Box::new(BondExpr::Primitive(
+                            BondPrimitive::Elided
+                        ))),
+                        tid: 1
+                    }]
+                },
+                QueryNode {
+                    expr: AtomExpr::Primitive(AtomPrimitive::Any),
+                    parity: None,
+                    bonds: vec![QueryBond {
+                        expr: BondExpr::Not(Box::new(BondExpr::Primitive(
+                            BondPrimitive::Elided
+                        ))),
+                        tid: 0
+                    }]
+                }
+            ])),
+            Ok("*!-&!:*".to_string())
+        )
+    }
+
+    #[test]
+    fn ring_bond_written_once() {
+        assert_eq!(round_trip("C=1CCC1"), "C=1CCC1")
+    }
+
+    #[test]
+    fn primitives() {
+        assert_eq!(
+            round_trip("[#6&D3&X4&v4&R&R2&r5&x&x2&h&h1&+0&-2&+3&A]"),
+            "[#6&D3&X4&v4&R&R2&r5&x&x2&h&h1&+0&-2&+3&A]"
+        )
+    }
+
+    #[test]
+    fn isotope() {
+        assert_eq!(round_trip("[13C]"), "[13C]");
+        assert_eq!(round_trip("[2H]"), "[2H]")
+    }
+
+    #[test]
+    fn hydrogen() {
+        assert_eq!(round_trip("[H]"), "[H]");
+        assert_eq!(round_trip("[CH2]"), "[C&H2]");
+        assert_eq!(round_trip("[!H]"), "[!H1]");
+        assert_eq!(round_trip("[C,H]"), "[C,H1]");
+        assert_eq!(round_trip("[2H,C]"), "[2H,C]")
+    }
+
+    #[test]
+    fn leading_hydrogen_count() {
+        let query = QueryMolecule::new(vec![QueryNode {
+            expr: AtomExpr::Primitive(AtomPrimitive::TotalHydrogens(2)),
+            parity: None,
+            bonds: vec![],
+        }]);
+        let smarts = write(&query).unwrap();
+
+        assert_eq!(smarts, "[H2]");
+        assert_eq!(count(&read_smarts(&smarts).unwrap(), "CCO"), 1)
+    }
+
+    #[test]
+    fn aromatic_elements() {
+        assert_eq!(round_trip("[se,as,n]"), "[se,as,n]");
+
+        let query = QueryMolecule::new(vec![QueryNode {
+            expr: AtomExpr::Primitive(AtomPrimitive::Element(
+                Element::Te,
+                true,
+            )),
+            parity: None,
+            bonds: vec![],
+        }]);
+
+        assert_eq!(write(&query), Ok("[#52&a]".to_string()))
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(round_trip("[C,N;H1]"), "[C,N;H1]");
+        assert_eq!(round_trip("[C&H1,N]"), "[C&H1,N]");
+        assert_eq!(round_trip("[!C;!N]"), "[!C&!N]")
+    }
+
+    #[test]
+    fn low_and_within_or_distributed() {
+        let query = QueryMolecule::new(vec![QueryNode {
+            expr: AtomExpr::Or(vec![
+                AtomExpr::Primitive(AtomPrimitive::Element(Element::O, false)),
+                AtomExpr::And(vec![
+                    AtomExpr::Or(vec![
+                        AtomExpr::Primitive(AtomPrimitive::Element(
+                            Element::C,
+                            false,
+                        )),
+                        AtomExpr::Primitive(AtomPrimitive::Element(
+                            Element::N,
+                            false,
+                        )),
+                    ]),
+                    AtomExpr::Primitive(AtomPrimitive::TotalHydrogens(3)),
+                ]),
+            ]),
+            parity: None,
+            bonds: vec![],
+        }]);
+        let smarts = write(&query).unwrap();
+
+        assert_eq!(smarts, "[O,C,N;O,H3]");
+        assert_eq!(count(&read_smarts(&smarts).unwrap(), "CNCCO"), 2)
+    }
+
+    #[test]
+    fn negated_or() {
+        let query = QueryMolecule::new(vec![QueryNode {
+            expr: AtomExpr::Not(Box::new(AtomExpr::Or(vec![
+                AtomExpr::Primitive(AtomPrimitive::Element(Element::C, false)),
+                AtomExpr::Primitive(AtomPrimitive::Element(Element::N, false)),
+            ]))),
+            parity: None,
+            bonds: vec![],
+        }]);
+
+        assert_eq!(write(&query), Ok("[!C&!N]".to_string()))
+    }
+
+    #[test]
+    fn recursive() {
+        assert_eq!(round_trip("[C;$(C[OH])]O"), "[C&$(C[O&H1])]O")
+    }
+
+    #[test]
+    fn chirality() {
+        assert_eq!(round_trip("N[C@@H](C)C(=O)O"), "N[C@@&H1](C)C(=O)O");
+        assert_eq!(round_trip("N[C@H](C)C(=O)O"), "N[C@&H1](C)C(=O)O");
+        assert_eq!(round_trip("[C@@H](N)(C)O"), "[C@@&H1](N)(C)O")
+    }
+
+    #[test]
+    fn chirality_restated_for_written_order() {
+        // ids in an order the writer won't follow
+        let query = read_smarts("N[C@@H](C)C(=O)O").unwrap();
+        let mut nodes = Vec::new();
+
+        for id in query.ids() {
+            let mut bonds = Vec::new();
+
+            for tid in query.neighbors(id).unwrap() {
+                bonds.push(QueryBond {
+                    expr: query.bond(id, tid).unwrap().unwrap().clone(),
+                    tid,
+                })
+            }
+
+            bonds.reverse();
+            nodes.push(QueryNode {
+                expr: query.atom(id).unwrap().clone(),
+                parity: query.parity(id).unwrap().cloned(),
+                bonds,
+            })
+        }
+
+        nodes[1].parity = nodes[1].parity.as_ref().map(Parity::negate);
+
+        let written = read_smarts(&write(&QueryMolecule::new(nodes)).unwrap());
+        let written = written.unwrap();
+
+        assert_eq!(count(&written, "N[C@@H](C)C(=O)O"), 1);
+        assert_eq!(count(&written, "N[C@H](C)C(=O)O"), 0)
+    }
+
+    #[test]
+    fn chirality_in_ring() {
+        let query = read_smarts("C[C@@H]1CC[C@H](C)CC1").unwrap();
+        let written = read_smarts(&write(&query).unwrap()).unwrap();
+
+        for smiles in &["C[C@@H]1CC[C@H](C)CC1", "C[C@H]1CC[C@H](C)CC1"] {
+            assert_eq!(count(&written, smiles), count(&query, smiles))
+        }
+    }
+
+    #[test]
+    fn chirality_with_unmentioned_ligand() {
+        assert_eq!(round_trip("N[C@](C)C(=O)O"), "N[C@](C)C(=O)O");
+        assert_eq!(round_trip("[C@](N)(C)C"), "[C@](N)(C)C")
+    }
+
+    #[test]
+    fn chirality_on_wrong_bond_count() {
+        let query = QueryMolecule::new(vec![QueryNode {
+            expr: AtomExpr::Primitive(AtomPrimitive::Any),
+            parity: Some(Parity::Positive),
+            bonds: vec![],
+        }]);
+
+        assert_eq!(write(&query), Err(WriteError::Parity(0)))
+    }
+}
@@ -0,0 +1,17 @@
+use gamma::graph::Error as GraphError;
+
+/// An error writing SMARTS. Each id is that of the offending atom.
+#[derive(Debug, PartialEq)]
+pub enum WriteError {
+    /// More than 99 ring closures open at once.
+    RingClosure(usize),
+    /// Chirality on an atom without three or four bonds.
+    Parity(usize),
+    Graph(GraphError),
+}
+
+impl From<GraphError> for WriteError {
+    fn from(error: GraphError) -> Self {
+        WriteError::Graph(error)
+    }
+}
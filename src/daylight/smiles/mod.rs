@@ -1,4 +1,11 @@
 mod read;
+mod write;
 
 pub use read::read;
-pub use read::Error;
+pub use read::read_aromatic;
+pub use read::Error as ReadError;
+pub use write::write;
+pub use write::write_canonical;
+pub use write::Error as WriteError;
+
+pub(crate) use write::{bond_marks, Traversal};
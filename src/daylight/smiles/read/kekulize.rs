@@ -5,7 +5,7 @@ use purr::parts::BondKind;
 
 use super::{pi_subgraph, Error};
 
-pub fn kekulize(atoms: &mut Vec<Atom>) -> Result<(), Error> {
+pub fn kekulize(atoms: &mut [Atom]) -> Result<(), Error> {
     let pi = pi_subgraph(atoms);
     let mut pairing = greedy(&pi);
 
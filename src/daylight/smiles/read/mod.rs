@@ -1,6 +1,7 @@
 mod error;
 mod kekulize;
 mod pi_subgraph;
+#[allow(clippy::module_inception)]
 mod read;
 mod to_bond;
 mod to_node;
@@ -9,7 +10,7 @@ mod trigonal_parity;
 pub use error::Error;
 pub use kekulize::kekulize;
 pub use pi_subgraph::pi_subgraph;
-pub use read::read;
+pub use read::{read, read_aromatic};
 pub use to_bond::to_bond;
 pub use to_node::to_node;
 pub use trigonal_parity::trigonal_parity;
@@ -2,7 +2,7 @@ use gamma::graph::{DefaultGraph, Graph};
 use purr::graph::{Atom, Bond};
 use purr::parts::BondKind;
 
-pub fn pi_subgraph(atoms: &Vec<Atom>) -> DefaultGraph {
+pub fn pi_subgraph(atoms: &[Atom]) -> DefaultGraph {
     let mut result = DefaultGraph::new();
     let mut subvalences = vec![];
 
@@ -23,10 +23,10 @@ pub fn pi_subgraph(atoms: &Vec<Atom>) -> DefaultGraph {
             }
 
             match kind {
-                BondKind::Elided => {
-                    if result.has_id(sid) && result.has_id(*tid) {
-                        result.add_edge(sid, *tid).expect("add edge")
-                    }
+                BondKind::Elided
+                    if result.has_id(sid) && result.has_id(*tid) =>
+                {
+                    result.add_edge(sid, *tid).expect("add edge")
                 }
                 BondKind::Aromatic => {
                     if subvalences[sid] > 0 {
@@ -41,10 +41,8 @@ pub fn pi_subgraph(atoms: &Vec<Atom>) -> DefaultGraph {
 
                             result.add_edge(sid, *tid).expect("add edge")
                         }
-                    } else if subvalences[*tid] > 0 {
-                        if !result.has_id(*tid) {
-                            result.add_node(*tid).expect("add target");
-                        }
+                    } else if subvalences[*tid] > 0 && !result.has_id(*tid) {
+                        result.add_node(*tid).expect("add target");
                     }
                 }
                 _ => (),
@@ -1,6 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use purr::graph::{from_tree, Error as GraphError};
+use purr::graph::{from_tree, Atom, Error as GraphError};
 use purr::parts;
 use purr::read::{read as read_tree, Reading};
 
@@ -10,6 +10,24 @@ use crate::molecule::DefaultMolecule;
 pub fn read(
     smiles: &str,
     map: Option<&mut HashMap<usize, u16>>,
+) -> Result<DefaultMolecule, Error> {
+    read_flagged(smiles, map, false)
+}
+
+/// Reads like `read`, and also sets `Node::aromatic` on atoms written in
+/// lowercase and `Bond::aromatic` on bonds written as `:` or elided ring
+/// bonds between two such atoms. Bond electrons remain in Kekulé form.
+pub fn read_aromatic(
+    smiles: &str,
+    map: Option<&mut HashMap<usize, u16>>,
+) -> Result<DefaultMolecule, Error> {
+    read_flagged(smiles, map, true)
+}
+
+fn read_flagged(
+    smiles: &str,
+    map: Option<&mut HashMap<usize, u16>>,
+    flags: bool,
 ) -> Result<DefaultMolecule, Error> {
     let Reading { root, trace } = read_tree(smiles)?;
     let mut atoms = match from_tree(root) {
@@ -24,16 +42,23 @@ pub fn read(
         },
     };
 
+    let aromatics = if flags {
+        Some(aromatic_flags(&atoms))
+    } else {
+        None
+    };
+
     kekulize(&mut atoms)?;
 
     let mut nodes = Vec::new();
 
     if let Some(map) = map {
         for (i, atom) in atoms.iter().enumerate() {
-            if let parts::AtomKind::Bracket { map: klass, .. } = &atom.kind {
-                if let Some(klass) = klass {
-                    map.insert(i, klass.into());
-                }
+            if let parts::AtomKind::Bracket {
+                map: Some(klass), ..
+            } = &atom.kind
+            {
+                map.insert(i, klass.into());
             }
         }
     }
@@ -42,15 +67,73 @@ pub fn read(
         nodes.push(to_node(i, &atoms, &trace)?)
     }
 
+    if let Some((atom_flags, bond_flags)) = aromatics {
+        for (sid, node) in nodes.iter_mut().enumerate() {
+            node.aromatic = atom_flags[sid];
+
+            for bond in node.bonds.iter_mut() {
+                bond.aromatic = bond_flags.contains(&(sid, bond.tid));
+            }
+        }
+    }
+
     Ok(DefaultMolecule::new(nodes))
 }
 
+fn aromatic_flags(atoms: &[Atom]) -> (Vec<bool>, HashSet<(usize, usize)>) {
+    let atom_flags = atoms.iter().map(Atom::is_aromatic).collect::<Vec<_>>();
+    let mut bond_flags = HashSet::new();
+
+    for (sid, atom) in atoms.iter().enumerate() {
+        for bond in atom.bonds.iter() {
+            let aromatic = match bond.kind {
+                parts::BondKind::Aromatic => true,
+                parts::BondKind::Elided => {
+                    atom_flags[sid]
+                        && atom_flags[bond.tid]
+                        && is_ring_bond(atoms, sid, bond.tid)
+                }
+                _ => false,
+            };
+
+            if aromatic {
+                bond_flags.insert((sid, bond.tid));
+            }
+        }
+    }
+
+    (atom_flags, bond_flags)
+}
+
+// Returns true if tid can be reached from sid other than through the bond
+// joining them.
+fn is_ring_bond(atoms: &[Atom], sid: usize, tid: usize) -> bool {
+    let mut visited = HashSet::new();
+    let mut stack = vec![sid];
+
+    visited.insert(sid);
+
+    while let Some(id) = stack.pop() {
+        for bond in atoms[id].bonds.iter() {
+            if id == sid && bond.tid == tid {
+                continue;
+            } else if bond.tid == tid {
+                return true;
+            } else if visited.insert(bond.tid) {
+                stack.push(bond.tid);
+            }
+        }
+    }
+
+    false
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
 
     use super::*;
-    use crate::molecule::{Atom, Bond, Element, Node, Parity};
+    use crate::molecule::{Atom, Bond, Element, Molecule, Node, Parity};
 
     #[test]
     fn invalid_character() {
@@ -102,9 +185,11 @@ mod tests {
                         electrons: 0,
                         hydrogens: 3,
                         isotope: None,
-                        parity: None
+                        parity: None,
+                        configuration: None
                     },
-                    bonds: vec![Bond::new(2, None, 1)]
+                    bonds: vec![Bond::new(2, None, 1)],
+                    aromatic: false
                 },
                 Node {
                     atom: Atom {
@@ -112,9 +197,11 @@ mod tests {
                         electrons: 0,
                         hydrogens: 0,
                         isotope: None,
-                        parity: None
+                        parity: None,
+                        configuration: None
                     },
-                    bonds: vec![Bond::new(2, None, 0)]
+                    bonds: vec![Bond::new(2, None, 0)],
+                    aromatic: false
                 },
             ]))
         )
@@ -131,9 +218,11 @@ mod tests {
                         electrons: 0,
                         hydrogens: 3,
                         isotope: None,
-                        parity: None
+                        parity: None,
+                        configuration: None
                     },
-                    bonds: vec![Bond::new(2, None, 1)]
+                    bonds: vec![Bond::new(2, None, 1)],
+                    aromatic: false
                 },
                 Node {
                     atom: Atom {
@@ -141,9 +230,11 @@ mod tests {
                         electrons: 0,
                         hydrogens: 0,
                         isotope: None,
-                        parity: None
+                        parity: None,
+                        configuration: None
                     },
-                    bonds: vec![Bond::new(2, None, 0)]
+                    bonds: vec![Bond::new(2, None, 0)],
+                    aromatic: false
                 },
             ]))
         )
@@ -160,9 +251,11 @@ mod tests {
                         electrons: 0,
                         hydrogens: 3,
                         isotope: None,
-                        parity: None
+                        parity: None,
+                        configuration: None
                     },
-                    bonds: vec![Bond::new(2, None, 1)]
+                    bonds: vec![Bond::new(2, None, 1)],
+                    aromatic: false
                 },
                 Node {
                     atom: Atom {
@@ -170,9 +263,11 @@ mod tests {
                         electrons: 0,
                         hydrogens: 3,
                         isotope: None,
-                        parity: None
+                        parity: None,
+                        configuration: None
                     },
-                    bonds: vec![Bond::new(2, None, 0)]
+                    bonds: vec![Bond::new(2, None, 0)],
+                    aromatic: false
                 },
             ]))
         )
@@ -189,9 +284,11 @@ mod tests {
                         electrons: 0,
                         hydrogens: 2,
                         isotope: None,
-                        parity: None
+                        parity: None,
+                        configuration: None
                     },
-                    bonds: vec![Bond::new(4, None, 1)]
+                    bonds: vec![Bond::new(4, None, 1)],
+                    aromatic: false
                 },
                 Node {
                     atom: Atom {
@@ -199,9 +296,11 @@ mod tests {
                         electrons: 0,
                         hydrogens: 2,
                         isotope: None,
-                        parity: None
+                        parity: None,
+                        configuration: None
                     },
-                    bonds: vec![Bond::new(4, None, 0)]
+                    bonds: vec![Bond::new(4, None, 0)],
+                    aromatic: false
                 },
             ]))
         )
@@ -218,9 +317,11 @@ mod tests {
                         electrons: 0,
                         hydrogens: 2,
                         isotope: None,
-                        parity: None
+                        parity: None,
+                        configuration: None
                     },
-                    bonds: vec![Bond::new(4, None, 1)]
+                    bonds: vec![Bond::new(4, None, 1)],
+                    aromatic: false
                 },
                 Node {
                     atom: Atom {
@@ -228,9 +329,11 @@ mod tests {
                         electrons: 0,
                         hydrogens: 2,
                         isotope: None,
-                        parity: None
+                        parity: None,
+                        configuration: None
                     },
-                    bonds: vec![Bond::new(4, None, 0)]
+                    bonds: vec![Bond::new(4, None, 0)],
+                    aromatic: false
                 },
             ]))
         )
@@ -247,9 +350,11 @@ mod tests {
                         electrons: 0,
                         hydrogens: 3,
                         isotope: None,
-                        parity: None
+                        parity: None,
+                        configuration: None
                     },
-                    bonds: vec![Bond::new(2, None, 1)]
+                    bonds: vec![Bond::new(2, None, 1)],
+                    aromatic: false
                 },
                 Node {
                     atom: Atom {
@@ -257,12 +362,14 @@ mod tests {
                         electrons: 0,
                         hydrogens: 1,
                         isotope: None,
-                        parity: None
+                        parity: None,
+                        configuration: None
                     },
                     bonds: vec![
                         Bond::new(2, None, 0),
                         Bond::new(4, Some(Parity::Negative), 2)
-                    ]
+                    ],
+                    aromatic: false
                 },
                 Node {
                     atom: Atom {
@@ -270,12 +377,14 @@ mod tests {
                         electrons: 0,
                         hydrogens: 1,
                         isotope: None,
-                        parity: None
+                        parity: None,
+                        configuration: None
                     },
                     bonds: vec![
                         Bond::new(4, Some(Parity::Negative), 1),
                         Bond::new(2, None, 3)
-                    ]
+                    ],
+                    aromatic: false
                 },
                 Node {
                     atom: Atom {
@@ -283,11 +392,58 @@ mod tests {
                         electrons: 0,
                         hydrogens: 3,
                         isotope: None,
-                        parity: None
+                        parity: None,
+                        configuration: None
                     },
-                    bonds: vec![Bond::new(2, None, 2)]
+                    bonds: vec![Bond::new(2, None, 2)],
+                    aromatic: false
                 }
             ]))
         )
     }
+
+    #[test]
+    fn aromatic_flags_unset() {
+        let molecule = read("c1ccccc1", None).unwrap();
+
+        assert_eq!(molecule.is_aromatic(0), Ok(false));
+        assert_eq!(molecule.bond(0, 1).unwrap().unwrap().aromatic, false)
+    }
+
+    #[test]
+    fn aromatic_flags_pyrrole() {
+        let molecule = read_aromatic("Cc1cc[nH]c1", None).unwrap();
+
+        assert_eq!(molecule.is_aromatic(0), Ok(false));
+        assert_eq!(molecule.is_aromatic(5), Ok(true));
+        assert_eq!(molecule.bond(0, 1).unwrap().unwrap().aromatic, false);
+        assert_eq!(molecule.bond(1, 2).unwrap().unwrap().aromatic, true);
+        assert_eq!(molecule.bond(5, 1).unwrap().unwrap().aromatic, true)
+    }
+
+    #[test]
+    fn aromatic_flags_explicit_single() {
+        let molecule = read_aromatic("c1ccccc1-c1ccccc1", None).unwrap();
+
+        assert_eq!(molecule.bond(5, 6).unwrap().unwrap().aromatic, false);
+        assert_eq!(molecule.bond(6, 7).unwrap().unwrap().aromatic, true)
+    }
+
+    #[test]
+    fn aromatic_flags_biphenyl() {
+        let molecule = read_aromatic("c1ccccc1c1ccccc1", None).unwrap();
+
+        assert_eq!(molecule.bond(5, 6).unwrap().unwrap().aromatic, false);
+        assert_eq!(molecule.bond(0, 5).unwrap().unwrap().aromatic, true);
+        assert_eq!(molecule.bond(6, 7).unwrap().unwrap().aromatic, true)
+    }
+
+    #[test]
+    fn aromatic_flags_colon() {
+        let molecule = read_aromatic("C:C", None).unwrap();
+
+        assert_eq!(molecule.is_aromatic(0), Ok(false));
+        assert_eq!(molecule.bond(0, 1).unwrap().unwrap().aromatic, true);
+        assert_eq!(molecule.bond(0, 1).unwrap().unwrap().electrons, 4)
+    }
 }
@@ -49,6 +49,7 @@ pub fn to_bond(
         electrons,
         parity,
         tid: bond.tid,
+        aromatic: false,
     })
 }
 
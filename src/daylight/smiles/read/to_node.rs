@@ -3,7 +3,7 @@ use std::convert::TryFrom;
 use purr::{graph, parts};
 
 use super::{to_bond, Error};
-use crate::molecule::{Atom, Element, Node, Parity};
+use crate::molecule::{Atom, Configuration, Element, Node, Parity};
 
 pub fn to_node(
     id: usize,
@@ -17,8 +17,12 @@ pub fn to_node(
         bonds.push(to_bond(id, bond, atoms, trace)?)
     }
 
-    match to_atom(atom) {
-        Ok(atom) => Ok(Node { atom, bonds }),
+    match to_atom(id, atoms, trace) {
+        Ok(atom) => Ok(Node {
+            atom,
+            bonds,
+            aromatic: false,
+        }),
         Err(error) => match error {
             AtomError::Isotope => Err(Error::Isotope(trace[id])),
             AtomError::Valence => Err(Error::Valence(trace[id])),
@@ -28,7 +32,13 @@ pub fn to_node(
     }
 }
 
-fn to_atom(atom: &graph::Atom) -> Result<Atom, AtomError> {
+fn to_atom(
+    id: usize,
+    atoms: &[graph::Atom],
+    trace: &[usize],
+) -> Result<Atom, AtomError> {
+    let atom = &atoms[id];
+
     match &atom.kind {
         parts::AtomKind::Star => star_to_atom(),
         parts::AtomKind::Aliphatic(aliphatic) => {
@@ -44,14 +54,20 @@ fn to_atom(atom: &graph::Atom) -> Result<Atom, AtomError> {
             charge,
             parity,
             ..
-        } => bracket_to_atom(
-            isotope,
-            symbol,
-            hcount,
-            charge,
-            parity,
-            &atom.bonds,
-        ),
+        } => {
+            let mut result =
+                bracket_to_atom(isotope, symbol, hcount, charge, &atom.bonds)?;
+
+            if let Some(parity) = parity {
+                let (parity, configuration) =
+                    to_stereo(parity, result.hydrogens, id, atoms, trace)?;
+
+                result.parity = parity;
+                result.configuration = configuration;
+            }
+
+            Ok(result)
+        }
     }
 }
 
@@ -62,13 +78,14 @@ fn star_to_atom() -> Result<Atom, AtomError> {
         electrons: 0,
         hydrogens: 0,
         parity: None,
+        configuration: None,
     })
 }
 
 fn bare_to_atom(
     element: Element,
     subvalence: u8,
-    bonds: &Vec<graph::Bond>,
+    bonds: &[graph::Bond],
 ) -> Result<Atom, AtomError> {
     let mut valence = subvalence;
 
@@ -90,6 +107,7 @@ fn bare_to_atom(
         electrons,
         hydrogens: subvalence,
         parity: None,
+        configuration: None,
     })
 }
 
@@ -98,8 +116,7 @@ fn bracket_to_atom(
     symbol: &parts::BracketSymbol,
     hcount: &Option<parts::VirtualHydrogen>,
     charge: &Option<parts::Charge>,
-    parity: &Option<parts::Parity>,
-    bonds: &Vec<graph::Bond>,
+    bonds: &[graph::Bond],
 ) -> Result<Atom, AtomError> {
     let charge = match charge {
         Some(charge) => charge.into(),
@@ -122,14 +139,14 @@ fn bracket_to_atom(
         None => 0,
     };
     let electrons = to_electrons(&element, hydrogens, charge, bonds)?;
-    let parity = to_parity(hydrogens, parity, bonds)?;
 
     Ok(Atom {
         element,
         isotope,
         electrons,
         hydrogens,
-        parity,
+        parity: None,
+        configuration: None,
     })
 }
 
@@ -146,10 +163,10 @@ fn to_isotope(
         None => return Ok(Some(isotope)),
     };
 
-    if isotope < u16::from(element.atomic_number()) {
-        return Err(AtomError::Isotope);
-    } else {
+    if element.is_valid_mass_number(isotope) {
         Ok(Some(isotope))
+    } else {
+        Err(AtomError::Isotope)
     }
 }
 
@@ -157,7 +174,7 @@ fn to_electrons(
     element: &Option<Element>,
     hydrogens: u8,
     ion: i8,
-    bonds: &Vec<graph::Bond>,
+    bonds: &[graph::Bond],
 ) -> Result<u8, AtomError> {
     let element = match element {
         Some(element) => element,
@@ -180,25 +197,155 @@ fn to_electrons(
     }
 }
 
-fn to_parity(
+// Returns the tetrahedral parity or other configuration of the atom at id.
+// A SMILES configuration counts an implicit hydrogen as the neighbor
+// following the preceding atom, or as the first if there is none.
+fn to_stereo(
+    parity: &parts::Parity,
     hydrogens: u8,
-    parity: &Option<parts::Parity>,
-    bonds: &Vec<graph::Bond>,
-) -> Result<Option<Parity>, AtomError> {
-    let parity = match parity {
-        Some(parity) => parity,
-        None => return Ok(None),
+    id: usize,
+    atoms: &[graph::Atom],
+    trace: &[usize],
+) -> Result<(Option<Parity>, Option<Configuration>), AtomError> {
+    let bonds = &atoms[id].bonds;
+    let follows_parent = hydrogens > 0
+        && match bonds.first() {
+            Some(bond) => trace[bond.tid] < trace[id],
+            None => false,
+        };
+    let configuration = match parity {
+        // On the center of a cumulene, @ and @@ abbreviate AL1 and AL2.
+        parts::Parity::Counterclockwise
+            if hydrogens == 0 && is_cumulated(&atoms[id]) =>
+        {
+            return to_allene(Parity::Negative, hydrogens, id, atoms, trace)
+                .map(|configuration| (None, Some(configuration)));
+        }
+        parts::Parity::Clockwise
+            if hydrogens == 0 && is_cumulated(&atoms[id]) =>
+        {
+            return to_allene(Parity::Positive, hydrogens, id, atoms, trace)
+                .map(|configuration| (None, Some(configuration)));
+        }
+        parts::Parity::Clockwise => {
+            return to_parity(Parity::Positive, hydrogens, bonds)
+                .map(|parity| (Some(parity), None));
+        }
+        parts::Parity::Counterclockwise => {
+            return to_parity(Parity::Negative, hydrogens, bonds)
+                .map(|parity| (Some(parity), None));
+        }
+        // purr moves a hydrogen following the parent to the front for @ and
+        // @@, but not for their long forms.
+        parts::Parity::TH1 | parts::Parity::TH2 => {
+            let parity = match (parity, follows_parent) {
+                (parts::Parity::TH1, false) | (parts::Parity::TH2, true) => {
+                    Parity::Negative
+                }
+                _ => Parity::Positive,
+            };
+
+            return to_parity(parity, hydrogens, bonds)
+                .map(|parity| (Some(parity), None));
+        }
+        parts::Parity::AL1 => {
+            return to_allene(Parity::Negative, hydrogens, id, atoms, trace)
+                .map(|configuration| (None, Some(configuration)));
+        }
+        parts::Parity::AL2 => {
+            return to_allene(Parity::Positive, hydrogens, id, atoms, trace)
+                .map(|configuration| (None, Some(configuration)));
+        }
+        parts::Parity::SP1 => Configuration::SquarePlanar(1),
+        parts::Parity::SP2 => Configuration::SquarePlanar(2),
+        parts::Parity::SP3 => Configuration::SquarePlanar(3),
+        parts::Parity::TB1 => Configuration::TrigonalBipyramidal(1),
+        parts::Parity::TB2 => Configuration::TrigonalBipyramidal(2),
+        parts::Parity::TB3 => Configuration::TrigonalBipyramidal(3),
+        parts::Parity::TB4 => Configuration::TrigonalBipyramidal(4),
+        parts::Parity::TB5 => Configuration::TrigonalBipyramidal(5),
+        parts::Parity::TB6 => Configuration::TrigonalBipyramidal(6),
+        parts::Parity::TB7 => Configuration::TrigonalBipyramidal(7),
+        parts::Parity::TB8 => Configuration::TrigonalBipyramidal(8),
+        parts::Parity::TB9 => Configuration::TrigonalBipyramidal(9),
+        parts::Parity::TB10 => Configuration::TrigonalBipyramidal(10),
+        parts::Parity::TB11 => Configuration::TrigonalBipyramidal(11),
+        parts::Parity::TB12 => Configuration::TrigonalBipyramidal(12),
+        parts::Parity::TB13 => Configuration::TrigonalBipyramidal(13),
+        parts::Parity::TB14 => Configuration::TrigonalBipyramidal(14),
+        parts::Parity::TB15 => Configuration::TrigonalBipyramidal(15),
+        parts::Parity::TB16 => Configuration::TrigonalBipyramidal(16),
+        parts::Parity::TB17 => Configuration::TrigonalBipyramidal(17),
+        parts::Parity::TB18 => Configuration::TrigonalBipyramidal(18),
+        parts::Parity::TB19 => Configuration::TrigonalBipyramidal(19),
+        parts::Parity::TB20 => Configuration::TrigonalBipyramidal(20),
+        parts::Parity::OH1 => Configuration::Octahedral(1),
+        parts::Parity::OH2 => Configuration::Octahedral(2),
+        parts::Parity::OH3 => Configuration::Octahedral(3),
+        parts::Parity::OH4 => Configuration::Octahedral(4),
+        parts::Parity::OH5 => Configuration::Octahedral(5),
+        parts::Parity::OH6 => Configuration::Octahedral(6),
+        parts::Parity::OH7 => Configuration::Octahedral(7),
+        parts::Parity::OH8 => Configuration::Octahedral(8),
+        parts::Parity::OH9 => Configuration::Octahedral(9),
+        parts::Parity::OH10 => Configuration::Octahedral(10),
+        parts::Parity::OH11 => Configuration::Octahedral(11),
+        parts::Parity::OH12 => Configuration::Octahedral(12),
+        parts::Parity::OH13 => Configuration::Octahedral(13),
+        parts::Parity::OH14 => Configuration::Octahedral(14),
+        parts::Parity::OH15 => Configuration::Octahedral(15),
+        parts::Parity::OH16 => Configuration::Octahedral(16),
+        parts::Parity::OH17 => Configuration::Octahedral(17),
+        parts::Parity::OH18 => Configuration::Octahedral(18),
+        parts::Parity::OH19 => Configuration::Octahedral(19),
+        parts::Parity::OH20 => Configuration::Octahedral(20),
+        parts::Parity::OH21 => Configuration::Octahedral(21),
+        parts::Parity::OH22 => Configuration::Octahedral(22),
+        parts::Parity::OH23 => Configuration::Octahedral(23),
+        parts::Parity::OH24 => Configuration::Octahedral(24),
+        parts::Parity::OH25 => Configuration::Octahedral(25),
+        parts::Parity::OH26 => Configuration::Octahedral(26),
+        parts::Parity::OH27 => Configuration::Octahedral(27),
+        parts::Parity::OH28 => Configuration::Octahedral(28),
+        parts::Parity::OH29 => Configuration::Octahedral(29),
+        parts::Parity::OH30 => Configuration::Octahedral(30),
     };
 
+    if hydrogens > 1 {
+        return Err(AtomError::Parity);
+    }
+
+    let reference = (0..hydrogens)
+        .map(|_| None)
+        .chain(bonds.iter().map(|bond| Some(bond.tid)))
+        .collect::<Vec<_>>();
+    let mut written =
+        bonds.iter().map(|bond| Some(bond.tid)).collect::<Vec<_>>();
+
+    if hydrogens == 1 {
+        written.insert(if follows_parent { 1 } else { 0 }, None);
+    }
+
+    match configuration.permute(&written, &reference) {
+        Some(configuration) => Ok((None, Some(configuration))),
+        None => Err(AtomError::Parity),
+    }
+}
+
+fn to_parity(
+    parity: Parity,
+    hydrogens: u8,
+    bonds: &[graph::Bond],
+) -> Result<Parity, AtomError> {
     if hydrogens == 0 {
         if bonds.len() == 4 {
-            Ok(Some(parity.into()))
+            Ok(parity)
         } else {
             Err(AtomError::Parity)
         }
     } else if hydrogens == 1 {
         if bonds.len() == 3 {
-            Ok(Some(parity.into()))
+            Ok(parity)
         } else {
             Err(AtomError::Parity)
         }
@@ -207,6 +354,98 @@ fn to_parity(
     }
 }
 
+// An allene center joins two double bonds, possibly through further
+// cumulated atoms. Substituents of the terminal atoms are written in
+// order of appearance, with an implicit hydrogen in place of its atom.
+fn to_allene(
+    parity: Parity,
+    hydrogens: u8,
+    id: usize,
+    atoms: &[graph::Atom],
+    trace: &[usize],
+) -> Result<Configuration, AtomError> {
+    if hydrogens > 0 || !is_cumulated(&atoms[id]) {
+        return Err(AtomError::Parity);
+    }
+
+    let mut reference = Vec::new();
+
+    for bond in atoms[id].bonds.iter() {
+        let (terminal, previous) = terminal(id, bond.tid, atoms);
+        let atom = &atoms[terminal];
+        let count = to_hydrogens(atom);
+
+        if count > 1 || count as usize + atom.bonds.len() != 3 {
+            return Err(AtomError::Parity);
+        }
+
+        if count == 1 {
+            reference.push((terminal, None));
+        }
+
+        for bond in atom.bonds.iter() {
+            if bond.tid != previous {
+                reference.push((terminal, Some(bond.tid)));
+            }
+        }
+    }
+
+    let mut written = reference.clone();
+
+    written.sort_by_key(|(terminal, neighbor)| match neighbor {
+        Some(neighbor) => trace[*neighbor],
+        None => trace[*terminal],
+    });
+
+    match Configuration::Allene(parity).permute(&written, &reference) {
+        Some(configuration) => Ok(configuration),
+        None => Err(AtomError::Parity),
+    }
+}
+
+fn is_cumulated(atom: &graph::Atom) -> bool {
+    atom.bonds.len() == 2 && atom.bonds.iter().all(|bond| bond.order() == 2)
+}
+
+// Follows cumulated double bonds from center through first, returning the
+// terminal atom and its neighbor toward center.
+fn terminal(
+    center: usize,
+    first: usize,
+    atoms: &[graph::Atom],
+) -> (usize, usize) {
+    let mut previous = center;
+    let mut current = first;
+
+    while is_cumulated(&atoms[current]) && to_hydrogens(&atoms[current]) == 0 {
+        let next = atoms[current]
+            .bonds
+            .iter()
+            .map(|bond| bond.tid)
+            .find(|&tid| tid != previous)
+            .expect("next");
+
+        if next == center {
+            break;
+        }
+
+        previous = current;
+        current = next;
+    }
+
+    (current, previous)
+}
+
+fn to_hydrogens(atom: &graph::Atom) -> u8 {
+    match &atom.kind {
+        parts::AtomKind::Bracket { hcount, .. } => match hcount {
+            Some(hcount) => hcount.into(),
+            None => 0,
+        },
+        _ => atom.subvalence(),
+    }
+}
+
 enum AtomError {
     Valence,
     Isotope,
@@ -259,6 +498,15 @@ mod tests {
         assert_eq!(result, Err(Error::Isotope(2)))
     }
 
+    #[test]
+    fn carbon_999() {
+        let Reading { root, trace } = read("C-[999C]").unwrap();
+        let atoms = from_tree(root).unwrap();
+        let result = to_node(1, &atoms, &trace);
+
+        assert_eq!(result, Err(Error::Isotope(2)))
+    }
+
     #[test]
     fn carbon_dioxide_parity() {
         let Reading { root, trace } = read("[C@](=O)=O").unwrap();
@@ -282,9 +530,11 @@ mod tests {
                     isotope: None,
                     electrons: 0,
                     hydrogens: 0,
-                    parity: None
+                    parity: None,
+                    configuration: None
                 },
-                bonds: vec![Bond::new(2, None, 0)]
+                bonds: vec![Bond::new(2, None, 0)],
+                aromatic: false
             })
         )
     }
@@ -303,9 +553,11 @@ mod tests {
                     isotope: None,
                     electrons: 0,
                     hydrogens: 3,
-                    parity: None
+                    parity: None,
+                    configuration: None
                 },
-                bonds: vec![Bond::new(2, None, 0)]
+                bonds: vec![Bond::new(2, None, 0)],
+                aromatic: false
             })
         )
     }
@@ -324,9 +576,11 @@ mod tests {
                     isotope: None,
                     electrons: 0,
                     hydrogens: 3,
-                    parity: None
+                    parity: None,
+                    configuration: None
                 },
-                bonds: vec![Bond::new(2, None, 0)]
+                bonds: vec![Bond::new(2, None, 0)],
+                aromatic: false
             })
         )
     }
@@ -345,9 +599,11 @@ mod tests {
                     isotope: None,
                     electrons: 0,
                     hydrogens: 0,
-                    parity: None
+                    parity: None,
+                    configuration: None
                 },
-                bonds: vec![Bond::new(2, None, 0)]
+                bonds: vec![Bond::new(2, None, 0)],
+                aromatic: false
             })
         )
     }
@@ -366,9 +622,11 @@ mod tests {
                     isotope: Some(12),
                     electrons: 0,
                     hydrogens: 0,
-                    parity: None
+                    parity: None,
+                    configuration: None
                 },
-                bonds: vec![]
+                bonds: vec![],
+                aromatic: false
             })
         )
     }
@@ -387,9 +645,11 @@ mod tests {
                     isotope: None,
                     electrons: 0,
                     hydrogens: 4,
-                    parity: None
+                    parity: None,
+                    configuration: None
                 },
-                bonds: vec![]
+                bonds: vec![],
+                aromatic: false
             })
         )
     }
@@ -408,9 +668,11 @@ mod tests {
                     isotope: None,
                     electrons: 0,
                     hydrogens: 3,
-                    parity: None
+                    parity: None,
+                    configuration: None
                 },
-                bonds: vec![]
+                bonds: vec![],
+                aromatic: false
             })
         )
     }
@@ -429,9 +691,11 @@ mod tests {
                     isotope: None,
                     electrons: 2,
                     hydrogens: 3,
-                    parity: None
+                    parity: None,
+                    configuration: None
                 },
-                bonds: vec![]
+                bonds: vec![],
+                aromatic: false
             })
         )
     }
@@ -450,9 +714,11 @@ mod tests {
                     isotope: Some(12),
                     electrons: 4,
                     hydrogens: 0,
-                    parity: None
+                    parity: None,
+                    configuration: None
                 },
-                bonds: vec![]
+                bonds: vec![],
+                aromatic: false
             })
         )
     }
@@ -471,14 +737,16 @@ mod tests {
                     isotope: None,
                     electrons: 0,
                     hydrogens: 0,
-                    parity: Some(Parity::Negative)
+                    parity: Some(Parity::Negative),
+                    configuration: None
                 },
                 bonds: vec![
                     Bond::new(2, None, 0),
                     Bond::new(2, None, 2),
                     Bond::new(2, None, 3),
                     Bond::new(2, None, 4)
-                ]
+                ],
+                aromatic: false
             })
         )
     }
@@ -497,14 +765,16 @@ mod tests {
                     isotope: None,
                     electrons: 0,
                     hydrogens: 0,
-                    parity: Some(Parity::Negative)
+                    parity: Some(Parity::Negative),
+                    configuration: None
                 },
                 bonds: vec![
                     Bond::new(2, None, 0),
                     Bond::new(2, None, 2),
                     Bond::new(2, None, 3),
                     Bond::new(2, None, 4)
-                ]
+                ],
+                aromatic: false
             })
         )
     }
@@ -523,15 +793,151 @@ mod tests {
                     isotope: None,
                     electrons: 0,
                     hydrogens: 0,
-                    parity: None
+                    parity: None,
+                    configuration: None
                 },
                 bonds: vec![
                     Bond::new(2, None, 1),
                     Bond::new(2, None, 2),
                     Bond::new(2, None, 3),
                     Bond::new(2, None, 4)
-                ]
+                ],
+                aromatic: false
             })
         )
     }
+
+    fn configuration(smiles: &str, id: usize) -> Result<Atom, Error> {
+        let Reading { root, trace } = read(smiles).unwrap();
+        let atoms = from_tree(root).unwrap();
+
+        to_node(id, &atoms, &trace).map(|node| node.atom)
+    }
+
+    #[test]
+    fn tetrahedral_th1() {
+        let atom = configuration("[C@TH1](F)(Cl)(Br)I", 0).unwrap();
+
+        assert_eq!(atom.parity, Some(Parity::Negative))
+    }
+
+    #[test]
+    fn tetrahedral_th2() {
+        let atom = configuration("[C@TH2](F)(Cl)(Br)I", 0).unwrap();
+
+        assert_eq!(atom.parity, Some(Parity::Positive))
+    }
+
+    #[test]
+    fn tetrahedral_th1_hydrogen() {
+        let long = configuration("F[C@TH1H](Cl)Br", 1).unwrap();
+        let short = configuration("F[C@H](Cl)Br", 1).unwrap();
+
+        assert_eq!(long, short)
+    }
+
+    #[test]
+    fn allene() {
+        let atom = configuration("FC(Cl)=[C@AL1]=C(Br)I", 3).unwrap();
+
+        assert_eq!(
+            atom,
+            Atom {
+                element: Some(Element::C),
+                isotope: None,
+                electrons: 0,
+                hydrogens: 0,
+                parity: None,
+                configuration: Some(Configuration::Allene(Parity::Negative))
+            }
+        )
+    }
+
+    #[test]
+    fn allene_shorthand() {
+        let short = configuration("OC=[C@]=CF", 2).unwrap();
+        let long = configuration("OC=[C@AL1]=CF", 2).unwrap();
+
+        assert_eq!(short, long);
+        assert_eq!(
+            configuration("NC(Br)=[C@@]=C(O)C", 3)
+                .unwrap()
+                .configuration,
+            Some(Configuration::Allene(Parity::Positive))
+        )
+    }
+
+    #[test]
+    fn allene_hydrogen_terminal() {
+        let atom = configuration("F[CH]=[C@AL2]=[CH]F", 2).unwrap();
+
+        // reference order is H, F, H, F; written order is F, H, H, F
+        assert_eq!(
+            atom.configuration,
+            Some(Configuration::Allene(Parity::Negative))
+        )
+    }
+
+    #[test]
+    fn cumulene() {
+        let atom = configuration("FC(Cl)=C=[C@AL1]=C=C(Br)I", 4).unwrap();
+
+        assert_eq!(
+            atom.configuration,
+            Some(Configuration::Allene(Parity::Negative))
+        )
+    }
+
+    #[test]
+    fn allene_single_bond() {
+        assert_eq!(
+            configuration("FC(Cl)=[C@AL1]C(Br)I", 3),
+            Err(Error::Parity(7))
+        )
+    }
+
+    #[test]
+    fn square_planar() {
+        let atom = configuration("F[Pt@SP3](Cl)(Br)I", 1).unwrap();
+
+        assert_eq!(atom.configuration, Some(Configuration::SquarePlanar(3)))
+    }
+
+    #[test]
+    fn square_planar_hydrogen_after_parent() {
+        let atom = configuration("F[Pt@SP1H](Cl)Br", 1).unwrap();
+
+        // written F, H, Cl, Br; reference H, F, Cl, Br
+        assert_eq!(atom.configuration, Some(Configuration::SquarePlanar(3)))
+    }
+
+    #[test]
+    fn square_planar_three_neighbors() {
+        assert_eq!(configuration("F[Pt@SP1](Cl)Br", 1), Err(Error::Parity(1)))
+    }
+
+    #[test]
+    fn trigonal_bipyramidal() {
+        let atom = configuration("[As@TB20](F)(Cl)(Br)(I)C", 0).unwrap();
+
+        assert_eq!(
+            atom.configuration,
+            Some(Configuration::TrigonalBipyramidal(20))
+        )
+    }
+
+    #[test]
+    fn octahedral() {
+        let atom = configuration("[Co@OH25](F)(Cl)(Br)(I)(C)O", 0).unwrap();
+
+        assert_eq!(atom.configuration, Some(Configuration::Octahedral(25)))
+    }
+
+    #[test]
+    fn octahedral_hydrogen_after_parent() {
+        let atom = configuration("F[Co@OH1H](Cl)(Br)(I)C", 1).unwrap();
+
+        // written F, H, Cl, Br, I, C; reference H, F, Cl, Br, I, C
+        assert_eq!(atom.configuration, Some(Configuration::Octahedral(28)))
+    }
 }
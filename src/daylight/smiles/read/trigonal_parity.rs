@@ -12,7 +12,7 @@ pub fn trigonal_parity(bonds: &[Bond]) -> Result<Option<Parity>, ()> {
         return Ok(None);
     }
 
-    let first = match bonds.get(0) {
+    let first = match bonds.first() {
         Some(first) => &first.kind,
         None => return Ok(None),
     };
@@ -20,10 +20,7 @@ pub fn trigonal_parity(bonds: &[Bond]) -> Result<Option<Parity>, ()> {
         Some(second) => &second.kind,
         None => return Ok(None),
     };
-    let third = match bonds.get(2) {
-        Some(third) => Some(&third.kind),
-        None => None,
-    };
+    let third = bonds.get(2).map(|third| &third.kind);
 
     if !is_directional(first) && !is_directional(second) {
         if let Some(third) = third {
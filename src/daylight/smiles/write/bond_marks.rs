@@ -0,0 +1,286 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{Error, Traversal};
+use crate::molecule::Molecule;
+use crate::stereo::is_cis;
+
+/// Assigns directional marks to single bonds flanking each double bond
+/// with parity. An entry (sid, tid) => true means tid lies above sid, and
+/// is written as `/` when the bond is written from sid to tid.
+pub fn bond_marks<M: Molecule>(
+    molecule: &M,
+    traversal: &Traversal,
+) -> Result<HashMap<(usize, usize), bool>, Error> {
+    let doubles = stereo_doubles(molecule, traversal)?;
+    let mut ends: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut chosen = HashSet::new();
+
+    for (index, double) in doubles.iter().enumerate() {
+        for end in double.ends.iter() {
+            ends.entry(end.id).or_default().push(index);
+        }
+    }
+
+    for double in doubles.iter() {
+        for end in double.ends.iter() {
+            if end
+                .substituents
+                .iter()
+                .any(|n| chosen.contains(&(end.id, *n)))
+            {
+                continue;
+            }
+
+            let preferred = end
+                .substituents
+                .iter()
+                .find(|&&n| !has_plain_double(n, molecule))
+                .unwrap_or(&end.substituents[0]);
+
+            chosen.insert((end.id, *preferred));
+            chosen.insert((*preferred, end.id));
+        }
+    }
+
+    let mut orientations = vec![None; doubles.len()];
+    let mut result = HashMap::new();
+
+    for start in 0..doubles.len() {
+        if orientations[start].is_some() {
+            continue;
+        }
+
+        let mut stack = vec![start];
+
+        orientations[start] = Some(true);
+
+        while let Some(index) = stack.pop() {
+            let double = &doubles[index];
+            let orientation = orientations[index].expect("orientation");
+
+            for end in double.ends.iter() {
+                for &neighbor in end.substituents.iter() {
+                    if !chosen.contains(&(end.id, neighbor)) {
+                        continue;
+                    }
+
+                    let above = end.wants(neighbor) == orientation;
+
+                    if result.insert((end.id, neighbor), above) == Some(!above)
+                    {
+                        return Err(double.error());
+                    }
+
+                    result.insert((neighbor, end.id), !above);
+
+                    for &other in ends.get(&neighbor).into_iter().flatten() {
+                        let other_end = match doubles[other].end(neighbor) {
+                            Some(other_end) => other_end,
+                            None => continue,
+                        };
+
+                        if !other_end.substituents.contains(&end.id) {
+                            continue;
+                        }
+
+                        let required = above != other_end.wants(end.id);
+
+                        match orientations[other] {
+                            Some(current) => {
+                                if current != required {
+                                    return Err(doubles[other].error());
+                                }
+                            }
+                            None => {
+                                orientations[other] = Some(required);
+                                stack.push(other);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+struct Double {
+    ends: [End; 2],
+}
+
+impl Double {
+    fn end(&self, id: usize) -> Option<&End> {
+        self.ends.iter().find(|end| end.id == id)
+    }
+
+    fn error(&self) -> Error {
+        Error::BondParity(self.ends[0].id, self.ends[1].id)
+    }
+}
+
+struct End {
+    id: usize,
+    flip: bool,
+    substituents: Vec<usize>,
+}
+
+impl End {
+    // Returns true if neighbor lies above id when the double bond takes
+    // its default orientation.
+    fn wants(&self, neighbor: usize) -> bool {
+        (neighbor == self.substituents[0]) != self.flip
+    }
+}
+
+fn stereo_doubles<M: Molecule>(
+    molecule: &M,
+    traversal: &Traversal,
+) -> Result<Vec<Double>, Error> {
+    let mut result = Vec::new();
+
+    for &sid in traversal.order() {
+        for tid in traversal.written_neighbors(sid) {
+            if traversal.position(tid) < traversal.position(sid)
+                || molecule.bond_order(sid, tid)? != 2f32
+            {
+                continue;
+            }
+
+            match molecule.bond(sid, tid)? {
+                Some(bond) => {
+                    if bond.parity.is_none() {
+                        continue;
+                    }
+                }
+                None => continue,
+            }
+
+            let left = to_end(sid, tid, molecule, traversal)?;
+            let mut right = to_end(tid, sid, molecule, traversal)?;
+            let cis = is_cis(
+                molecule,
+                sid,
+                tid,
+                left.substituents[0],
+                right.substituents[0],
+            )?;
+
+            right.flip = cis != Some(true);
+
+            result.push(Double {
+                ends: [left, right],
+            })
+        }
+    }
+
+    Ok(result)
+}
+
+fn to_end<M: Molecule>(
+    id: usize,
+    partner: usize,
+    molecule: &M,
+    traversal: &Traversal,
+) -> Result<End, Error> {
+    let mut substituents = Vec::new();
+
+    for neighbor in traversal.written_neighbors(id) {
+        if neighbor != partner && molecule.bond_order(id, neighbor)? == 1f32 {
+            substituents.push(neighbor)
+        }
+    }
+
+    if substituents.is_empty() {
+        Err(Error::BondParity(id, partner))
+    } else {
+        Ok(End {
+            id,
+            flip: false,
+            substituents,
+        })
+    }
+}
+
+fn has_plain_double<M: Molecule>(id: usize, molecule: &M) -> bool {
+    let neighbors = match molecule.neighbors(id) {
+        Ok(neighbors) => neighbors,
+        Err(_) => return false,
+    };
+
+    for neighbor in neighbors {
+        if molecule.bond_order(id, neighbor) == Ok(2f32) {
+            if let Ok(Some(bond)) = molecule.bond(id, neighbor) {
+                if bond.parity.is_none() {
+                    return true;
+                }
+            }
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use gamma::graph::Graph;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::read_smiles;
+
+    fn marks_for(smiles: &str) -> Result<Vec<(usize, usize, bool)>, Error> {
+        let molecule = read_smiles(smiles, None).unwrap();
+        let ranks = molecule.ids().map(|id| (id, id)).collect();
+        let traversal = Traversal::new(&molecule, &ranks).unwrap();
+        let mut result = bond_marks(&molecule, &traversal)?
+            .into_iter()
+            .map(|((sid, tid), above)| (sid, tid, above))
+            .collect::<Vec<_>>();
+
+        result.sort();
+
+        Ok(result)
+    }
+
+    #[test]
+    fn ethene() {
+        assert_eq!(marks_for("C=C"), Ok(vec![]))
+    }
+
+    #[test]
+    fn trans_butene() {
+        assert_eq!(
+            marks_for("C/C=C/C"),
+            Ok(vec![
+                (0, 1, false),
+                (1, 0, true),
+                (2, 3, false),
+                (3, 2, true)
+            ])
+        )
+    }
+
+    #[test]
+    fn cis_butene() {
+        assert_eq!(
+            marks_for("C/C=C\\C"),
+            Ok(vec![
+                (0, 1, false),
+                (1, 0, true),
+                (2, 3, true),
+                (3, 2, false)
+            ])
+        )
+    }
+
+    #[test]
+    fn branched_conjugated() {
+        assert_eq!(marks_for("C/C=C(/C)\\C=C\\C").unwrap().len(), 8)
+    }
+
+    #[test]
+    fn hexatriene_shared_mark() {
+        assert_eq!(marks_for("C/C=C/C=C/C").unwrap().len(), 6)
+    }
+}
@@ -0,0 +1,19 @@
+use gamma::graph::Error as GraphError;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Charge(usize),
+    Hydrogens(usize),
+    Isotope(usize),
+    BondOrder(usize, usize),
+    BondParity(usize, usize),
+    Configuration(usize),
+    RingClosure(usize),
+    Graph(GraphError),
+}
+
+impl From<GraphError> for Error {
+    fn from(error: GraphError) -> Self {
+        Error::Graph(error)
+    }
+}
@@ -0,0 +1,13 @@
+mod bond_marks;
+mod error;
+mod traversal;
+#[allow(clippy::module_inception)]
+mod write;
+mod write_atom;
+
+pub use bond_marks::bond_marks;
+pub use error::Error;
+pub use traversal::Traversal;
+pub use write::write;
+pub use write::write_canonical;
+pub use write_atom::{is_aromatic_symbol, write_atom};
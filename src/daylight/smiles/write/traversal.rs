@@ -0,0 +1,235 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use gamma::graph::{Error, Graph};
+
+/// A depth-first spanning forest over a Graph, with children and ring
+/// closures arranged in the order they will be written.
+#[derive(Debug, PartialEq)]
+pub struct Traversal {
+    roots: Vec<usize>,
+    order: Vec<usize>,
+    positions: HashMap<usize, usize>,
+    parents: HashMap<usize, usize>,
+    children: HashMap<usize, Vec<usize>>,
+    rings: HashMap<usize, Vec<usize>>,
+}
+
+impl Traversal {
+    /// Traverses graph starting from the lowest-ranked atom of each
+    /// component, visiting neighbors in ascending rank order.
+    pub fn new<G: Graph>(
+        graph: &G,
+        ranks: &HashMap<usize, usize>,
+    ) -> Result<Self, Error> {
+        let mut ids = graph.ids().collect::<Vec<_>>();
+        let mut roots = Vec::new();
+        let mut order = Vec::new();
+        let mut positions = HashMap::new();
+        let mut parents = HashMap::new();
+        let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
+        let mut stack = Vec::new();
+
+        ids.sort_by_key(|id| ranks[id]);
+
+        for root in ids {
+            if positions.contains_key(&root) {
+                continue;
+            }
+
+            roots.push(root);
+            stack.push((root, None));
+
+            while let Some((id, parent)) = stack.pop() {
+                if positions.contains_key(&id) {
+                    continue;
+                }
+
+                positions.insert(id, order.len());
+                order.push(id);
+
+                if let Some(parent) = parent {
+                    parents.insert(id, parent);
+                    children.entry(parent).or_default().push(id);
+                }
+
+                let mut neighbors = graph
+                    .neighbors(id)?
+                    .filter(|neighbor| !positions.contains_key(neighbor))
+                    .collect::<Vec<_>>();
+
+                neighbors.sort_by_key(|neighbor| Reverse(ranks[neighbor]));
+
+                for neighbor in neighbors {
+                    stack.push((neighbor, Some(id)))
+                }
+            }
+        }
+
+        let mut rings: HashMap<usize, Vec<usize>> = HashMap::new();
+
+        for (sid, tid) in graph.edges() {
+            if parents.get(&sid) == Some(&tid)
+                || parents.get(&tid) == Some(&sid)
+            {
+                continue;
+            }
+
+            rings.entry(sid).or_default().push(tid);
+            rings.entry(tid).or_default().push(sid);
+        }
+
+        for (id, partners) in rings.iter_mut() {
+            let position = positions[id];
+
+            // closures first, then openings, each in order of appearance
+            partners.sort_by_key(|partner| {
+                (positions[partner] > position, positions[partner])
+            });
+        }
+
+        Ok(Self {
+            roots,
+            order,
+            positions,
+            parents,
+            children,
+            rings,
+        })
+    }
+
+    /// Returns the root of each connected component, in writing order.
+    pub fn roots(&self) -> &[usize] {
+        &self.roots
+    }
+
+    /// Returns all ids in writing order.
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Returns the index of id in writing order.
+    pub fn position(&self, id: usize) -> usize {
+        self.positions[&id]
+    }
+
+    pub fn parent(&self, id: usize) -> Option<usize> {
+        self.parents.get(&id).cloned()
+    }
+
+    pub fn children(&self, id: usize) -> &[usize] {
+        match self.children.get(&id) {
+            Some(children) => children,
+            None => &[],
+        }
+    }
+
+    /// Returns ring closure partners of id. Closures precede openings.
+    pub fn rings(&self, id: usize) -> &[usize] {
+        match self.rings.get(&id) {
+            Some(rings) => rings,
+            None => &[],
+        }
+    }
+
+    /// Returns the neighbors of id in the order a reader will place them:
+    /// parent, ring closures, ring openings, then children. Consecutive
+    /// openings are placed in reverse order of closure by purr's reader.
+    pub fn written_neighbors(&self, id: usize) -> Vec<usize> {
+        let position = self.position(id);
+        let (closures, openings): (Vec<usize>, Vec<usize>) = self
+            .rings(id)
+            .iter()
+            .partition(|&&partner| self.position(partner) < position);
+
+        self.parent(id)
+            .into_iter()
+            .chain(closures)
+            .chain(openings.into_iter().rev())
+            .chain(self.children(id).iter().cloned())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use gamma::graph::DefaultGraph;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn identity(graph: &DefaultGraph) -> HashMap<usize, usize> {
+        graph.ids().map(|id| (id, id)).collect()
+    }
+
+    #[test]
+    fn reversed_ranks() {
+        let graph = DefaultGraph::try_from(vec![(0, 1)]).unwrap();
+        let ranks = vec![(0, 1), (1, 0)].into_iter().collect();
+        let traversal = Traversal::new(&graph, &ranks).unwrap();
+
+        assert_eq!(traversal.order(), &[1, 0])
+    }
+
+    #[test]
+    fn two_components() {
+        let graph =
+            DefaultGraph::try_from(vec![vec![1], vec![0], vec![]]).unwrap();
+        let traversal = Traversal::new(&graph, &identity(&graph)).unwrap();
+
+        assert_eq!(traversal.roots(), &[0, 2])
+    }
+
+    #[test]
+    fn branched() {
+        let graph =
+            DefaultGraph::try_from(vec![(0, 1), (1, 2), (1, 3)]).unwrap();
+        let traversal = Traversal::new(&graph, &identity(&graph)).unwrap();
+
+        assert_eq!(traversal.children(1), &[2, 3])
+    }
+
+    #[test]
+    fn cyclopropane() {
+        let graph =
+            DefaultGraph::try_from(vec![(0, 1), (1, 2), (2, 0)]).unwrap();
+        let traversal = Traversal::new(&graph, &identity(&graph)).unwrap();
+
+        assert_eq!(
+            (traversal.rings(0), traversal.rings(2)),
+            (&[2][..], &[0][..])
+        )
+    }
+
+    #[test]
+    fn written_neighbors() {
+        let graph = DefaultGraph::try_from(vec![
+            (0, 1),
+            (1, 2),
+            (2, 3),
+            (3, 1),
+            (1, 4),
+        ])
+        .unwrap();
+        let traversal = Traversal::new(&graph, &identity(&graph)).unwrap();
+
+        assert_eq!(traversal.written_neighbors(1), vec![0, 3, 2, 4])
+    }
+
+    #[test]
+    fn written_neighbors_openings() {
+        let graph = DefaultGraph::try_from(vec![
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 0),
+        ])
+        .unwrap();
+        let traversal = Traversal::new(&graph, &identity(&graph)).unwrap();
+
+        assert_eq!(traversal.written_neighbors(0), vec![3, 2, 1])
+    }
+}
@@ -0,0 +1,747 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+
+use gamma::graph::{Error as GraphError, Graph};
+
+use super::{bond_marks, is_aromatic_symbol, write_atom, Error, Traversal};
+use crate::aromaticity::{Aromaticity, Model};
+use crate::canon::canonical_ranks;
+use crate::molecule::{Bond, DefaultMolecule, Molecule, Node};
+use crate::stereo::strip_parities;
+
+/// Writes a SMILES string for molecule. Atoms are visited depth-first in
+/// the order given by `Graph::ids`. Bonds are written in Kekulé form.
+pub fn write<M: Molecule>(molecule: &M) -> Result<String, Error> {
+    let ranks = molecule
+        .ids()
+        .enumerate()
+        .map(|(rank, id)| (id, rank))
+        .collect::<HashMap<_, _>>();
+
+    write_ranked(molecule, &ranks)
+}
+
+/// Writes a SMILES string for molecule that is the same for any atom order
+/// and Kekulé form, using ranks from `canon::canonical_ranks`. Parities
+/// that are not stereogenic are dropped, and atoms and bonds aromatic
+/// under the Daylight model are written in aromatic form.
+pub fn write_canonical<M: Molecule>(molecule: &M) -> Result<String, Error> {
+    let standard = standardize(molecule)?;
+    let ranks = canonical_ranks(&standard)?;
+
+    write_ranked(&standard, &ranks)
+}
+
+/// Writes a SMILES string for molecule, starting each component from its
+/// lowest-ranked atom and branching to lower-ranked neighbors first.
+pub fn write_ranked<M: Molecule>(
+    molecule: &M,
+    ranks: &HashMap<usize, usize>,
+) -> Result<String, Error> {
+    let traversal = Traversal::new(molecule, ranks)?;
+    let marks = bond_marks(molecule, &traversal)?;
+    let mut out = String::new();
+    let mut rnums = Rnums::new();
+    let mut stack = traversal
+        .roots()
+        .iter()
+        .rev()
+        .map(|&root| Unit::Root(root))
+        .collect::<Vec<_>>();
+
+    while let Some(unit) = stack.pop() {
+        let id = match unit {
+            Unit::Root(id) => {
+                if !out.is_empty() {
+                    out.push('.');
+                }
+
+                id
+            }
+            Unit::Chain(id) => {
+                let parent = traversal.parent(id).expect("parent");
+
+                write_bond(parent, id, molecule, &marks, &mut out)?;
+
+                id
+            }
+            Unit::Branch(id) => {
+                let parent = traversal.parent(id).expect("parent");
+
+                out.push('(');
+                write_bond(parent, id, molecule, &marks, &mut out)?;
+
+                id
+            }
+            Unit::Close => {
+                out.push(')');
+
+                continue;
+            }
+        };
+
+        write_atom(id, molecule, &traversal, &mut out)?;
+
+        let mut released = Vec::new();
+
+        for &partner in traversal.rings(id) {
+            if traversal.position(partner) < traversal.position(id) {
+                let rnum = rnums.close(partner, id);
+
+                write_bond(id, partner, molecule, &marks, &mut out)?;
+                write_rnum(rnum, &mut out);
+                released.push(rnum);
+            } else {
+                match rnums.open(id, partner) {
+                    Some(rnum) => write_rnum(rnum, &mut out),
+                    None => return Err(Error::RingClosure(id)),
+                }
+            }
+        }
+
+        for rnum in released {
+            rnums.release(rnum);
+        }
+
+        let children = traversal.children(id);
+
+        if let Some((last, rest)) = children.split_last() {
+            stack.push(Unit::Chain(*last));
+
+            for &child in rest.iter().rev() {
+                stack.push(Unit::Close);
+                stack.push(Unit::Branch(child));
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+// Returns a copy of molecule without parities that aren't stereogenic, and
+// flagging as aromatic those aromatic atoms with a lowercase symbol and the
+// aromatic bonds between them. Atom ids are renumbered from zero.
+fn standardize<M: Molecule>(
+    molecule: &M,
+) -> Result<DefaultMolecule, GraphError> {
+    let stripped = strip_parities(molecule)?;
+    let aromaticity = Aromaticity::new(&stripped, &Model::Daylight)?;
+    let mut flags = Vec::new();
+
+    for id in stripped.ids() {
+        flags.push(
+            aromaticity.is_aromatic_atom(id)
+                && match &stripped.atom(id)?.element {
+                    Some(element) => is_aromatic_symbol(element, true),
+                    None => false,
+                },
+        );
+    }
+
+    let mut nodes = Vec::new();
+
+    for id in stripped.ids() {
+        let mut bonds = Vec::new();
+
+        for tid in stripped.neighbors(id)? {
+            let bond = match stripped.bond(id, tid)? {
+                Some(bond) => bond,
+                None => return Err(GraphError::MissingEdge(id, tid)),
+            };
+
+            bonds.push(Bond {
+                electrons: bond.electrons,
+                parity: bond.parity.clone(),
+                tid,
+                aromatic: flags[id]
+                    && flags[tid]
+                    && aromaticity.is_aromatic_bond(id, tid),
+            })
+        }
+
+        nodes.push(Node {
+            atom: stripped.atom(id)?.clone(),
+            bonds,
+            aromatic: flags[id],
+        })
+    }
+
+    Ok(DefaultMolecule::new(nodes))
+}
+
+fn write_bond<M: Molecule>(
+    sid: usize,
+    tid: usize,
+    molecule: &M,
+    marks: &HashMap<(usize, usize), bool>,
+    out: &mut String,
+) -> Result<(), Error> {
+    if let Some(above) = marks.get(&(sid, tid)) {
+        out.push(if *above { '/' } else { '\\' });
+
+        return Ok(());
+    }
+
+    let bond = match molecule.bond(sid, tid)? {
+        Some(bond) => bond,
+        None => return Err(GraphError::MissingEdge(sid, tid).into()),
+    };
+    let aromatic_ends =
+        molecule.is_aromatic(sid)? && molecule.is_aromatic(tid)?;
+
+    if bond.aromatic {
+        if !aromatic_ends {
+            out.push(':');
+        }
+
+        return Ok(());
+    }
+
+    let order = molecule.bond_order(sid, tid)?;
+
+    if order == 1f32 {
+        if aromatic_ends {
+            out.push('-');
+        }
+
+        return Ok(());
+    } else if order == 2f32 {
+        out.push('=')
+    } else if order == 3f32 {
+        out.push('#')
+    } else if order == 4f32 {
+        out.push('$')
+    } else {
+        return Err(Error::BondOrder(sid, tid));
+    }
+
+    Ok(())
+}
+
+fn write_rnum(rnum: u8, out: &mut String) {
+    if rnum < 10 {
+        write!(out, "{}", rnum).expect("write rnum")
+    } else {
+        write!(out, "%{}", rnum).expect("write rnum")
+    }
+}
+
+enum Unit {
+    Root(usize),
+    Chain(usize),
+    Branch(usize),
+    Close,
+}
+
+struct Rnums {
+    used: BTreeSet<u8>,
+    open: HashMap<(usize, usize), u8>,
+}
+
+impl Rnums {
+    fn new() -> Self {
+        Self {
+            used: BTreeSet::new(),
+            open: HashMap::new(),
+        }
+    }
+
+    fn open(&mut self, sid: usize, tid: usize) -> Option<u8> {
+        let rnum = (1..100).find(|rnum| !self.used.contains(rnum))?;
+
+        self.used.insert(rnum);
+        self.open.insert((sid, tid), rnum);
+
+        Some(rnum)
+    }
+
+    fn close(&mut self, sid: usize, tid: usize) -> u8 {
+        self.open.remove(&(sid, tid)).expect("open rnum")
+    }
+
+    fn release(&mut self, rnum: u8) {
+        self.used.remove(&rnum);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gamma::graph::Graph;
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    use super::*;
+    use crate::daylight::{read_aromatic_smiles, read_smiles};
+    use crate::molecule::{Atom, Bond, DefaultMolecule, Node};
+
+    #[test]
+    fn empty() {
+        let molecule = DefaultMolecule::new(vec![]);
+
+        assert_eq!(write(&molecule), Ok("".to_string()))
+    }
+
+    #[test]
+    fn methane() {
+        let molecule = read_smiles("C", None).unwrap();
+
+        assert_eq!(write(&molecule), Ok("C".to_string()))
+    }
+
+    #[test]
+    fn ethanol() {
+        let molecule = read_smiles("CCO", None).unwrap();
+
+        assert_eq!(write(&molecule), Ok("CCO".to_string()))
+    }
+
+    #[test]
+    fn isobutane() {
+        let molecule = read_smiles("CC(C)C", None).unwrap();
+
+        assert_eq!(write(&molecule), Ok("CC(C)C".to_string()))
+    }
+
+    #[test]
+    fn neopentane() {
+        let molecule = read_smiles("CC(C)(C)C", None).unwrap();
+
+        assert_eq!(write(&molecule), Ok("CC(C)(C)C".to_string()))
+    }
+
+    #[test]
+    fn ethene() {
+        let molecule = read_smiles("C=C", None).unwrap();
+
+        assert_eq!(write(&molecule), Ok("C=C".to_string()))
+    }
+
+    #[test]
+    fn ethyne() {
+        let molecule = read_smiles("C#C", None).unwrap();
+
+        assert_eq!(write(&molecule), Ok("C#C".to_string()))
+    }
+
+    #[test]
+    fn quadruple() {
+        let molecule = read_smiles("[Rh]$[Rh]", None).unwrap();
+
+        assert_eq!(write(&molecule), Ok("[Rh]$[Rh]".to_string()))
+    }
+
+    #[test]
+    fn methane_hydrate() {
+        let molecule = read_smiles("C.O", None).unwrap();
+
+        assert_eq!(write(&molecule), Ok("C.O".to_string()))
+    }
+
+    #[test]
+    fn cyclohexane() {
+        let molecule = read_smiles("C1CCCCC1", None).unwrap();
+
+        assert_eq!(write(&molecule), Ok("C1CCCCC1".to_string()))
+    }
+
+    #[test]
+    fn cyclopropyl_branch() {
+        let molecule = read_smiles("C(C1CC1)C", None).unwrap();
+
+        assert_eq!(write(&molecule), Ok("C(C1CC1)C".to_string()))
+    }
+
+    #[test]
+    fn cyclohexene_ring_bond() {
+        let molecule = read_smiles("C=1CCCCC=1", None).unwrap();
+
+        assert_eq!(write(&molecule), Ok("C1CCCCC=1".to_string()))
+    }
+
+    #[test]
+    fn benzene_aromatic() {
+        let molecule = read_smiles("c1ccccc1", None).unwrap();
+
+        assert_eq!(write(&molecule), Ok("C1C=CC=CC=1".to_string()))
+    }
+
+    #[test]
+    fn spiro() {
+        let molecule = read_smiles("C12(CC1)CC2", None).unwrap();
+
+        assert_eq!(write(&molecule), Ok("C12(CC1)CC2".to_string()))
+    }
+
+    #[test]
+    fn rnum_reuse() {
+        let molecule = read_smiles("C1CC1C1CC1", None).unwrap();
+
+        assert_eq!(write(&molecule), Ok("C1CC1C1CC1".to_string()))
+    }
+
+    #[test]
+    fn bracket_atoms() {
+        let molecule =
+            read_smiles("[13CH3][NH3+].[O-]C(=O)[Fe].[CH2]", None).unwrap();
+
+        assert_eq!(
+            write(&molecule),
+            Ok("[13CH3][NH3+].[O-]C(=O)[Fe].[CH2]".to_string())
+        )
+    }
+
+    #[test]
+    fn stars() {
+        let molecule = read_smiles("*C[12*][*H]", None).unwrap();
+
+        assert_eq!(write(&molecule), Ok("*C[12*][*H]".to_string()))
+    }
+
+    #[test]
+    fn half_bond() {
+        let molecule = DefaultMolecule::new(vec![
+            Node {
+                atom: Atom::default(),
+                bonds: vec![Bond::new(1, None, 1)],
+                aromatic: false,
+            },
+            Node {
+                atom: Atom::default(),
+                bonds: vec![Bond::new(1, None, 0)],
+                aromatic: false,
+            },
+        ]);
+
+        assert_eq!(write(&molecule), Err(Error::BondOrder(0, 1)))
+    }
+
+    #[test]
+    fn trans_butene() {
+        let molecule = read_smiles("C/C=C/C", None).unwrap();
+
+        assert_eq!(write(&molecule), Ok("C\\C=C\\C".to_string()))
+    }
+
+    #[test]
+    fn cis_butene() {
+        let molecule = read_smiles("C/C=C\\C", None).unwrap();
+
+        assert_eq!(write(&molecule), Ok("C\\C=C/C".to_string()))
+    }
+
+    #[test]
+    fn tetrahedral_root() {
+        let molecule = read_smiles("[C@](F)(Cl)(Br)I", None).unwrap();
+
+        assert_eq!(write(&molecule), Ok("[C@](F)(Cl)(Br)I".to_string()))
+    }
+
+    #[test]
+    fn tetrahedral_hydrogen() {
+        let molecule = read_smiles("F[C@H](Cl)Br", None).unwrap();
+
+        assert_eq!(write(&molecule), Ok("F[C@H](Cl)Br".to_string()))
+    }
+
+    #[test]
+    fn tetrahedral_ring_closure() {
+        let molecule = read_smiles("F[C@]1(Cl)CC1", None).unwrap();
+
+        assert_eq!(write(&molecule), Ok("F[C@]1(Cl)CC1".to_string()))
+    }
+
+    #[test]
+    fn round_trip_tetrahedral_reordered() {
+        let molecule = read_smiles("C[C@@H]1CC[C@](O)(F)C1", None).unwrap();
+
+        assert_eq!(read_smiles(&write(&molecule).unwrap(), None), Ok(molecule))
+    }
+
+    #[test]
+    fn round_trip_double_partner_second() {
+        let molecule = read_smiles("F/C(Cl)=C(/Br)I", None).unwrap();
+
+        assert_eq!(read_smiles(&write(&molecule).unwrap(), None), Ok(molecule))
+    }
+
+    #[test]
+    fn round_trip_ring_double_bond() {
+        let molecule = read_smiles("C/1=C/CCCCCC1", None).unwrap();
+
+        assert_eq!(read_smiles(&write(&molecule).unwrap(), None), Ok(molecule))
+    }
+
+    #[test]
+    fn round_trip_conjugated() {
+        let molecule = read_smiles("C/C=C/C=C\\C=C\\C", None).unwrap();
+
+        assert_eq!(read_smiles(&write(&molecule).unwrap(), None), Ok(molecule))
+    }
+
+    #[test]
+    fn round_trip_branched_double() {
+        let molecule = read_smiles("C/C(=C(/F)C)/Cl", None).unwrap();
+
+        assert_eq!(read_smiles(&write(&molecule).unwrap(), None), Ok(molecule))
+    }
+
+    #[test]
+    fn round_trip_tetrahedral_spiro() {
+        let molecule =
+            read_smiles("[C@@]12(F)CC[C@](Cl)(CC1)CC2", None).unwrap();
+
+        assert_eq!(read_smiles(&write(&molecule).unwrap(), None), Ok(molecule))
+    }
+
+    #[test]
+    fn round_trip_configurations() {
+        let inputs = vec![
+            "FC(Cl)=[C@AL1]=C(Br)I",
+            "FC(Cl)=C=[C@AL2]=C=C(Br)I",
+            "F[Pt@SP1](Cl)(Br)I",
+            "F[Pt@SP2H](Cl)Br",
+            "[Pt@SP3H](F)(Cl)Br",
+            "F[As@TB7](Cl)(Br)(I)C",
+            "F[Co@OH12](Cl)(Br)(I)(C)O",
+        ];
+
+        for input in inputs {
+            let molecule = read_smiles(input, None).unwrap();
+
+            assert_eq!(write(&molecule), Ok(input.to_string()))
+        }
+    }
+
+    #[test]
+    fn allene_reordered() {
+        let molecule = read_smiles("FC(Cl)=[C@AL1]=C(Br)I", None).unwrap();
+        let ranks =
+            vec![(2, 0), (1, 1), (0, 2), (3, 3), (4, 4), (5, 5), (6, 6)]
+                .into_iter()
+                .collect();
+
+        assert_eq!(
+            write_ranked(&molecule, &ranks),
+            Ok("ClC(F)=[C@AL2]=C(Br)I".to_string())
+        )
+    }
+
+    #[test]
+    fn square_planar_reordered() {
+        let molecule = read_smiles("F[Pt@SP1](Cl)(Br)I", None).unwrap();
+        let ranks = vec![(1, 0), (2, 1), (0, 2), (3, 3), (4, 4)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(
+            write_ranked(&molecule, &ranks),
+            Ok("[Pt@SP3](Cl)(F)(Br)I".to_string())
+        )
+    }
+
+    #[test]
+    fn octahedral_reordered() {
+        let molecule = read_smiles("F[Co@OH12](Cl)(Br)(I)(C)O", None).unwrap();
+        let ranks =
+            vec![(2, 0), (1, 1), (0, 2), (3, 3), (4, 4), (5, 5), (6, 6)]
+                .into_iter()
+                .collect();
+
+        assert_eq!(
+            write_ranked(&molecule, &ranks),
+            Ok("Cl[Co@OH23](F)(Br)(I)(C)O".to_string())
+        )
+    }
+
+    #[test]
+    fn trigonal_bipyramidal_reordered() {
+        let molecule = read_smiles("S[As@TB1](F)(Cl)(Br)N", None).unwrap();
+        let ranks = vec![(1, 0), (0, 1), (2, 2), (3, 3), (4, 4), (5, 5)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(
+            write_ranked(&molecule, &ranks),
+            Ok("[As@TB1](S)(F)(Cl)(Br)N".to_string())
+        )
+    }
+
+    #[test]
+    fn aromatic_round_trips() {
+        let inputs = vec![
+            "c1ccccc1",
+            "c1ccccc1-c1ccccc1",
+            "c1cc[nH]c1",
+            "c1ccncc1",
+            "c1ccoc1",
+            "c1ccsc1",
+            "Cc1ccc2ccccc2c1",
+            "O=c1cccc[nH]1",
+            "c1cc[n+](C)cc1",
+            "C:C",
+            "c1ccc[se]1",
+        ];
+
+        for input in inputs {
+            let molecule = read_aromatic_smiles(input, None).unwrap();
+
+            assert_eq!(write(&molecule), Ok(input.to_string()))
+        }
+    }
+
+    #[test]
+    fn canonical_ethanol() {
+        let left = read_smiles("OCC", None).unwrap();
+        let right = read_smiles("C(C)O", None).unwrap();
+
+        assert_eq!(write_canonical(&left), write_canonical(&right))
+    }
+
+    #[test]
+    fn canonical_components() {
+        let left = read_smiles("C1CCCCC1.C1CCCCCCCCC1", None).unwrap();
+        let right = read_smiles("C1CCCCCCCCC1.C1CCCCC1", None).unwrap();
+
+        assert_eq!(write_canonical(&left), write_canonical(&right))
+    }
+
+    #[test]
+    fn canonical_diastereomers() {
+        let cis = read_smiles("C[C@H]1CC[C@@H](C)CC1", None).unwrap();
+        let trans = read_smiles("C[C@H]1CC[C@H](C)CC1", None).unwrap();
+
+        assert_ne!(write_canonical(&cis), write_canonical(&trans))
+    }
+
+    #[test]
+    fn canonical_configurations() {
+        let inputs = vec!["S[As@TB1](F)(Cl)(Br)N", "C[Co@OH1](F)(Cl)(Br)(I)N"];
+
+        for input in inputs {
+            let molecule = read_smiles(input, None).unwrap();
+            let canonical = write_canonical(&molecule).unwrap();
+
+            assert_eq!(
+                write_canonical(&read_smiles(&canonical, None).unwrap()),
+                Ok(canonical)
+            )
+        }
+    }
+
+    #[test]
+    fn canonical_configurations_permuted() {
+        let inputs = vec![
+            "Cl[Pt@SP1](Cl)(N)N",
+            "Cl[Pt@SP2](Cl)(N)N",
+            "F[Pt@SP1](F)(F)Cl",
+            "F[Pt@SP3](Cl)(Br)I",
+            "OC(Cl)=[C@AL1]=C(F)C",
+            "FC(F)=C=[C@AL2]=C=C(Br)I",
+            "S[As@TB1](F)(Cl)(Br)N",
+            "F[As@TB15](F)(Cl)(Cl)F",
+            "C[Co@OH1](F)(Cl)(Br)(I)N",
+            "N[Co@OH3](N)(N)(Cl)(Cl)Cl",
+        ];
+
+        for input in inputs {
+            let molecule = read_smiles(input, None).unwrap();
+            let expected = write_canonical(&molecule);
+            let order = molecule.order();
+
+            for shift in 0..order {
+                let ranks = molecule
+                    .ids()
+                    .map(|id| (id, (order - id + shift) % order))
+                    .collect();
+                let permuted = write_ranked(&molecule, &ranks).unwrap();
+
+                assert_eq!(
+                    write_canonical(&read_smiles(&permuted, None).unwrap()),
+                    expected
+                )
+            }
+        }
+    }
+
+    #[test]
+    fn canonical_square_planar_isomers() {
+        let cis = read_smiles("Cl[Pt@SP1](Cl)(N)N", None).unwrap();
+        let also_cis = read_smiles("N[Pt@SP1](N)(Cl)Cl", None).unwrap();
+        let trans = read_smiles("Cl[Pt@SP2](Cl)(N)N", None).unwrap();
+
+        assert_eq!(write_canonical(&cis), write_canonical(&also_cis));
+        assert_ne!(write_canonical(&cis), write_canonical(&trans))
+    }
+
+    #[test]
+    fn canonical_kekule_forms() {
+        let pairs = vec![
+            ("Cc1ccccc1C", "CC1=CC=CC=C1C"),
+            ("C1=CC=C2C=CC=CC2=C1", "C1=CC2=CC=CC=C2C=C1"),
+            ("c1ccc2ccccc2c1", "C1=CC=C2C=CC=CC2=C1"),
+            ("Cc1cc[nH]c1", "CC1=CNC=C1"),
+            ("O=c1cc[nH]cc1", "O=C1C=CNC=C1"),
+        ];
+
+        for (left, right) in pairs {
+            let left = read_smiles(left, None).unwrap();
+            let right = read_smiles(right, None).unwrap();
+
+            assert_eq!(write_canonical(&left), write_canonical(&right))
+        }
+    }
+
+    #[test]
+    fn canonical_aromatic_output() {
+        let molecule = read_smiles("CC1=CC=CC=C1C", None).unwrap();
+        let canonical = write_canonical(&molecule).unwrap();
+
+        assert!(canonical.contains("ccc"));
+        assert_eq!(
+            write_canonical(&read_smiles(&canonical, None).unwrap()),
+            Ok(canonical)
+        )
+    }
+
+    #[test]
+    fn canonical_non_stereogenic_parities() {
+        let pairs = vec![("C[C@H](C)F", "CC(C)F"), ("F/C(F)=C/F", "FC(F)=CF")];
+
+        for (left, right) in pairs {
+            let left = read_smiles(left, None).unwrap();
+            let right = read_smiles(right, None).unwrap();
+
+            assert_eq!(write_canonical(&left), write_canonical(&right))
+        }
+    }
+
+    #[test]
+    fn canonical_decalin_isomers() {
+        let cis = read_smiles("C1CC[C@H]2CCCC[C@@H]2C1", None).unwrap();
+        let trans = read_smiles("C1CC[C@H]2CCCC[C@H]2C1", None).unwrap();
+
+        assert_ne!(write_canonical(&cis), write_canonical(&trans))
+    }
+
+    #[test]
+    fn canonical_reversed_ranks() {
+        let inputs = vec![
+            "C1CC[C@H]2CCCC[C@@H]2C1",
+            "OC(=O)[C@H](O)C(O)[C@@H](O)C(=O)O",
+            "C/C=C/C(/C=C\\C)=C/C",
+            "F[C@@]12CC[C@](Cl)(CC1)CC2",
+            "C12C3C4C1C5C2C3C45",
+        ];
+
+        for input in inputs {
+            let molecule = read_smiles(input, None).unwrap();
+            let order = molecule.order();
+            let ranks = molecule.ids().map(|id| (id, order - id)).collect();
+            let reversed = write_ranked(&molecule, &ranks).unwrap();
+
+            assert_eq!(
+                write_canonical(&read_smiles(&reversed, None).unwrap()),
+                write_canonical(&molecule)
+            )
+        }
+    }
+}
@@ -0,0 +1,459 @@
+use std::fmt::Write;
+
+use gamma::graph::Error as GraphError;
+
+use super::{Error, Traversal};
+use crate::molecule::{Configuration, Element, Molecule, Parity};
+use crate::stereo::allene_substituents;
+
+pub fn write_atom<M: Molecule>(
+    id: usize,
+    molecule: &M,
+    traversal: &Traversal,
+    out: &mut String,
+) -> Result<(), Error> {
+    let atom = molecule.atom(id)?;
+    let charge = to_charge(id, molecule)?;
+    let parity = match &atom.parity {
+        Some(parity) => Some(to_parity(id, parity, molecule, traversal)?),
+        None => None,
+    };
+    let configuration = match &atom.configuration {
+        Some(configuration) => {
+            Some(to_configuration(id, configuration, molecule, traversal)?)
+        }
+        None => None,
+    };
+    let aromatic = match &atom.element {
+        Some(element) => {
+            molecule.is_aromatic(id)? && is_aromatic_symbol(element, true)
+        }
+        None => false,
+    };
+    let mut valence = 0f32;
+
+    for neighbor in molecule.neighbors(id)? {
+        valence += molecule.bond_order(id, neighbor)?;
+    }
+
+    if atom.isotope.is_none()
+        && charge == 0
+        && parity.is_none()
+        && configuration.is_none()
+    {
+        match &atom.element {
+            Some(element) => {
+                if let Some(targets) = targets(element) {
+                    if implicit_hydrogens(targets, valence)
+                        == Some(atom.hydrogens)
+                    {
+                        if !aromatic {
+                            out.push_str(element.symbol());
+
+                            return Ok(());
+                        } else if is_aromatic_symbol(element, false)
+                            && is_bare_aromatic(id, molecule, targets)?
+                        {
+                            out.push_str(&element.symbol().to_lowercase());
+
+                            return Ok(());
+                        }
+                    }
+                }
+            }
+            None => {
+                if atom.hydrogens == 0 {
+                    out.push('*');
+
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    out.push('[');
+
+    if let Some(isotope) = atom.isotope {
+        if isotope > 999 {
+            return Err(Error::Isotope(id));
+        }
+
+        write!(out, "{}", isotope).expect("write isotope");
+    }
+
+    match &atom.element {
+        Some(element) if aromatic => {
+            out.push_str(&element.symbol().to_lowercase())
+        }
+        Some(element) => out.push_str(element.symbol()),
+        None => out.push('*'),
+    }
+
+    match parity {
+        Some(Parity::Negative) => out.push('@'),
+        Some(Parity::Positive) => out.push_str("@@"),
+        None => (),
+    }
+
+    match configuration {
+        Some(Configuration::Allene(Parity::Negative)) => out.push_str("@AL1"),
+        Some(Configuration::Allene(Parity::Positive)) => out.push_str("@AL2"),
+        Some(Configuration::SquarePlanar(class)) => {
+            write!(out, "@SP{}", class).expect("write configuration")
+        }
+        Some(Configuration::TrigonalBipyramidal(class)) => {
+            write!(out, "@TB{}", class).expect("write configuration")
+        }
+        Some(Configuration::Octahedral(class)) => {
+            write!(out, "@OH{}", class).expect("write configuration")
+        }
+        None => (),
+    }
+
+    match atom.hydrogens {
+        0 => (),
+        1 => out.push('H'),
+        2..=9 => write!(out, "H{}", atom.hydrogens).expect("write hydrogens"),
+        _ => return Err(Error::Hydrogens(id)),
+    }
+
+    match charge {
+        0 => (),
+        1 => out.push('+'),
+        -1 => out.push('-'),
+        _ => write!(out, "{:+}", charge).expect("write charge"),
+    }
+
+    out.push(']');
+
+    Ok(())
+}
+
+fn to_charge<M: Molecule>(id: usize, molecule: &M) -> Result<i8, Error> {
+    let charge = molecule.charge(id)?;
+
+    if charge.fract() == 0f32 && charge.abs() <= 15f32 {
+        Ok(charge as i8)
+    } else {
+        Err(Error::Charge(id))
+    }
+}
+
+// Parity is stored relative to the order of neighbors reported by the
+// Molecule, with any implicit hydrogen leading. A reader places the implicit
+// hydrogen of an atom after its parent.
+fn to_parity<M: Molecule>(
+    id: usize,
+    parity: &Parity,
+    molecule: &M,
+    traversal: &Traversal,
+) -> Result<Parity, Error> {
+    let reference = molecule.neighbors(id)?.collect::<Vec<_>>();
+    let written = traversal.written_neighbors(id);
+    let mut swaps = 0;
+
+    for (i, first) in written.iter().enumerate() {
+        for second in written.iter().skip(i + 1) {
+            let left = reference.iter().position(|id| id == first);
+            let right = reference.iter().position(|id| id == second);
+
+            if left > right {
+                swaps += 1;
+            }
+        }
+    }
+
+    let mut result = if swaps % 2 == 0 {
+        parity.clone()
+    } else {
+        parity.negate()
+    };
+
+    if traversal.parent(id).is_some() && molecule.atom(id)?.hydrogens > 0 {
+        result = result.negate();
+    }
+
+    Ok(result)
+}
+
+// Configurations are written relative to neighbors in the order a reader
+// will place them, with an implicit hydrogen following the parent. Allene
+// substituents are placed in order of appearance.
+fn to_configuration<M: Molecule>(
+    id: usize,
+    configuration: &Configuration,
+    molecule: &M,
+    traversal: &Traversal,
+) -> Result<Configuration, Error> {
+    let hydrogens = molecule.atom(id)?.hydrogens;
+    let (reference, written) = match configuration {
+        Configuration::Allene(_) => {
+            let reference = match allene_substituents(id, molecule)? {
+                Some(reference) => reference,
+                None => return Err(Error::Configuration(id)),
+            };
+            let mut written = reference.clone();
+
+            written.sort_by_key(|(terminal, neighbor)| match neighbor {
+                Some(neighbor) => traversal.position(*neighbor),
+                None => traversal.position(*terminal),
+            });
+
+            (reference, written)
+        }
+        _ => {
+            let reference = (0..hydrogens)
+                .map(|_| (id, None))
+                .chain(molecule.neighbors(id)?.map(|tid| (id, Some(tid))))
+                .collect::<Vec<_>>();
+            let mut written = traversal
+                .written_neighbors(id)
+                .into_iter()
+                .map(|tid| (id, Some(tid)))
+                .collect::<Vec<_>>();
+            let index = match traversal.parent(id) {
+                Some(_) => 1,
+                None => 0,
+            };
+
+            for _ in 0..hydrogens {
+                written.insert(index.min(written.len()), (id, None));
+            }
+
+            (reference, written)
+        }
+    };
+
+    match configuration.permute(&reference, &written) {
+        Some(configuration) => Ok(configuration),
+        None => Err(Error::Configuration(id)),
+    }
+}
+
+fn targets(element: &Element) -> Option<&'static [u8]> {
+    match element {
+        Element::B => Some(&[3]),
+        Element::C => Some(&[4]),
+        Element::N | Element::P => Some(&[3, 5]),
+        Element::O => Some(&[2]),
+        Element::S => Some(&[2, 4, 6]),
+        Element::F | Element::Cl | Element::Br | Element::I => Some(&[1]),
+        _ => None,
+    }
+}
+
+// Returns true if element has a lowercase symbol, either inside brackets
+// or outside them.
+pub fn is_aromatic_symbol(element: &Element, bracket: bool) -> bool {
+    match element {
+        Element::B
+        | Element::C
+        | Element::N
+        | Element::O
+        | Element::P
+        | Element::S => true,
+        Element::As | Element::Se => bracket,
+        _ => false,
+    }
+}
+
+// A bare aromatic atom is read with aromatic bonds of order one. It gains
+// a Kekulé double bond if this leaves it short of a target valence. The
+// atom may be written bare only if that agrees with its actual bonds.
+fn is_bare_aromatic<M: Molecule>(
+    id: usize,
+    molecule: &M,
+    targets: &[u8],
+) -> Result<bool, Error> {
+    let mut valence = 0u8;
+    let mut double = false;
+
+    for neighbor in molecule.neighbors(id)? {
+        let bond = match molecule.bond(id, neighbor)? {
+            Some(bond) => bond,
+            None => return Err(GraphError::MissingEdge(id, neighbor).into()),
+        };
+
+        if bond.aromatic {
+            valence += 1;
+            double = double || bond.electrons == 4;
+        } else {
+            valence += bond.electrons / 2;
+        }
+    }
+
+    let short = targets.iter().any(|&target| target > valence)
+        && !targets.contains(&valence);
+
+    Ok(short == double)
+}
+
+fn implicit_hydrogens(targets: &[u8], valence: f32) -> Option<u8> {
+    if valence.fract() != 0f32 {
+        return None;
+    }
+
+    let valence = valence as u8;
+
+    match targets.iter().find(|&&target| target >= valence) {
+        Some(target) => Some(target - valence),
+        None => Some(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::molecule::{Atom, Bond, DefaultMolecule, Node};
+
+    fn write_single(atom: Atom) -> Result<String, Error> {
+        let molecule = DefaultMolecule::new(vec![Node {
+            atom,
+            bonds: vec![],
+            aromatic: false,
+        }]);
+        let ranks = vec![(0, 0)].into_iter().collect();
+        let traversal = Traversal::new(&molecule, &ranks).unwrap();
+        let mut out = String::new();
+
+        write_atom(0, &molecule, &traversal, &mut out)?;
+
+        Ok(out)
+    }
+
+    #[test]
+    fn methane() {
+        assert_eq!(
+            write_single(Atom {
+                element: Some(Element::C),
+                hydrogens: 4,
+                ..Atom::default()
+            }),
+            Ok("C".to_string())
+        )
+    }
+
+    #[test]
+    fn methyl_radical() {
+        assert_eq!(
+            write_single(Atom {
+                element: Some(Element::C),
+                hydrogens: 3,
+                electrons: 1,
+                ..Atom::default()
+            }),
+            Ok("[CH3]".to_string())
+        )
+    }
+
+    #[test]
+    fn ammonium() {
+        assert_eq!(
+            write_single(Atom {
+                element: Some(Element::N),
+                hydrogens: 4,
+                ..Atom::default()
+            }),
+            Ok("[NH4+]".to_string())
+        )
+    }
+
+    #[test]
+    fn oxide_dianion() {
+        assert_eq!(
+            write_single(Atom {
+                element: Some(Element::O),
+                electrons: 8,
+                ..Atom::default()
+            }),
+            Ok("[O-2]".to_string())
+        )
+    }
+
+    #[test]
+    fn carbon_13_methane() {
+        assert_eq!(
+            write_single(Atom {
+                element: Some(Element::C),
+                isotope: Some(13),
+                hydrogens: 4,
+                ..Atom::default()
+            }),
+            Ok("[13CH4]".to_string())
+        )
+    }
+
+    #[test]
+    fn isotope_overflow() {
+        assert_eq!(
+            write_single(Atom {
+                element: Some(Element::C),
+                isotope: Some(1000),
+                hydrogens: 4,
+                ..Atom::default()
+            }),
+            Err(Error::Isotope(0))
+        )
+    }
+
+    #[test]
+    fn hydrogen_overflow() {
+        assert_eq!(
+            write_single(Atom {
+                element: None,
+                hydrogens: 10,
+                ..Atom::default()
+            }),
+            Err(Error::Hydrogens(0))
+        )
+    }
+
+    #[test]
+    fn star() {
+        assert_eq!(write_single(Atom::default()), Ok("*".to_string()))
+    }
+
+    #[test]
+    fn iron() {
+        assert_eq!(
+            write_single(Atom {
+                element: Some(Element::Fe),
+                electrons: 8,
+                ..Atom::default()
+            }),
+            Ok("[Fe]".to_string())
+        )
+    }
+
+    #[test]
+    fn half_bond() {
+        let molecule = DefaultMolecule::new(vec![
+            Node {
+                atom: Atom {
+                    element: Some(Element::H),
+                    ..Atom::default()
+                },
+                bonds: vec![Bond::new(1, None, 1)],
+                aromatic: false,
+            },
+            Node {
+                atom: Atom {
+                    element: Some(Element::H),
+                    ..Atom::default()
+                },
+                bonds: vec![Bond::new(1, None, 0)],
+                aromatic: false,
+            },
+        ]);
+        let ranks = vec![(0, 0), (1, 1)].into_iter().collect();
+        let traversal = Traversal::new(&molecule, &ranks).unwrap();
+        let mut out = String::new();
+
+        assert_eq!(
+            write_atom(0, &molecule, &traversal, &mut out),
+            Err(Error::Charge(0))
+        )
+    }
+}
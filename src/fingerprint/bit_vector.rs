@@ -0,0 +1,143 @@
+/// A fixed-length vector of bits, such as a folded fingerprint.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct BitVector {
+    blocks: Vec<u64>,
+    len: usize,
+}
+
+impl BitVector {
+    /// Returns a vector of len bits, all unset.
+    pub fn new(len: usize) -> Self {
+        Self {
+            blocks: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns true if the bit at index is set. Panics if index is out of
+    /// range.
+    pub fn get(&self, index: usize) -> bool {
+        assert!(index < self.len, "index out of range");
+
+        self.blocks[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// Sets the bit at index. Panics if index is out of range.
+    pub fn set(&mut self, index: usize) {
+        assert!(index < self.len, "index out of range");
+
+        self.blocks[index / 64] |= 1 << (index % 64);
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.blocks
+            .iter()
+            .map(|block| block.count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns the indices of set bits in ascending order.
+    pub fn ones(&self) -> Vec<usize> {
+        (0..self.len).filter(|&index| self.get(index)).collect()
+    }
+
+    /// Returns true if every bit set in other is also set here. Panics if
+    /// the lengths differ.
+    pub fn contains(&self, other: &BitVector) -> bool {
+        assert_eq!(self.len, other.len, "length mismatch");
+
+        self.blocks
+            .iter()
+            .zip(other.blocks.iter())
+            .all(|(left, right)| left & right == *right)
+    }
+
+    /// Returns the Tanimoto coefficient: bits set in both over bits set in
+    /// either, or zero if neither has bits set. Panics if the lengths
+    /// differ.
+    pub fn tanimoto(&self, other: &BitVector) -> f64 {
+        assert_eq!(self.len, other.len, "length mismatch");
+
+        let mut both = 0;
+        let mut either = 0;
+
+        for (left, right) in self.blocks.iter().zip(other.blocks.iter()) {
+            both += (left & right).count_ones();
+            either += (left | right).count_ones();
+        }
+
+        if either == 0 {
+            0.0
+        } else {
+            f64::from(both) / f64::from(either)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn bits(len: usize, ones: &[usize]) -> BitVector {
+        let mut result = BitVector::new(len);
+
+        for &index in ones {
+            result.set(index);
+        }
+
+        result
+    }
+
+    #[test]
+    fn new() {
+        let vector = BitVector::new(100);
+
+        assert_eq!(vector.len(), 100);
+        assert_eq!(vector.count_ones(), 0)
+    }
+
+    #[test]
+    fn set_and_get() {
+        let vector = bits(130, &[0, 64, 129]);
+
+        assert_eq!(vector.get(64), true);
+        assert_eq!(vector.get(65), false);
+        assert_eq!(vector.ones(), vec![0, 64, 129]);
+        assert_eq!(vector.count_ones(), 3)
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of range")]
+    fn set_out_of_range() {
+        BitVector::new(8).set(8)
+    }
+
+    #[test]
+    fn contains() {
+        let large = bits(100, &[1, 5, 70]);
+
+        assert_eq!(large.contains(&bits(100, &[5, 70])), true);
+        assert_eq!(large.contains(&bits(100, &[5, 71])), false);
+        assert_eq!(large.contains(&BitVector::new(100)), true)
+    }
+
+    #[test]
+    fn tanimoto() {
+        let left = bits(100, &[1, 2, 3]);
+        let right = bits(100, &[2, 3, 4]);
+
+        assert_eq!(left.tanimoto(&right), 0.5);
+        assert_eq!(left.tanimoto(&left), 1.0);
+        assert_eq!(BitVector::new(8).tanimoto(&BitVector::new(8)), 0.0)
+    }
+}
@@ -0,0 +1,7 @@
+/// The atom environment behind a fingerprint identifier: all atoms within
+/// radius bonds of center.
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone)]
+pub struct Environment {
+    pub center: usize,
+    pub radius: usize,
+}
@@ -0,0 +1,29 @@
+/// Combines values in order into a 32-bit hash. Unlike std's hashers, the
+/// result is the same on every platform and release, so fingerprints
+/// may be stored and compared later.
+pub fn hash(values: &[u32]) -> u32 {
+    values.iter().fold(0, |seed: u32, &value| {
+        seed ^ value
+            .wrapping_add(0x9e37_79b9)
+            .wrapping_add(seed << 6)
+            .wrapping_add(seed >> 2)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    use super::*;
+
+    #[test]
+    fn stable() {
+        assert_eq!(hash(&[1, 2, 3]), hash(&[1, 2, 3]));
+        assert_eq!(hash(&[1, 2, 3]), 4_216_901_971)
+    }
+
+    #[test]
+    fn order_matters() {
+        assert_ne!(hash(&[1, 2]), hash(&[2, 1]))
+    }
+}
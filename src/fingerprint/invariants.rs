@@ -0,0 +1,11 @@
+/// The atom invariants from which Morgan identifiers are grown.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Invariants {
+    /// Atomic number, mass number, heavy atom degree, total hydrogen
+    /// count, charge and ring membership, as in ECFP.
+    Connectivity,
+    /// Pharmacophoric roles, as in FCFP: hydrogen bond donor, acceptor,
+    /// aromatic, halogen, basic and acidic. Atoms of the same roles are
+    /// interchangeable.
+    Feature,
+}
@@ -0,0 +1,13 @@
+mod bit_vector;
+mod environment;
+mod hash;
+mod invariants;
+mod morgan;
+mod path_fingerprint;
+
+pub use bit_vector::BitVector;
+pub use environment::Environment;
+use hash::hash;
+pub use invariants::Invariants;
+pub use morgan::Morgan;
+pub use path_fingerprint::path_fingerprint;
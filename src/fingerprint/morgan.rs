@@ -0,0 +1,474 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use gamma::graph::Error;
+
+use super::{hash, BitVector, Environment, Invariants};
+use crate::daylight::{read_smarts, QueryMolecule};
+use crate::molecule::{Element, Molecule};
+use crate::substructure::{anchored_match, Target};
+
+// Pharmacophoric roles for feature invariants, after Gobbi and Poppinger.
+const FEATURES: [&str; 6] = [
+    // donor
+    "[$([N;!H0;v3]),$([N;!H0;+1;v4]),$([O,S;H1;+0]),$([n;H1;+0])]",
+    // acceptor
+    "[$([O,S;H1;v2]-[!$(*=[O,N,P,S])]),$([O,S;H0;v2]),$([O,S;-]),\
+     $([O,S;H0;v1]),$([N;v3;!$(N-*=!@[O,N,P,S])]),$([nH0,o,s;+0]),$([F])]",
+    // aromatic
+    "[a]",
+    // halogen
+    "[F,Cl,Br,I]",
+    // basic
+    "[#7;+,$([N;H2&+0][$([C,a]);!$([C,a](=O))]),\
+     $([N;H1&+0]([$([C,a]);!$([C,a](=O))])[$([C,a]);!$([C,a](=O))]),\
+     $([N;H0&+0]([C;!$(C(=O))])([C;!$(C(=O))])[C;!$(C(=O))])]",
+    // acidic
+    "[$([C,S](=[O,S,P])-[O;H1,-1])]",
+];
+
+/// A Morgan circular fingerprint, known as ECFP with connectivity
+/// invariants and FCFP with feature invariants.
+///
+/// Each heavy atom starts with an identifier hashed from its invariants.
+/// Every round, an atom's identifier is rehashed with the round number,
+/// its own identifier and those of its neighbors, each paired with the
+/// bond leading there, so that after n rounds it describes the
+/// environment within n bonds. An environment spanning the same bonds as
+/// one recorded earlier is dropped. Identifiers are the same for any atom
+/// order.
+#[derive(Debug, PartialEq)]
+pub struct Morgan {
+    environments: BTreeMap<u32, Vec<Environment>>,
+}
+
+impl Morgan {
+    /// Computes identifiers for the environments of every heavy atom of
+    /// molecule out to radius bonds. Hydrogen atoms take no part, other
+    /// than by adding to the hydrogen count of their neighbor. Bonds are
+    /// told apart by order, with aromaticity perceived under the Daylight
+    /// model.
+    pub fn new<M: Molecule>(
+        molecule: &M,
+        radius: usize,
+        invariants: &Invariants,
+    ) -> Result<Self, Error> {
+        let target = Target::new(molecule)?;
+        let mut atoms = Vec::new();
+        let mut neighbors = HashMap::new();
+
+        for id in molecule.ids() {
+            if molecule.atom(id)?.element == Some(Element::H) {
+                continue;
+            }
+
+            let mut outs = Vec::new();
+
+            for tid in molecule.neighbors(id)? {
+                if molecule.atom(tid)?.element == Some(Element::H) {
+                    continue;
+                }
+
+                let bond = match molecule.bond(id, tid)? {
+                    Some(bond) => bond,
+                    None => return Err(Error::MissingEdge(id, tid)),
+                };
+                let code = if target.aromaticity().is_aromatic_bond(id, tid) {
+                    3
+                } else {
+                    u32::from(bond.electrons)
+                };
+
+                outs.push((tid, code));
+            }
+
+            atoms.push(id);
+            neighbors.insert(id, outs);
+        }
+
+        let features = match invariants {
+            Invariants::Connectivity => Vec::new(),
+            Invariants::Feature => FEATURES
+                .iter()
+                .map(|smarts| read_smarts(smarts).expect("feature SMARTS"))
+                .collect(),
+        };
+        let mut identifiers = HashMap::new();
+        let mut neighborhoods = HashMap::new();
+        let mut seen = HashSet::new();
+        let mut environments: BTreeMap<u32, Vec<Environment>> = BTreeMap::new();
+
+        for &id in atoms.iter() {
+            let identifier = match invariants {
+                Invariants::Connectivity => {
+                    connectivity(id, &target, neighbors[&id].len())?
+                }
+                Invariants::Feature => feature(id, &target, &features)?,
+            };
+
+            identifiers.insert(id, identifier);
+            neighborhoods.insert(id, BTreeSet::new());
+            environments
+                .entry(identifier)
+                .or_default()
+                .push(Environment {
+                    center: id,
+                    radius: 0,
+                });
+        }
+
+        seen.insert(BTreeSet::new());
+
+        for round in 1..=radius {
+            let mut next_identifiers = HashMap::new();
+            let mut next_neighborhoods = HashMap::new();
+
+            for &id in atoms.iter() {
+                let mut neighborhood: BTreeSet<(usize, usize)> =
+                    neighborhoods[&id].clone();
+                let mut pairs = Vec::new();
+
+                for &(tid, code) in neighbors[&id].iter() {
+                    pairs.push((code, identifiers[&tid]));
+                    neighborhood.insert((id.min(tid), id.max(tid)));
+                    neighborhood.extend(neighborhoods[&tid].iter().cloned());
+                }
+
+                pairs.sort_unstable();
+
+                let mut values = vec![round as u32, identifiers[&id]];
+
+                for (code, identifier) in pairs {
+                    values.push(code);
+                    values.push(identifier);
+                }
+
+                next_identifiers.insert(id, hash(&values));
+                next_neighborhoods.insert(id, neighborhood);
+            }
+
+            // Of environments spanning the same bonds, the one with the
+            // lowest identifier is kept.
+            let mut order = atoms.clone();
+
+            order.sort_by(|left, right| {
+                (&next_neighborhoods[left], next_identifiers[left])
+                    .cmp(&(&next_neighborhoods[right], next_identifiers[right]))
+            });
+
+            for id in order {
+                if seen.insert(next_neighborhoods[&id].clone()) {
+                    environments
+                        .entry(next_identifiers[&id])
+                        .or_default()
+                        .push(Environment {
+                            center: id,
+                            radius: round,
+                        });
+                }
+            }
+
+            identifiers = next_identifiers;
+            neighborhoods = next_neighborhoods;
+        }
+
+        for environments in environments.values_mut() {
+            environments.sort();
+        }
+
+        Ok(Self { environments })
+    }
+
+    /// Returns each identifier with the number of environments giving it,
+    /// the unfolded count form of the fingerprint.
+    pub fn counts(&self) -> BTreeMap<u32, usize> {
+        self.environments
+            .iter()
+            .map(|(&identifier, environments)| (identifier, environments.len()))
+            .collect()
+    }
+
+    /// Returns the environments giving identifier, ordered by center and
+    /// radius. Empty if there are none.
+    pub fn environments(&self, identifier: u32) -> &[Environment] {
+        match self.environments.get(&identifier) {
+            Some(environments) => environments,
+            None => &[],
+        }
+    }
+
+    /// Returns the fingerprint folded to size bits, with each identifier
+    /// setting the bit at identifier modulo size. Panics if size is zero.
+    pub fn bits(&self, size: usize) -> BitVector {
+        let mut result = BitVector::new(size);
+
+        for &identifier in self.environments.keys() {
+            result.set(fold(identifier, size));
+        }
+
+        result
+    }
+
+    /// Returns, for each bit set in `bits(size)`, the environments setting
+    /// it, ordered by center and radius. Panics if size is zero.
+    pub fn bit_info(&self, size: usize) -> BTreeMap<usize, Vec<Environment>> {
+        let mut result: BTreeMap<usize, Vec<Environment>> = BTreeMap::new();
+
+        for (&identifier, environments) in self.environments.iter() {
+            result
+                .entry(fold(identifier, size))
+                .or_default()
+                .extend(environments.iter().cloned());
+        }
+
+        for environments in result.values_mut() {
+            environments.sort();
+        }
+
+        result
+    }
+}
+
+fn fold(identifier: u32, size: usize) -> usize {
+    assert!(size > 0, "zero size");
+
+    identifier as usize % size
+}
+
+fn connectivity<M: Molecule>(
+    id: usize,
+    target: &Target<M>,
+    degree: usize,
+) -> Result<u32, Error> {
+    let molecule = target.molecule();
+    let atom = molecule.atom(id)?;
+    let mut hydrogens = u32::from(atom.hydrogens);
+
+    for tid in molecule.neighbors(id)? {
+        if molecule.atom(tid)?.element == Some(Element::H) {
+            hydrogens += 1;
+        }
+    }
+
+    Ok(hash(&[
+        atom.element
+            .as_ref()
+            .map_or(0, |e| u32::from(e.atomic_number())),
+        atom.isotope.map_or(0, u32::from),
+        degree as u32,
+        hydrogens,
+        molecule.charge(id)?.round() as i32 as u32,
+        u32::from(target.rings().is_in_ring(id)),
+    ]))
+}
+
+fn feature<M: Molecule>(
+    id: usize,
+    target: &Target<M>,
+    features: &[QueryMolecule],
+) -> Result<u32, Error> {
+    let mut flags = 0;
+
+    for (index, query) in features.iter().enumerate() {
+        if anchored_match(query, target, id)? {
+            flags |= 1 << index;
+        }
+    }
+
+    Ok(hash(&[flags]))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    use super::*;
+    use crate::daylight::read_smiles;
+    use crate::molecule::DefaultMolecule;
+
+    fn morgan(smiles: &str, radius: usize, invariants: &Invariants) -> Morgan {
+        let molecule = read_smiles(smiles, None).unwrap();
+
+        Morgan::new(&molecule, radius, invariants).unwrap()
+    }
+
+    fn ecfp(smiles: &str, radius: usize) -> BTreeMap<u32, usize> {
+        morgan(smiles, radius, &Invariants::Connectivity).counts()
+    }
+
+    fn fcfp(smiles: &str, radius: usize) -> BTreeMap<u32, usize> {
+        morgan(smiles, radius, &Invariants::Feature).counts()
+    }
+
+    #[test]
+    fn empty() {
+        let molecule = DefaultMolecule::new(vec![]);
+        let fingerprint =
+            Morgan::new(&molecule, 2, &Invariants::Connectivity).unwrap();
+
+        assert_eq!(fingerprint.counts(), BTreeMap::new())
+    }
+
+    #[test]
+    fn methane() {
+        assert_eq!(ecfp("C", 2).values().cloned().collect::<Vec<_>>(), vec![1])
+    }
+
+    #[test]
+    fn ethane() {
+        let mut counts = ecfp("CC", 2).values().cloned().collect::<Vec<_>>();
+
+        counts.sort_unstable();
+
+        assert_eq!(counts, vec![1, 2])
+    }
+
+    #[test]
+    fn radius_zero() {
+        assert_eq!(ecfp("CCO", 0).len(), 3);
+        assert_eq!(ecfp("CCO", 1).len(), 6)
+    }
+
+    #[test]
+    fn duplicate_environments_dropped() {
+        // radius 2 spans the same bonds as radius 1 at the central carbon
+        assert_eq!(ecfp("CCO", 2).len(), ecfp("CCO", 1).len())
+    }
+
+    #[test]
+    fn atom_order() {
+        assert_eq!(ecfp("OCC(=O)N", 3), ecfp("NC(=O)CO", 3));
+        assert_eq!(ecfp("c1ccccc1O", 2), ecfp("Oc1ccccc1", 2))
+    }
+
+    #[test]
+    fn explicit_hydrogens() {
+        assert_eq!(ecfp("[H]C([H])([H])C", 2), ecfp("CC", 2))
+    }
+
+    #[test]
+    fn kekule_forms() {
+        assert_eq!(ecfp("C1=CC=CC=C1", 2), ecfp("c1ccccc1", 2))
+    }
+
+    #[test]
+    fn invariants_distinguish() {
+        assert_ne!(ecfp("CCO", 1), ecfp("CCN", 1));
+        assert_ne!(ecfp("CCC[13CH3]", 1), ecfp("CCCC", 1));
+        assert_ne!(ecfp("CC[O-]", 1), ecfp("CCO", 1));
+        assert_ne!(ecfp("C1CCCCC1", 0), ecfp("CCCCCC", 0))
+    }
+
+    #[test]
+    fn bonds_distinguish() {
+        assert_ne!(ecfp("C=C", 1), ecfp("CC", 1));
+        assert_ne!(ecfp("C=CC", 1), ecfp("C#CC", 1))
+    }
+
+    #[test]
+    fn features() {
+        assert_eq!(fcfp("CCCl", 2), fcfp("CCBr", 2));
+        assert_ne!(ecfp("CCCl", 2), ecfp("CCBr", 2));
+        assert_ne!(fcfp("CCO", 2), fcfp("CCC", 2));
+        assert_ne!(fcfp("CC(=O)O", 0), fcfp("CC(=O)OC", 0))
+    }
+
+    #[test]
+    fn feature_roles() {
+        let features = FEATURES
+            .iter()
+            .map(|smarts| read_smarts(smarts).unwrap())
+            .collect::<Vec<_>>();
+        let roles = |smiles: &str, id: usize| {
+            let molecule = read_smiles(smiles, None).unwrap();
+
+            feature(id, &Target::new(&molecule).unwrap(), &features).unwrap()
+        };
+
+        // donor 1, acceptor 2, aromatic 4, halogen 8, basic 16, acidic 32
+        assert_eq!(roles("CCN", 2), hash(&[1 | 2 | 16]));
+        assert_eq!(roles("c1ccncc1", 3), hash(&[2 | 4]));
+        assert_eq!(roles("CC(=O)N", 3), hash(&[1]));
+        assert_eq!(roles("CC(=O)O", 1), hash(&[32]));
+        assert_eq!(roles("CC(=O)O", 3), hash(&[1]));
+        assert_eq!(roles("CCO", 2), hash(&[1 | 2]));
+        assert_eq!(roles("CF", 1), hash(&[2 | 8]));
+        assert_eq!(roles("CC", 0), hash(&[0]))
+    }
+
+    #[test]
+    fn bits() {
+        let fingerprint =
+            morgan("CC(=O)Oc1ccccc1C(=O)O", 2, &Invariants::Connectivity);
+        let bits = fingerprint.bits(2048);
+
+        assert_eq!(bits.len(), 2048);
+
+        for identifier in fingerprint.counts().keys() {
+            assert!(bits.get(*identifier as usize % 2048))
+        }
+
+        assert!(bits.count_ones() <= fingerprint.counts().len())
+    }
+
+    #[test]
+    #[should_panic(expected = "zero size")]
+    fn bits_zero_size() {
+        morgan("C", 1, &Invariants::Connectivity).bits(0);
+    }
+
+    #[test]
+    fn environments() {
+        let fingerprint = morgan("CCO", 1, &Invariants::Connectivity);
+        let oxygen = fingerprint.counts().keys().cloned().find(|&identifier| {
+            fingerprint.environments(identifier)
+                == [Environment {
+                    center: 2,
+                    radius: 1,
+                }]
+        });
+
+        assert!(oxygen.is_some());
+        assert_eq!(fingerprint.environments(0).len(), 0)
+    }
+
+    #[test]
+    fn bit_info() {
+        let fingerprint = morgan("CCO", 2, &Invariants::Connectivity);
+        let info = fingerprint.bit_info(1);
+
+        assert_eq!(
+            info,
+            vec![(
+                0,
+                vec![
+                    Environment {
+                        center: 0,
+                        radius: 0
+                    },
+                    Environment {
+                        center: 0,
+                        radius: 1
+                    },
+                    Environment {
+                        center: 1,
+                        radius: 0
+                    },
+                    Environment {
+                        center: 1,
+                        radius: 1
+                    },
+                    Environment {
+                        center: 2,
+                        radius: 0
+                    },
+                    Environment {
+                        center: 2,
+                        radius: 1
+                    },
+                ]
+            )]
+            .into_iter()
+            .collect()
+        )
+    }
+}
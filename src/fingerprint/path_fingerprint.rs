@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use gamma::graph::Error;
+
+use super::{hash, BitVector};
+use crate::aromaticity::{Aromaticity, Model};
+use crate::molecule::Molecule;
+
+/// Returns a Daylight-style path fingerprint of size bits. Every simple
+/// path of up to length bonds, including single atoms, is hashed from
+/// the element and charge of its atoms and the bonds joining them, read
+/// in whichever direction gives the smaller label sequence, and sets one
+/// bit. Bonds are labeled aromatic under the Daylight model, or else by
+/// their electrons.
+///
+/// These are exactly the labels a `substructure::MoleculeQuery` requires
+/// to agree, so whenever a query molecule matches a target, every bit of
+/// the query's fingerprint is set in the target's, given the same length
+/// and size. A target whose bits don't contain the query's can therefore
+/// be skipped. Paths through atoms without an element, which match any
+/// atom, are left out. Panics if size is zero.
+pub fn path_fingerprint<M: Molecule>(
+    molecule: &M,
+    length: usize,
+    size: usize,
+) -> Result<BitVector, Error> {
+    assert!(size > 0, "zero size");
+
+    let aromaticity = Aromaticity::new(molecule, &Model::Daylight)?;
+    let mut atoms = HashMap::new();
+    let mut bonds = HashMap::new();
+    let mut result = BitVector::new(size);
+
+    for id in molecule.ids() {
+        if let Some(element) = &molecule.atom(id)?.element {
+            atoms.insert(
+                id,
+                (
+                    u32::from(element.atomic_number()),
+                    molecule.charge(id)?.round() as i32 as u32,
+                ),
+            );
+        }
+    }
+
+    for (sid, tid) in molecule.edges() {
+        let code = if aromaticity.is_aromatic_bond(sid, tid) {
+            3
+        } else {
+            match molecule.bond(sid, tid)? {
+                Some(bond) => u32::from(bond.electrons),
+                None => return Err(Error::MissingEdge(sid, tid)),
+            }
+        };
+
+        bonds.insert((sid, tid), code);
+        bonds.insert((tid, sid), code);
+    }
+
+    for id in molecule.ids() {
+        if atoms.contains_key(&id) {
+            extend(
+                &mut vec![id],
+                molecule,
+                length,
+                &atoms,
+                &bonds,
+                &mut result,
+            )?;
+        }
+    }
+
+    Ok(result)
+}
+
+// Sets the bit of path, then of each longer path beginning with it.
+fn extend<M: Molecule>(
+    path: &mut Vec<usize>,
+    molecule: &M,
+    length: usize,
+    atoms: &HashMap<usize, (u32, u32)>,
+    bonds: &HashMap<(usize, usize), u32>,
+    result: &mut BitVector,
+) -> Result<(), Error> {
+    let first = path[0];
+    let last = path[path.len() - 1];
+
+    // each path of two or more atoms is reached from both ends
+    if first <= last {
+        let forward = labels(path.iter(), atoms, bonds);
+        let reverse = labels(path.iter().rev(), atoms, bonds);
+        let size = result.len();
+
+        result.set(hash(&forward.min(reverse)) as usize % size);
+    }
+
+    if path.len() > length {
+        return Ok(());
+    }
+
+    for neighbor in molecule.neighbors(last)? {
+        if !atoms.contains_key(&neighbor) || path.contains(&neighbor) {
+            continue;
+        }
+
+        path.push(neighbor);
+        extend(path, molecule, length, atoms, bonds, result)?;
+        path.pop();
+    }
+
+    Ok(())
+}
+
+// Returns atom and bond labels in path order.
+fn labels<'a, I: Iterator<Item = &'a usize>>(
+    path: I,
+    atoms: &HashMap<usize, (u32, u32)>,
+    bonds: &HashMap<(usize, usize), u32>,
+) -> Vec<u32> {
+    let mut result = Vec::new();
+    let mut previous: Option<usize> = None;
+
+    for &id in path {
+        if let Some(previous) = previous {
+            result.push(bonds[&(previous, id)]);
+        }
+
+        let (element, charge) = atoms[&id];
+
+        result.push(element);
+        result.push(charge);
+        previous = Some(id);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::read_smiles;
+    use crate::molecule::DefaultMolecule;
+    use crate::substructure::{substructure_match, MoleculeQuery};
+
+    fn fingerprint(smiles: &str, length: usize) -> BitVector {
+        let molecule = read_smiles(smiles, None).unwrap();
+
+        path_fingerprint(&molecule, length, 2048).unwrap()
+    }
+
+    #[test]
+    fn empty() {
+        let molecule = DefaultMolecule::new(vec![]);
+
+        assert_eq!(
+            path_fingerprint(&molecule, 7, 1024),
+            Ok(BitVector::new(1024))
+        )
+    }
+
+    #[test]
+    #[should_panic(expected = "zero size")]
+    fn zero_size() {
+        let molecule = DefaultMolecule::new(vec![]);
+
+        path_fingerprint(&molecule, 7, 0).unwrap();
+    }
+
+    #[test]
+    fn atoms_only() {
+        assert_eq!(fingerprint("CCO", 0).count_ones(), 2)
+    }
+
+    #[test]
+    fn direction() {
+        // C, O, C-C, C-O, C-C-O
+        assert_eq!(fingerprint("CCO", 7).count_ones(), 5);
+        assert_eq!(fingerprint("OCC", 7), fingerprint("CCO", 7))
+    }
+
+    #[test]
+    fn length_limits_paths() {
+        assert_eq!(fingerprint("CCCCCC", 2).count_ones(), 3);
+        assert_eq!(fingerprint("CCCCCC", 7).count_ones(), 6)
+    }
+
+    #[test]
+    fn kekule_forms() {
+        assert_eq!(fingerprint("C1=CC=CC=C1", 7), fingerprint("c1ccccc1", 7))
+    }
+
+    #[test]
+    fn labels_distinguish() {
+        assert!(!fingerprint("CCO", 7).contains(&fingerprint("CCN", 7)));
+        assert!(!fingerprint("CC", 7).contains(&fingerprint("C=C", 7)));
+        assert!(!fingerprint("CC[O-]", 7).contains(&fingerprint("CCO", 7)));
+        assert!(!fingerprint("CCCCCC", 7).contains(&fingerprint("c1ccccc1", 7)))
+    }
+
+    #[test]
+    fn wildcard_paths_left_out() {
+        assert_eq!(fingerprint("C*C", 7), fingerprint("C.C", 7))
+    }
+
+    #[test]
+    fn superset_property() {
+        let pairs = [
+            ("CC", "CCO"),
+            ("CO", "OCC(=O)O"),
+            ("C=O", "CC(=O)Nc1ccccc1"),
+            ("c1ccccc1", "Cc1ccc(O)cc1"),
+            ("C1=CC=CC=C1", "c1ccc2ccccc2c1"),
+            ("[O-]C=O", "CC(=O)[O-]"),
+            ("C*O", "CC(C)(C)O"),
+            ("[13CH4]", "[13CH3]O"),
+            ("[H]OC", "[H]OCC"),
+            ("C1CCCCC1", "C1CCC2CCCCC2C1"),
+            ("N#CC", "N#CCC(=O)N"),
+            ("C[C@H](N)O", "C[C@@H](N)OC"),
+        ];
+
+        for (query, target) in pairs.iter() {
+            let query = read_smiles(query, None).unwrap();
+            let target = read_smiles(target, None).unwrap();
+            let matcher = MoleculeQuery::new(&query, false).unwrap();
+
+            assert!(substructure_match(&matcher, &target).unwrap().is_some());
+
+            for &length in &[0, 1, 3, 7] {
+                let query_bits = path_fingerprint(&query, length, 256).unwrap();
+                let target_bits =
+                    path_fingerprint(&target, length, 256).unwrap();
+
+                assert!(target_bits.contains(&query_bits))
+            }
+        }
+    }
+}
@@ -0,0 +1,9 @@
+/// Errors encountered when parsing a formula string. Positions are
+/// zero-based character offsets.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Character(usize),
+    Element(usize),
+    Isotope(usize),
+    EndOfLine,
+}
@@ -0,0 +1,430 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use gamma::graph::Error as GraphError;
+
+use super::Error;
+use crate::molecule::{Element, Molecule};
+
+/// Atom counts keyed by element and isotope, together with a net charge.
+/// An isotope of None denotes natural isotopic composition.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Formula {
+    pub counts: HashMap<(Element, Option<u16>), usize>,
+    pub charge: i32,
+}
+
+/// Returns the Formula of molecule, including implicit hydrogens. Atoms
+/// without an element are ignored.
+pub fn formula<M: Molecule>(molecule: &M) -> Result<Formula, GraphError> {
+    let mut result = Formula::default();
+    let mut charge = 0f32;
+
+    for id in molecule.ids() {
+        let atom = molecule.atom(id)?;
+        let element = match &atom.element {
+            Some(element) => element,
+            None => continue,
+        };
+
+        result.add(element.clone(), atom.isotope, 1);
+        result.add(Element::H, None, atom.hydrogens as usize);
+
+        charge += molecule.charge(id)?;
+    }
+
+    result.charge = charge.round() as i32;
+
+    Ok(result)
+}
+
+impl Formula {
+    /// Returns the total number of atoms of element, counting all isotopes.
+    pub fn count(&self, element: &Element) -> usize {
+        self.counts
+            .iter()
+            .filter(|((other, _), _)| other == element)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    fn add(&mut self, element: Element, isotope: Option<u16>, count: usize) {
+        if count > 0 {
+            *self.counts.entry((element, isotope)).or_insert(0) += count
+        }
+    }
+}
+
+/// Writes the formula in Hill order: carbon, then hydrogen, then the
+/// remaining elements alphabetically. Without carbon, all elements
+/// including hydrogen are alphabetical. Isotopes are bracketed and follow
+/// the element with natural composition, as in `C5[13C]H6`. A nonzero
+/// charge is appended as `+`, `-`, `+2`, and so on.
+impl fmt::Display for Formula {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let carbon = self.count(&Element::C) > 0;
+        let mut entries = self
+            .counts
+            .iter()
+            .filter(|(_, &count)| count > 0)
+            .collect::<Vec<_>>();
+
+        entries.sort_by_key(|((element, isotope), _)| {
+            let rank = match element {
+                Element::C if carbon => 0,
+                Element::H if carbon => 1,
+                _ => 2,
+            };
+
+            (rank, element.symbol(), *isotope)
+        });
+
+        for ((element, isotope), &count) in entries {
+            match isotope {
+                Some(isotope) => write!(f, "[{}{}]", isotope, element)?,
+                None => write!(f, "{}", element)?,
+            }
+
+            if count > 1 {
+                write!(f, "{}", count)?;
+            }
+        }
+
+        match self.charge {
+            0 => Ok(()),
+            1 => f.write_str("+"),
+            -1 => f.write_str("-"),
+            charge if charge > 0 => write!(f, "+{}", charge),
+            charge => write!(f, "{}", charge),
+        }
+    }
+}
+
+/// Parses a formula such as `C2H6O`, `C5[13C]H6` or `C2H3O2-`. Elements
+/// may appear in any order and repeat, but counts must be positive and
+/// the string must not be empty.
+impl FromStr for Formula {
+    type Err = Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let chars = string.chars().collect::<Vec<_>>();
+        let mut cursor = 0;
+        let mut result = Formula::default();
+
+        if chars.is_empty() {
+            return Err(Error::Character(0));
+        }
+
+        while cursor < chars.len() {
+            match chars[cursor] {
+                '+' | '-' => {
+                    result.charge = read_charge(&chars, &mut cursor)?;
+
+                    if cursor < chars.len() {
+                        return Err(Error::Character(cursor));
+                    }
+                }
+                '[' => {
+                    let start = cursor;
+
+                    cursor += 1;
+
+                    let isotope = match read_number(&chars, &mut cursor) {
+                        Some(isotope) => isotope,
+                        None => return Err(character(&chars, cursor)),
+                    };
+                    let element = read_element(&chars, &mut cursor)?;
+
+                    match chars.get(cursor) {
+                        Some(']') => cursor += 1,
+                        _ => return Err(character(&chars, cursor)),
+                    }
+
+                    let isotope = match u16::try_from(isotope) {
+                        Ok(isotope)
+                            if element.is_valid_mass_number(isotope) =>
+                        {
+                            isotope
+                        }
+                        _ => return Err(Error::Isotope(start)),
+                    };
+                    let count = read_count(&chars, &mut cursor)?;
+
+                    result.add(element, Some(isotope), count)
+                }
+                _ => {
+                    let element = read_element(&chars, &mut cursor)?;
+                    let count = read_count(&chars, &mut cursor)?;
+
+                    result.add(element, None, count)
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+fn read_element(chars: &[char], cursor: &mut usize) -> Result<Element, Error> {
+    let start = *cursor;
+
+    match chars.get(*cursor) {
+        Some(c) if c.is_ascii_uppercase() => *cursor += 1,
+        _ => return Err(character(chars, *cursor)),
+    }
+
+    if let Some(c) = chars.get(*cursor) {
+        if c.is_ascii_lowercase() {
+            *cursor += 1;
+        }
+    }
+
+    chars[start..*cursor]
+        .iter()
+        .collect::<String>()
+        .parse::<Element>()
+        .map_err(|_| Error::Element(start))
+}
+
+fn read_number(chars: &[char], cursor: &mut usize) -> Option<usize> {
+    let mut result: Option<usize> = None;
+
+    while let Some(digit) = chars.get(*cursor).and_then(|c| c.to_digit(10)) {
+        result = Some(
+            result
+                .unwrap_or(0)
+                .saturating_mul(10)
+                .saturating_add(digit as usize),
+        );
+        *cursor += 1;
+    }
+
+    result
+}
+
+// Reads the count following an element, one if none is written.
+fn read_count(chars: &[char], cursor: &mut usize) -> Result<usize, Error> {
+    let start = *cursor;
+
+    match read_number(chars, cursor) {
+        Some(0) => Err(Error::Character(start)),
+        Some(count) => Ok(count),
+        None => Ok(1),
+    }
+}
+
+fn read_charge(chars: &[char], cursor: &mut usize) -> Result<i32, Error> {
+    let sign = if chars[*cursor] == '+' { 1 } else { -1 };
+    let start = *cursor;
+
+    *cursor += 1;
+
+    match read_number(chars, cursor) {
+        Some(magnitude) if magnitude > i32::MAX as usize => {
+            Err(Error::Character(start + 1))
+        }
+        Some(magnitude) => Ok(sign * magnitude as i32),
+        None => Ok(sign),
+    }
+}
+
+fn character(chars: &[char], cursor: usize) -> Error {
+    if cursor < chars.len() {
+        Error::Character(cursor)
+    } else {
+        Error::EndOfLine
+    }
+}
+
+#[cfg(test)]
+mod formula {
+    use pretty_assertions::assert_eq;
+
+    use crate::daylight::read_smiles;
+
+    fn hill(smiles: &str) -> String {
+        super::formula(&read_smiles(smiles, None).unwrap())
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn benzene() {
+        assert_eq!(hill("c1ccccc1"), "C6H6")
+    }
+
+    #[test]
+    fn ethanol() {
+        assert_eq!(hill("OCC"), "C2H6O")
+    }
+
+    #[test]
+    fn no_carbon() {
+        assert_eq!(hill("OS(=O)(=O)O"), "H2O4S")
+    }
+
+    #[test]
+    fn hydrogen_chloride() {
+        assert_eq!(hill("Cl"), "ClH")
+    }
+
+    #[test]
+    fn isotope() {
+        assert_eq!(hill("[13CH4]"), "[13C]H4")
+    }
+
+    #[test]
+    fn isotope_follows_element() {
+        assert_eq!(hill("[13CH3]C"), "C[13C]H6")
+    }
+
+    #[test]
+    fn deuterium() {
+        assert_eq!(hill("[2H]C"), "CH3[2H]")
+    }
+
+    #[test]
+    fn acetate() {
+        assert_eq!(hill("CC(=O)[O-]"), "C2H3O2-")
+    }
+
+    #[test]
+    fn dication() {
+        assert_eq!(hill("[Ca+2]"), "Ca+2")
+    }
+
+    #[test]
+    fn disconnected() {
+        assert_eq!(hill("[Na+].[Cl-]"), "ClNa")
+    }
+
+    #[test]
+    fn star() {
+        assert_eq!(hill("*C"), "CH3")
+    }
+}
+
+#[cfg(test)]
+mod from_str {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn counts(
+        entries: Vec<(Element, Option<u16>, usize)>,
+    ) -> HashMap<(Element, Option<u16>), usize> {
+        entries
+            .into_iter()
+            .map(|(element, isotope, count)| ((element, isotope), count))
+            .collect()
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!("".parse::<Formula>(), Err(Error::Character(0)))
+    }
+
+    #[test]
+    fn ethanol() {
+        assert_eq!(
+            "C2H6O".parse::<Formula>(),
+            Ok(Formula {
+                counts: counts(vec![
+                    (Element::C, None, 2),
+                    (Element::H, None, 6),
+                    (Element::O, None, 1)
+                ]),
+                charge: 0
+            })
+        )
+    }
+
+    #[test]
+    fn repeated_element() {
+        assert_eq!(
+            "CH3COOH".parse::<Formula>(),
+            Ok(Formula {
+                counts: counts(vec![
+                    (Element::C, None, 2),
+                    (Element::H, None, 4),
+                    (Element::O, None, 2)
+                ]),
+                charge: 0
+            })
+        )
+    }
+
+    #[test]
+    fn isotope() {
+        assert_eq!(
+            "C5[13C]2H6".parse::<Formula>(),
+            Ok(Formula {
+                counts: counts(vec![
+                    (Element::C, None, 5),
+                    (Element::C, Some(13), 2),
+                    (Element::H, None, 6)
+                ]),
+                charge: 0
+            })
+        )
+    }
+
+    #[test]
+    fn anion() {
+        assert_eq!("C2H3O2-".parse::<Formula>().unwrap().charge, -1)
+    }
+
+    #[test]
+    fn dication() {
+        assert_eq!("Ca+2".parse::<Formula>().unwrap().charge, 2)
+    }
+
+    #[test]
+    fn unknown_element() {
+        assert_eq!("CXx".parse::<Formula>(), Err(Error::Element(1)))
+    }
+
+    #[test]
+    fn lowercase_start() {
+        assert_eq!("cH4".parse::<Formula>(), Err(Error::Character(0)))
+    }
+
+    #[test]
+    fn text_after_charge() {
+        assert_eq!("CH3+C".parse::<Formula>(), Err(Error::Character(4)))
+    }
+
+    #[test]
+    fn unclosed_isotope() {
+        assert_eq!("[13C".parse::<Formula>(), Err(Error::EndOfLine))
+    }
+
+    #[test]
+    fn isotope_below_atomic_number() {
+        assert_eq!("[5C]".parse::<Formula>(), Err(Error::Isotope(0)))
+    }
+
+    #[test]
+    fn zero_count() {
+        assert_eq!("C0H4".parse::<Formula>(), Err(Error::Character(1)))
+    }
+
+    #[test]
+    fn zero_isotope_count() {
+        assert_eq!("[13C]00".parse::<Formula>(), Err(Error::Character(5)))
+    }
+
+    #[test]
+    fn unknown_isotope() {
+        assert_eq!("C[60000C]".parse::<Formula>(), Err(Error::Isotope(1)))
+    }
+
+    #[test]
+    fn round_trip() {
+        let formula = "C5[13C]H5[2H]NO2-".parse::<Formula>().unwrap();
+
+        assert_eq!(formula.to_string(), "C5[13C]H5[2H]NO2-")
+    }
+}
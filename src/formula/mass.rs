@@ -0,0 +1,149 @@
+use gamma::graph::Error;
+
+use super::{formula, Formula};
+use crate::molecule::{Element, Molecule};
+
+/// Mass of the electron in unified atomic mass units.
+pub const ELECTRON_MASS: f64 = 0.000_548_579_909;
+
+/// Returns the average molecular mass of molecule, using standard atomic
+/// weights for atoms of natural composition.
+pub fn average_mass<M: Molecule>(molecule: &M) -> Result<f64, Error> {
+    Ok(formula(molecule)?.average_mass())
+}
+
+/// Returns the monoisotopic mass of molecule, using the most abundant
+/// isotope for atoms of natural composition.
+pub fn exact_mass<M: Molecule>(molecule: &M) -> Result<f64, Error> {
+    Ok(formula(molecule)?.exact_mass())
+}
+
+impl Formula {
+    /// Returns the sum of standard atomic weights, less the mass of
+    /// electrons removed by a positive charge or plus those added by a
+    /// negative one. Atoms with an isotope contribute that isotope's mass.
+    pub fn average_mass(&self) -> f64 {
+        self.mass(Element::atomic_weight)
+    }
+
+    /// Returns the sum of most abundant isotope masses, corrected for
+    /// charge as in `average_mass`. Atoms with an isotope contribute that
+    /// isotope's mass.
+    pub fn exact_mass(&self) -> f64 {
+        self.mass(Element::monoisotopic_mass)
+    }
+
+    fn mass(&self, natural: fn(&Element) -> f64) -> f64 {
+        let mut result = 0.;
+
+        for ((element, isotope), &count) in self.counts.iter() {
+            let mass = match isotope {
+                Some(isotope) => isotope_mass(element, *isotope),
+                None => natural(element),
+            };
+
+            result += mass * count as f64;
+        }
+
+        result - f64::from(self.charge) * ELECTRON_MASS
+    }
+}
+
+// Nuclides missing from Element::isotopes fall back to their mass number.
+fn isotope_mass(element: &Element, mass_number: u16) -> f64 {
+    match element.isotope(mass_number) {
+        Some(isotope) => isotope.mass,
+        None => f64::from(mass_number),
+    }
+}
+
+#[cfg(test)]
+mod average_mass {
+    use super::*;
+    use crate::daylight::read_smiles;
+
+    fn mass(smiles: &str) -> f64 {
+        average_mass(&read_smiles(smiles, None).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn water() {
+        assert!((mass("O") - 18.015).abs() < 1e-9)
+    }
+
+    #[test]
+    fn benzene() {
+        assert!((mass("c1ccccc1") - 78.114).abs() < 1e-9)
+    }
+
+    #[test]
+    fn labeled_methane() {
+        assert!((mass("[13CH4]") - (13.00335483507 + 4. * 1.008)).abs() < 1e-9)
+    }
+
+    #[test]
+    fn sodium_cation() {
+        assert!((mass("[Na+]") - (22.98976928 - ELECTRON_MASS)).abs() < 1e-9)
+    }
+}
+
+#[cfg(test)]
+mod exact_mass {
+    use super::*;
+    use crate::daylight::read_smiles;
+    use crate::molecule::{Atom, DefaultMolecule, Node};
+
+    fn mass(smiles: &str) -> f64 {
+        exact_mass(&read_smiles(smiles, None).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn water() {
+        assert!((mass("O") - 18.0105646837).abs() < 1e-9)
+    }
+
+    #[test]
+    fn chloromethane() {
+        assert!(
+            (mass("CCl") - (12. + 3. * 1.00782503223 + 34.968852682)).abs()
+                < 1e-9
+        )
+    }
+
+    #[test]
+    fn deuterium_oxide() {
+        assert!(
+            (mass("[2H]O[2H]") - (2. * 2.01410177812 + 15.99491461957)).abs()
+                < 1e-9
+        )
+    }
+
+    #[test]
+    fn unlisted_isotope() {
+        let molecule = DefaultMolecule::new(vec![Node {
+            atom: Atom {
+                element: Some(Element::C),
+                isotope: Some(15),
+                hydrogens: 4,
+                ..Default::default()
+            },
+            bonds: vec![],
+            aromatic: false,
+        }]);
+        let mass = exact_mass(&molecule).unwrap();
+
+        assert!((mass - (15. + 4. * 1.00782503223)).abs() < 1e-9)
+    }
+
+    #[test]
+    fn ammonium() {
+        let expected = 14.00307400443 + 4. * 1.00782503223 - ELECTRON_MASS;
+
+        assert!((mass("[NH4+]") - expected).abs() < 1e-9)
+    }
+
+    #[test]
+    fn chloride() {
+        assert!((mass("[Cl-]") - (34.968852682 + ELECTRON_MASS)).abs() < 1e-9)
+    }
+}
@@ -0,0 +1,10 @@
+mod error;
+#[allow(clippy::module_inception)]
+mod formula;
+mod mass;
+mod pattern;
+
+pub use error::Error;
+pub use formula::{formula, Formula};
+pub use mass::{average_mass, exact_mass, ELECTRON_MASS};
+pub use pattern::{isotope_pattern, Peak};
@@ -0,0 +1,248 @@
+use std::collections::BTreeMap;
+
+use super::{Formula, ELECTRON_MASS};
+use crate::molecule::Element;
+
+/// A peak in an isotope pattern. `intensity` is relative to the most
+/// intense peak, which has intensity 1.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Peak {
+    pub mz: f64,
+    pub intensity: f64,
+}
+
+// Abundance and abundance-weighted mass, keyed by nominal mass.
+type Distribution = BTreeMap<u32, (f64, f64)>;
+
+// Intermediate peaks below this fraction of threshold, relative to the
+// most abundant, are dropped.
+const PRUNING: f64 = 1e-3;
+
+/// Returns the isotope pattern of formula at the given charge state, in
+/// order of increasing m/z. Per-element natural abundances are convolved,
+/// and isotopologues sharing a nominal mass are merged into one peak at
+/// their abundance-weighted mass. Peaks with intensity below threshold
+/// are removed.
+///
+/// The charge of formula is ignored in favor of charge, which removes
+/// (or adds) electron masses and divides by its magnitude. A charge of
+/// zero gives neutral masses.
+pub fn isotope_pattern(
+    formula: &Formula,
+    charge: i32,
+    threshold: f64,
+) -> Vec<Peak> {
+    let cutoff = threshold * PRUNING;
+    let mut result = single(0, 0.);
+
+    for ((element, isotope), &count) in formula.counts.iter() {
+        let distribution = match isotope {
+            Some(isotope) => labeled(element, *isotope),
+            None => natural(element),
+        };
+
+        result = convolve(&result, &power(&distribution, count, cutoff), cutoff)
+    }
+
+    let max = result
+        .values()
+        .map(|(abundance, _)| *abundance)
+        .fold(0., f64::max);
+    let divisor = if charge == 0 {
+        1.
+    } else {
+        f64::from(charge.abs())
+    };
+
+    result
+        .values()
+        .filter(|(abundance, _)| *abundance / max >= threshold)
+        .map(|(abundance, weighted)| Peak {
+            mz: (weighted / abundance - f64::from(charge) * ELECTRON_MASS)
+                / divisor,
+            intensity: abundance / max,
+        })
+        .collect()
+}
+
+fn single(nominal: u32, mass: f64) -> Distribution {
+    let mut result = Distribution::new();
+
+    result.insert(nominal, (1., mass));
+
+    result
+}
+
+fn labeled(element: &Element, mass_number: u16) -> Distribution {
+    let mass = match element.isotope(mass_number) {
+        Some(isotope) => isotope.mass,
+        None => f64::from(mass_number),
+    };
+
+    single(u32::from(mass_number), mass)
+}
+
+fn natural(element: &Element) -> Distribution {
+    let mut result = Distribution::new();
+
+    for isotope in element.isotopes() {
+        if isotope.abundance > 0. {
+            result.insert(
+                u32::from(isotope.mass_number),
+                (isotope.abundance, isotope.abundance * isotope.mass),
+            );
+        }
+    }
+
+    if result.is_empty() {
+        let mass = element.monoisotopic_mass();
+
+        single(mass.round() as u32, mass)
+    } else {
+        result
+    }
+}
+
+fn convolve(a: &Distribution, b: &Distribution, cutoff: f64) -> Distribution {
+    let mut result = Distribution::new();
+
+    for (nominal_a, (abundance_a, weighted_a)) in a.iter() {
+        for (nominal_b, (abundance_b, weighted_b)) in b.iter() {
+            let entry = result.entry(nominal_a + nominal_b).or_insert((0., 0.));
+
+            // abundance_a * abundance_b * (mass_a + mass_b)
+            entry.0 += abundance_a * abundance_b;
+            entry.1 += weighted_a * abundance_b + weighted_b * abundance_a;
+        }
+    }
+
+    prune(result, cutoff)
+}
+
+// Raises distribution to power by repeated squaring.
+fn power(
+    distribution: &Distribution,
+    power: usize,
+    cutoff: f64,
+) -> Distribution {
+    let mut result = single(0, 0.);
+    let mut base = distribution.clone();
+    let mut power = power;
+
+    while power > 0 {
+        if power & 1 == 1 {
+            result = convolve(&result, &base, cutoff);
+        }
+
+        power >>= 1;
+
+        if power > 0 {
+            base = convolve(&base, &base, cutoff);
+        }
+    }
+
+    result
+}
+
+fn prune(distribution: Distribution, cutoff: f64) -> Distribution {
+    let max = distribution
+        .values()
+        .map(|(abundance, _)| *abundance)
+        .fold(0., f64::max);
+
+    distribution
+        .into_iter()
+        .filter(|(_, (abundance, _))| *abundance >= max * cutoff)
+        .collect()
+}
+
+#[cfg(test)]
+mod isotope_pattern {
+    use super::*;
+
+    fn pattern(formula: &str, charge: i32, threshold: f64) -> Vec<Peak> {
+        super::isotope_pattern(&formula.parse().unwrap(), charge, threshold)
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            super::isotope_pattern(&Formula::default(), 0, 0.),
+            vec![Peak {
+                mz: 0.,
+                intensity: 1.
+            }]
+        )
+    }
+
+    #[test]
+    fn chlorine() {
+        let peaks = pattern("Cl", 0, 0.);
+
+        assert_eq!(peaks.len(), 2);
+        assert_eq!(peaks[0].mz, 34.968852682);
+        assert_eq!(peaks[0].intensity, 1.);
+        assert!((peaks[1].mz - 36.965902602).abs() < 1e-9);
+        assert!((peaks[1].intensity - 0.2424 / 0.7576).abs() < 1e-9)
+    }
+
+    #[test]
+    fn dibromomethane() {
+        let peaks = pattern("CH2Br2", 0, 0.01);
+        let nominal = peaks
+            .iter()
+            .map(|peak| peak.mz.round() as u32)
+            .collect::<Vec<_>>();
+        let base = peaks.iter().position(|peak| peak.intensity == 1.).unwrap();
+
+        assert_eq!(nominal, vec![172, 174, 175, 176]);
+        assert_eq!(base, 1)
+    }
+
+    #[test]
+    fn benzene_m_plus_one() {
+        let peaks = pattern("C6H6", 0, 0.001);
+
+        assert!((peaks[0].mz - (72. + 6. * 1.00782503223)).abs() < 1e-9);
+        assert!((peaks[1].intensity - 0.0656).abs() < 1e-4)
+    }
+
+    #[test]
+    fn threshold() {
+        assert_eq!(pattern("C6H6", 0, 0.1).len(), 1)
+    }
+
+    #[test]
+    fn labeled() {
+        let peaks = pattern("[13C]", 0, 0.);
+
+        assert_eq!(
+            peaks,
+            vec![Peak {
+                mz: 13.00335483507,
+                intensity: 1.
+            }]
+        )
+    }
+
+    #[test]
+    fn doubly_charged() {
+        let peaks = pattern("C6H6", 2, 0.1);
+
+        assert!(
+            (peaks[0].mz
+                - (72. + 6. * 1.00782503223 - 2. * ELECTRON_MASS) / 2.)
+                .abs()
+                < 1e-9
+        )
+    }
+
+    #[test]
+    fn large() {
+        let peaks = pattern("C100H202", 0, 0.);
+        let monoisotopic = 100. * 12. + 202. * 1.00782503223;
+
+        assert!((peaks[0].mz - monoisotopic).abs() < 1e-6);
+        assert!(peaks.iter().all(|peak| peak.intensity <= 1.))
+    }
+}
@@ -1,5 +1,14 @@
+pub mod aromaticity;
+pub mod canon;
+pub mod cip;
 pub mod daylight;
+pub mod fingerprint;
+pub mod formula;
+pub mod mdl;
 pub mod molecule;
+pub mod rings;
+pub mod stereo;
+pub mod substructure;
 
 // https://github.com/rust-lang/cargo/issues/383#issuecomment-720873790
 #[cfg(doctest)]
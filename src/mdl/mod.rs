@@ -0,0 +1,10 @@
+mod molfile;
+mod sdf;
+
+pub use molfile::read as read_molfile;
+pub use molfile::write as write_molfile;
+pub use molfile::ReadError as MolfileInputError;
+pub use molfile::WriteError as MolfileOutputError;
+pub use sdf::ReadError as SdfInputError;
+pub use sdf::WriteError as SdfOutputError;
+pub use sdf::{Record as SdfRecord, SdfReader, SdfWriter};
@@ -0,0 +1,10 @@
+mod odd_sort;
+mod read;
+mod write;
+
+use odd_sort::odd_sort;
+
+pub use read::read;
+pub use read::Error as ReadError;
+pub use write::write;
+pub use write::Error as WriteError;
@@ -0,0 +1,38 @@
+/// Returns true if sorting reference in ascending order, with None last,
+/// takes an odd number of swaps.
+pub fn odd_sort(reference: &[Option<usize>]) -> bool {
+    let key = |index: &Option<usize>| index.unwrap_or(usize::MAX);
+    let mut swaps = 0;
+
+    for (i, left) in reference.iter().enumerate() {
+        for right in &reference[i + 1..] {
+            if key(left) > key(right) {
+                swaps += 1;
+            }
+        }
+    }
+
+    swaps % 2 == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn sorted() {
+        assert_eq!(odd_sort(&[Some(0), Some(1), Some(2), None]), false)
+    }
+
+    #[test]
+    fn hydrogen_first() {
+        assert_eq!(odd_sort(&[None, Some(0), Some(1), Some(2)]), true)
+    }
+
+    #[test]
+    fn two_swaps() {
+        assert_eq!(odd_sort(&[Some(2), Some(0), Some(1), Some(3)]), false)
+    }
+}
@@ -0,0 +1,20 @@
+/// An error encountered while reading a Molfile. Values are one-based
+/// line numbers.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    EndOfFile(usize),
+    Counts(usize),
+    Version(usize),
+    Atom(usize),
+    Symbol(usize),
+    MassDifference(usize),
+    Bond(usize),
+    BondType(usize),
+    Property(usize),
+    Block(usize),
+    Collection(usize),
+    Isotope(usize),
+    Valence(usize),
+    ChargedStar(usize),
+    Kekulization,
+}
@@ -0,0 +1,138 @@
+use super::AtomRecord;
+use crate::molecule::Element;
+
+/// Returns the number of implicit hydrogens on atom given the sum of its
+/// bond orders, following the MDL valence model. An explicit valence is
+/// taken as the total of bond orders and hydrogens.
+pub fn implicit_hydrogens(atom: &AtomRecord, bonding: u8) -> u8 {
+    let element = match &atom.element {
+        Some(element) => element,
+        None => return 0,
+    };
+    let target = match atom.valence {
+        Some(valence) => return valence.saturating_sub(bonding),
+        None => match default_valences(element, atom.charge)
+            .iter()
+            .find(|&&target| target >= bonding)
+        {
+            Some(target) => *target,
+            None => return 0,
+        },
+    };
+    let unpaired = match atom.radical {
+        0 => 0,
+        2 => 1,
+        _ => 2,
+    };
+
+    target.saturating_sub(bonding).saturating_sub(unpaired)
+}
+
+fn default_valences(element: &Element, charge: i8) -> &'static [u8] {
+    match (element, charge) {
+        (Element::H, 0) => &[1],
+        (Element::B, 0) => &[3],
+        (Element::B, -1) => &[4],
+        (Element::C, 0) => &[4],
+        (Element::C, 1) | (Element::C, -1) => &[3],
+        (Element::N, 0) => &[3],
+        (Element::N, 1) => &[4],
+        (Element::N, -1) => &[2],
+        (Element::O, 0) => &[2],
+        (Element::O, 1) => &[3],
+        (Element::O, -1) => &[1],
+        (Element::F, 0) => &[1],
+        (Element::Si, 0) => &[4],
+        (Element::P, 0) => &[3, 5],
+        (Element::P, 1) => &[4],
+        (Element::S, 0) => &[2, 4, 6],
+        (Element::S, 1) => &[3],
+        (Element::S, -1) => &[1],
+        (Element::Cl, 0) | (Element::Br, 0) | (Element::I, 0) => &[1],
+        (Element::As, 0) => &[3, 5],
+        (Element::Se, 0) => &[2, 4, 6],
+        _ => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn record(element: Element, charge: i8, radical: u8) -> AtomRecord {
+        AtomRecord {
+            line: 5,
+            element: Some(element),
+            isotope: None,
+            mass_difference: 0,
+            charge,
+            radical,
+            parity: 0,
+            valence: None,
+        }
+    }
+
+    #[test]
+    fn star() {
+        let mut atom = record(Element::C, 0, 0);
+
+        atom.element = None;
+
+        assert_eq!(implicit_hydrogens(&atom, 1), 0)
+    }
+
+    #[test]
+    fn methane() {
+        assert_eq!(implicit_hydrogens(&record(Element::C, 0, 0), 0), 4)
+    }
+
+    #[test]
+    fn ammonium() {
+        assert_eq!(implicit_hydrogens(&record(Element::N, 1, 0), 0), 4)
+    }
+
+    #[test]
+    fn hydroxide() {
+        assert_eq!(implicit_hydrogens(&record(Element::O, -1, 0), 0), 1)
+    }
+
+    #[test]
+    fn methyl_radical() {
+        assert_eq!(implicit_hydrogens(&record(Element::C, 0, 2), 0), 3)
+    }
+
+    #[test]
+    fn carbene() {
+        assert_eq!(implicit_hydrogens(&record(Element::C, 0, 3), 0), 2)
+    }
+
+    #[test]
+    fn sulfoxide() {
+        assert_eq!(implicit_hydrogens(&record(Element::S, 0, 0), 3), 1)
+    }
+
+    #[test]
+    fn explicit_valence() {
+        let mut atom = record(Element::C, 0, 0);
+
+        atom.valence = Some(0);
+
+        assert_eq!(implicit_hydrogens(&atom, 0), 0)
+    }
+
+    #[test]
+    fn explicit_valence_radical() {
+        let mut atom = record(Element::C, 0, 2);
+
+        atom.valence = Some(3);
+
+        assert_eq!(implicit_hydrogens(&atom, 1), 2)
+    }
+
+    #[test]
+    fn iron() {
+        assert_eq!(implicit_hydrogens(&record(Element::Fe, 0, 0), 2), 0)
+    }
+}
@@ -0,0 +1,59 @@
+use gamma::graph::{DefaultGraph, Graph};
+use gamma::matching::{greedy, maximum_matching};
+
+use super::{implicit_hydrogens, AtomRecord, Error};
+use crate::molecule::Bond;
+
+/// Raises to double a subset of the aromatic bonds, given as pairs of
+/// indexes into bonds, such that each atom able to take one more bond
+/// takes exactly one.
+pub fn kekulize(
+    atoms: &[AtomRecord],
+    bonds: &mut [Vec<Bond>],
+    aromatic: &[(usize, usize)],
+) -> Result<(), Error> {
+    let mut pi = DefaultGraph::new();
+
+    for &(sid, tid) in aromatic {
+        for &id in [sid, tid].iter() {
+            if pi.has_id(id) {
+                continue;
+            }
+
+            let bonding = bonds[id]
+                .iter()
+                .fold(0, |sum, bond| sum + bond.electrons / 2);
+
+            if implicit_hydrogens(&atoms[id], bonding) > 0 {
+                pi.add_node(id).expect("add node")
+            }
+        }
+    }
+
+    for &(sid, tid) in aromatic {
+        if pi.has_id(sid) && pi.has_id(tid) {
+            pi.add_edge(sid, tid).expect("add edge")
+        }
+    }
+
+    let mut pairing = greedy(&pi);
+
+    maximum_matching(&pi, &mut pairing);
+
+    if pairing.order() != pi.order() {
+        return Err(Error::Kekulization);
+    }
+
+    for (sid, tid) in pairing.edges() {
+        for &(source, target) in [(sid, tid), (tid, sid)].iter() {
+            let bond = bonds[source]
+                .iter_mut()
+                .find(|bond| bond.tid == target)
+                .expect("target bond");
+
+            bond.electrons = 4;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,16 @@
+mod error;
+mod implicit_hydrogens;
+mod kekulize;
+#[allow(clippy::module_inception)]
+mod read;
+mod read_v3000;
+mod records;
+mod to_nodes;
+
+pub use error::Error;
+pub use implicit_hydrogens::implicit_hydrogens;
+pub use kekulize::kekulize;
+pub use read::{line, read};
+pub use read_v3000::read_v3000;
+pub use records::{read_atom, read_bond, read_counts, AtomRecord, BondRecord};
+pub use to_nodes::to_nodes;
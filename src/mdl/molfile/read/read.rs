@@ -0,0 +1,532 @@
+use super::{
+    read_atom, read_bond, read_counts, read_v3000, to_nodes, AtomRecord,
+    BondRecord, Error,
+};
+use crate::molecule::DefaultMolecule;
+use crate::stereo::StereoGroup;
+
+/// Reads a V2000 or V3000 Molfile. V2000 charges, isotopes, and radicals
+/// are taken from the atom block unless overridden by the corresponding
+/// properties. Enhanced stereo groups, found only in V3000, are appended
+/// to groups if provided.
+pub fn read(
+    molfile: &str,
+    groups: Option<&mut Vec<StereoGroup>>,
+) -> Result<DefaultMolecule, Error> {
+    let lines = molfile.lines().collect::<Vec<_>>();
+
+    line(&lines, 0)?;
+    line(&lines, 1)?;
+    line(&lines, 2)?;
+
+    let (atoms, bonds, stereo_groups) =
+        match line(&lines, 3)?.get(33..).map(str::trim) {
+            None | Some("") | Some("V2000") => {
+                let (atoms, bonds) = read_v2000(&lines)?;
+
+                (atoms, bonds, Vec::new())
+            }
+            Some("V3000") => read_v3000(&lines)?,
+            Some(_) => return Err(Error::Version(4)),
+        };
+    let nodes = to_nodes(&atoms, &bonds)?;
+
+    if let Some(groups) = groups {
+        groups.extend(stereo_groups);
+    }
+
+    Ok(DefaultMolecule::new(nodes))
+}
+
+/// Returns the line at index, or EndOfFile if there is none.
+pub fn line<'a>(lines: &[&'a str], index: usize) -> Result<&'a str, Error> {
+    match lines.get(index) {
+        Some(line) => Ok(line),
+        None => Err(Error::EndOfFile(index + 1)),
+    }
+}
+
+fn read_v2000(
+    lines: &[&str],
+) -> Result<(Vec<AtomRecord>, Vec<BondRecord>), Error> {
+    let line = |index: usize| line(lines, index);
+    let counts = line(3)?;
+    let (atom_count, bond_count) = read_counts(counts, 4)?;
+    let mut atoms = Vec::with_capacity(atom_count);
+    let mut bonds = Vec::with_capacity(bond_count);
+    let mut index = 4;
+
+    for _ in 0..atom_count {
+        atoms.push(read_atom(line(index)?, index + 1)?);
+        index += 1;
+    }
+
+    for _ in 0..bond_count {
+        bonds.push(read_bond(line(index)?, index + 1, atom_count)?);
+        index += 1;
+    }
+
+    let mut charges_reset = false;
+    let mut radicals_reset = false;
+
+    loop {
+        let text = line(index)?;
+        let number = index + 1;
+
+        index += 1;
+
+        if text.starts_with("M  END") {
+            break;
+        } else if text.starts_with("M  CHG") {
+            if !charges_reset {
+                atoms.iter_mut().for_each(|atom| atom.charge = 0);
+                charges_reset = true;
+            }
+
+            for (atom, value) in read_pairs(text, number, &mut atoms)? {
+                if !(-15..=15).contains(&value) {
+                    return Err(Error::Property(number));
+                }
+
+                atom.charge = value as i8;
+            }
+        } else if text.starts_with("M  RAD") {
+            if !radicals_reset {
+                atoms.iter_mut().for_each(|atom| atom.radical = 0);
+                radicals_reset = true;
+            }
+
+            for (atom, value) in read_pairs(text, number, &mut atoms)? {
+                if !(0..=3).contains(&value) {
+                    return Err(Error::Property(number));
+                }
+
+                atom.radical = value as u8;
+            }
+        } else if text.starts_with("M  ISO") {
+            for (atom, value) in read_pairs(text, number, &mut atoms)? {
+                if value < 1 {
+                    return Err(Error::Property(number));
+                }
+
+                atom.isotope = Some(value as u16);
+                atom.mass_difference = 0;
+            }
+        } else if text.starts_with("A  ") || text.starts_with("G  ") {
+            index += 1;
+        } else if text.starts_with("S  SKP") {
+            match text.get(6..).map(str::trim).map(str::parse::<usize>) {
+                Some(Ok(count)) => index += count,
+                _ => return Err(Error::Property(number)),
+            }
+        }
+    }
+
+    Ok((atoms, bonds))
+}
+
+// Reads the (atom, value) pairs of a property line, returning the records
+// they refer to.
+fn read_pairs<'a>(
+    text: &str,
+    number: usize,
+    atoms: &'a mut [AtomRecord],
+) -> Result<Vec<(&'a mut AtomRecord, i16)>, Error> {
+    let tokens = text
+        .get(6..)
+        .unwrap_or("")
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<Vec<_>, _>>()
+        .or(Err(Error::Property(number)))?;
+    let (count, pairs) = match tokens.split_first() {
+        Some((count, pairs)) => (*count, pairs),
+        None => return Err(Error::Property(number)),
+    };
+
+    if !(1..=8).contains(&count) || pairs.len() != count as usize * 2 {
+        return Err(Error::Property(number));
+    }
+
+    let mut values = vec![None; atoms.len()];
+
+    for pair in pairs.chunks(2) {
+        let id = pair[0] as usize;
+
+        if pair[0] < 1 || id > atoms.len() || values[id - 1].is_some() {
+            return Err(Error::Property(number));
+        }
+
+        values[id - 1] = Some(pair[1]);
+    }
+
+    Ok(atoms
+        .iter_mut()
+        .zip(values)
+        .filter_map(|(atom, value)| value.map(|value| (atom, value)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::molecule::{Atom, Bond, Element, Molecule, Node, Parity};
+
+    fn molfile(atoms: &[&str], bonds: &[&str], properties: &[&str]) -> String {
+        let mut result = vec![
+            String::from(""),
+            String::from("  chemcore"),
+            String::from(""),
+            format!(
+                "{:>3}{:>3}  0  0  0  0  0  0  0  0999 V2000",
+                atoms.len(),
+                bonds.len()
+            ),
+        ];
+
+        for atom in atoms {
+            result.push(format!(
+                "    0.0000    0.0000    0.0000 {}  0  0  0  0  0  0  0  0",
+                atom
+            ));
+        }
+
+        for bond in bonds {
+            result.push(format!("{}  0  0  0  0", bond));
+        }
+
+        for property in properties {
+            result.push(property.to_string());
+        }
+
+        result.push(String::from("M  END"));
+        result.join("\n")
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(read("", None), Err(Error::EndOfFile(1)))
+    }
+
+    #[test]
+    fn missing_counts() {
+        assert_eq!(read("\n\n\n", None), Err(Error::EndOfFile(4)))
+    }
+
+    #[test]
+    fn unknown_version() {
+        assert_eq!(
+            read(
+                "\n\n\n  0  0  0  0  0  0  0  0  0  0999 V4000\nM  END",
+                None
+            ),
+            Err(Error::Version(4))
+        )
+    }
+
+    #[test]
+    fn missing_atom() {
+        let text = molfile(&["C   0  0"], &[], &[]);
+        let text = text.replace("  1  0  0", "  2  0  0");
+
+        assert_eq!(read(&text, None), Err(Error::Atom(6)))
+    }
+
+    #[test]
+    fn missing_end() {
+        let text = molfile(&["C   0  0"], &[], &[]);
+
+        assert_eq!(
+            read(text.trim_end_matches("M  END"), None),
+            Err(Error::EndOfFile(6))
+        )
+    }
+
+    #[test]
+    fn bad_property_count() {
+        let text = molfile(&["C   0  0"], &[], &["M  CHG  2   1   1"]);
+
+        assert_eq!(read(&text, None), Err(Error::Property(6)))
+    }
+
+    #[test]
+    fn property_unknown_atom() {
+        let text = molfile(&["C   0  0"], &[], &["M  CHG  1   2   1"]);
+
+        assert_eq!(read(&text, None), Err(Error::Property(6)))
+    }
+
+    #[test]
+    fn duplicate_bond() {
+        let text = molfile(
+            &["C   0  0", "C   0  0"],
+            &["  1  2  1", "  2  1  1"],
+            &[],
+        );
+
+        assert_eq!(read(&text, None), Err(Error::Bond(8)))
+    }
+
+    #[test]
+    fn hypervalent_carbon() {
+        let text = molfile(
+            &["C   0  0", "O   0  0", "O   0  0", "O   0  0"],
+            &["  1  2  2", "  1  3  2", "  1  4  1"],
+            &[],
+        );
+
+        assert_eq!(read(&text, None), Err(Error::Valence(5)))
+    }
+
+    #[test]
+    fn charged_star() {
+        let text = molfile(&["*   0  0"], &[], &["M  CHG  1   1   1"]);
+
+        assert_eq!(read(&text, None), Err(Error::ChargedStar(5)))
+    }
+
+    #[test]
+    fn mass_difference() {
+        let text = molfile(&["C   1  0"], &[], &[]);
+
+        assert_eq!(
+            read(&text, None).unwrap().atom(0).unwrap().isotope,
+            Some(13)
+        )
+    }
+
+    #[test]
+    fn negative_mass_difference() {
+        let text = molfile(&["O  -1  0"], &[], &[]);
+
+        assert_eq!(
+            read(&text, None).unwrap().atom(0).unwrap().isotope,
+            Some(15)
+        )
+    }
+
+    #[test]
+    fn mass_difference_overridden() {
+        let text = molfile(&["C   1  0"], &[], &["M  ISO  1   1  14"]);
+
+        assert_eq!(
+            read(&text, None).unwrap().atom(0).unwrap().isotope,
+            Some(14)
+        )
+    }
+
+    #[test]
+    fn unknown_mass_difference() {
+        let text = molfile(&["Fe -1  0"], &[], &[]);
+
+        assert_eq!(read(&text, None), Err(Error::MassDifference(5)))
+    }
+
+    #[test]
+    fn invalid_mass_difference() {
+        let text = molfile(&["H  -1  0"], &[], &[]);
+
+        assert_eq!(read(&text, None), Err(Error::MassDifference(5)))
+    }
+
+    #[test]
+    fn unkekulizable() {
+        let text = molfile(
+            &["C   0  0", "C   0  0", "C   0  0"],
+            &["  1  2  4", "  2  3  4", "  3  1  4"],
+            &[],
+        );
+
+        assert_eq!(read(&text, None), Err(Error::Kekulization))
+    }
+
+    #[test]
+    fn skipped_lines() {
+        let text = molfile(
+            &["C   0  0"],
+            &[],
+            &["A    1", "M  CHG  1   1   1", "S  SKP  1", "M  CHG x"],
+        );
+
+        assert_eq!(read(&text, None).unwrap().charge(0), Ok(0.))
+    }
+
+    #[test]
+    fn ethanol() {
+        let text = molfile(
+            &["C   0  0", "C   0  0", "O   0  0"],
+            &["  1  2  1", "  2  3  1"],
+            &[],
+        );
+
+        assert_eq!(
+            read(&text, None),
+            Ok(DefaultMolecule::new(vec![
+                Node {
+                    atom: Atom {
+                        element: Some(Element::C),
+                        electrons: 0,
+                        hydrogens: 3,
+                        isotope: None,
+                        parity: None,
+                        configuration: None
+                    },
+                    bonds: vec![Bond::new(2, None, 1)],
+                    aromatic: false
+                },
+                Node {
+                    atom: Atom {
+                        element: Some(Element::C),
+                        electrons: 0,
+                        hydrogens: 2,
+                        isotope: None,
+                        parity: None,
+                        configuration: None
+                    },
+                    bonds: vec![Bond::new(2, None, 0), Bond::new(2, None, 2)],
+                    aromatic: false
+                },
+                Node {
+                    atom: Atom {
+                        element: Some(Element::O),
+                        electrons: 4,
+                        hydrogens: 1,
+                        isotope: None,
+                        parity: None,
+                        configuration: None
+                    },
+                    bonds: vec![Bond::new(2, None, 1)],
+                    aromatic: false
+                },
+            ]))
+        )
+    }
+
+    #[test]
+    fn atom_parity() {
+        let text = molfile(
+            &["N   0  0", "C   0  0  1", "C   0  0", "C   0  0"],
+            &["  1  2  1", "  2  3  1", "  2  4  1"],
+            &[],
+        );
+        let molecule = read(&text, None).unwrap();
+
+        assert_eq!(molecule.atom(1).unwrap().parity, Some(Parity::Negative))
+    }
+
+    #[test]
+    fn atom_parity_ignored() {
+        let text = molfile(&["C   0  0  1", "C   0  0"], &["  1  2  1"], &[]);
+        let molecule = read(&text, None).unwrap();
+
+        assert_eq!(molecule.atom(0).unwrap().parity, None)
+    }
+
+    #[test]
+    fn atom_block_charge() {
+        let text = molfile(&["N   0  3"], &[], &[]);
+        let molecule = read(&text, None).unwrap();
+
+        assert_eq!(molecule.charge(0), Ok(1.));
+        assert_eq!(molecule.atom(0).unwrap().hydrogens, 4)
+    }
+
+    #[test]
+    fn charge_property_overrides_atom_block() {
+        let text = molfile(
+            &["N   0  3", "O   0  0"],
+            &["  1  2  1"],
+            &["M  CHG  1   2  -1"],
+        );
+        let molecule = read(&text, None).unwrap();
+
+        assert_eq!(molecule.charge(0), Ok(0.));
+        assert_eq!(molecule.charge(1), Ok(-1.));
+        assert_eq!(molecule.atom(0).unwrap().hydrogens, 2);
+        assert_eq!(molecule.atom(1).unwrap().hydrogens, 0)
+    }
+
+    #[test]
+    fn isotope_property() {
+        let text = molfile(&["C   0  0"], &[], &["M  ISO  1   1  13"]);
+
+        assert_eq!(
+            read(&text, None).unwrap().atom(0).unwrap().isotope,
+            Some(13)
+        )
+    }
+
+    #[test]
+    fn uranium_isotope() {
+        let text = molfile(&["U   0  0"], &[], &["M  ISO  1   1 235"]);
+
+        assert_eq!(
+            read(&text, None).unwrap().atom(0).unwrap().isotope,
+            Some(235)
+        )
+    }
+
+    #[test]
+    fn radical_property() {
+        let text = molfile(&["C   0  0"], &[], &["M  RAD  1   1   2"]);
+        let atom = read(&text, None).unwrap().atom(0).unwrap().clone();
+
+        assert_eq!(atom.hydrogens, 3);
+        assert_eq!(atom.electrons, 1)
+    }
+
+    #[test]
+    fn benzene() {
+        let text = molfile(
+            &["C   0  0"; 6],
+            &[
+                "  1  2  4",
+                "  2  3  4",
+                "  3  4  4",
+                "  4  5  4",
+                "  5  6  4",
+                "  6  1  4",
+            ],
+            &[],
+        );
+        let molecule = read(&text, None).unwrap();
+        let orders = vec![(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0)]
+            .into_iter()
+            .map(|(sid, tid)| molecule.bond_order(sid, tid).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(orders.iter().sum::<f32>(), 9.);
+        assert_eq!(
+            (0..6)
+                .map(|id| molecule.atom(id).unwrap().hydrogens)
+                .collect::<Vec<_>>(),
+            vec![1; 6]
+        )
+    }
+
+    #[test]
+    fn pyrrole() {
+        let text = molfile(
+            &[
+                "N   0  0", "C   0  0", "C   0  0", "C   0  0", "C   0  0",
+                "H   0  0",
+            ],
+            &[
+                "  1  2  4",
+                "  2  3  4",
+                "  3  4  4",
+                "  4  5  4",
+                "  5  1  4",
+                "  1  6  1",
+            ],
+            &[],
+        );
+        let molecule = read(&text, None).unwrap();
+
+        assert_eq!(molecule.bond_order(1, 2), Ok(2.));
+        assert_eq!(molecule.bond_order(3, 4), Ok(2.));
+        assert_eq!(molecule.atom(0).unwrap().hydrogens, 0)
+    }
+}
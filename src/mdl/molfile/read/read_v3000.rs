@@ -0,0 +1,566 @@
+use std::collections::HashMap;
+
+use super::{line, AtomRecord, BondRecord, Error};
+use crate::molecule::Element;
+use crate::stereo::StereoGroup;
+
+/// The atoms, bonds, and enhanced stereo groups of a connection table.
+pub type Ctab = (Vec<AtomRecord>, Vec<BondRecord>, Vec<StereoGroup>);
+
+/// Reads the atoms, bonds, and enhanced stereo groups of a V3000 Molfile.
+/// Blocks other than atoms, bonds, and collections are skipped.
+pub fn read_v3000(lines: &[&str]) -> Result<Ctab, Error> {
+    let records = join_records(lines)?;
+    let mut records = records.iter();
+    let mut atoms = Vec::new();
+    let mut bonds = Vec::new();
+    let mut groups = Vec::new();
+    let mut indexes = HashMap::new();
+
+    match records.next() {
+        Some((_, text)) if text == "BEGIN CTAB" => (),
+        Some((number, _)) => return Err(Error::Block(*number)),
+        None => return Err(Error::Block(lines.len())),
+    }
+
+    let (counts_line, atom_count, bond_count) = match records.next() {
+        Some((number, text)) => {
+            let (atom_count, bond_count) = read_counts(text, *number)?;
+
+            (*number, atom_count, bond_count)
+        }
+        None => return Err(Error::Block(lines.len())),
+    };
+
+    loop {
+        let (number, text) = match records.next() {
+            Some(record) => record,
+            None => return Err(Error::Block(lines.len())),
+        };
+        let block = match text.strip_prefix("BEGIN ") {
+            Some(block) => block,
+            None if text == "END CTAB" => break,
+            None => continue,
+        };
+        let end = format!("END {}", block);
+
+        loop {
+            let (number, text) = match records.next() {
+                Some(record) => record,
+                None => return Err(Error::Block(*number)),
+            };
+
+            if text == &end {
+                break;
+            }
+
+            match block {
+                "ATOM" => {
+                    let atom = read_atom(text, *number)?;
+
+                    if indexes.insert(atom.0, atoms.len()).is_some() {
+                        return Err(Error::Atom(*number));
+                    }
+
+                    atoms.push(atom.1);
+                }
+                "BOND" => bonds.push(read_bond(text, *number, &indexes)?),
+                "COLLECTION" => {
+                    if let Some(group) = read_group(text, *number, &indexes)? {
+                        groups.push(group)
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    if atoms.len() != atom_count || bonds.len() != bond_count {
+        return Err(Error::Counts(counts_line));
+    }
+
+    Ok((atoms, bonds, groups))
+}
+
+// Returns the text of each M  V30 record through M  END, with continued
+// lines joined, along with the number of its first line.
+fn join_records(lines: &[&str]) -> Result<Vec<(usize, String)>, Error> {
+    let mut result: Vec<(usize, String)> = Vec::new();
+    let mut continued = false;
+    let mut index = 4;
+
+    loop {
+        let text = line(lines, index)?;
+
+        index += 1;
+
+        if text.starts_with("M  END") {
+            break;
+        }
+
+        let text = match text.strip_prefix("M  V30 ") {
+            Some(text) => text,
+            None => continue,
+        };
+        let (text, continues) = match text.strip_suffix('-') {
+            Some(text) => (text, true),
+            None => (text, false),
+        };
+
+        if continued {
+            result.last_mut().expect("last record").1.push_str(text);
+        } else {
+            result.push((index, text.to_string()));
+        }
+
+        continued = continues;
+    }
+
+    Ok(result
+        .into_iter()
+        .map(|(number, text)| (number, text.trim().to_string()))
+        .collect())
+}
+
+fn read_counts(text: &str, number: usize) -> Result<(usize, usize), Error> {
+    let tokens = text.split_whitespace().collect::<Vec<_>>();
+
+    match tokens.as_slice() {
+        ["COUNTS", atoms, bonds, ..] => match (atoms.parse(), bonds.parse()) {
+            (Ok(atoms), Ok(bonds)) => Ok((atoms, bonds)),
+            _ => Err(Error::Counts(number)),
+        },
+        _ => Err(Error::Counts(number)),
+    }
+}
+
+fn read_atom(text: &str, number: usize) -> Result<(usize, AtomRecord), Error> {
+    let tokens = tokenize(text);
+
+    if tokens.len() < 6 {
+        return Err(Error::Atom(number));
+    }
+
+    let index = tokens[0].parse().or(Err(Error::Atom(number)))?;
+    let (element, isotope) = match tokens[1] {
+        "*" | "A" | "Q" | "R" | "R#" => (None, None),
+        "D" => (Some(Element::H), Some(2)),
+        "T" => (Some(Element::H), Some(3)),
+        symbol => match symbol.parse::<Element>() {
+            Ok(element) => (Some(element), None),
+            Err(_) => return Err(Error::Symbol(number)),
+        },
+    };
+    let mut atom = AtomRecord {
+        line: number,
+        element,
+        isotope,
+        mass_difference: 0,
+        charge: 0,
+        radical: 0,
+        parity: 0,
+        valence: None,
+    };
+
+    for (key, value) in properties(&tokens[6..]) {
+        let value = match value.parse::<i16>() {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+
+        match (key, value) {
+            ("CHG", -15..=15) => atom.charge = value as i8,
+            ("RAD", 0..=3) => atom.radical = value as u8,
+            ("MASS", 1..=999) => atom.isotope = Some(value as u16),
+            ("VAL", -1) => atom.valence = Some(0),
+            ("VAL", 0) => atom.valence = None,
+            ("VAL", 1..=14) => atom.valence = Some(value as u8),
+            ("CFG", 0..=3) => atom.parity = value as u8,
+            ("CHG", _) | ("RAD", _) | ("MASS", _) | ("VAL", _) | ("CFG", _) => {
+                return Err(Error::Atom(number))
+            }
+            _ => (),
+        }
+    }
+
+    Ok((index, atom))
+}
+
+fn read_bond(
+    text: &str,
+    number: usize,
+    indexes: &HashMap<usize, usize>,
+) -> Result<BondRecord, Error> {
+    let tokens = tokenize(text);
+
+    if tokens.len() < 4 {
+        return Err(Error::Bond(number));
+    }
+
+    let kind = match tokens[1].parse() {
+        Ok(kind @ 1..=4) => kind,
+        _ => return Err(Error::BondType(number)),
+    };
+    let atom = |token: &str| match token.parse::<usize>() {
+        Ok(index) => indexes.get(&index).cloned().ok_or(Error::Bond(number)),
+        Err(_) => Err(Error::Bond(number)),
+    };
+
+    Ok(BondRecord {
+        line: number,
+        sid: atom(tokens[2])?,
+        tid: atom(tokens[3])?,
+        kind,
+    })
+}
+
+// Reads an enhanced stereo collection, or returns None for other kinds
+// of collection.
+fn read_group(
+    text: &str,
+    number: usize,
+    indexes: &HashMap<usize, usize>,
+) -> Result<Option<StereoGroup>, Error> {
+    let tokens = tokenize(text);
+    let name = match tokens.first() {
+        Some(name) => name,
+        None => return Err(Error::Collection(number)),
+    };
+    let (kind, label) = if *name == "MDLV30/STEABS" {
+        ("ABS", "")
+    } else if let Some(label) = name.strip_prefix("MDLV30/STEREL") {
+        ("REL", label)
+    } else if let Some(label) = name.strip_prefix("MDLV30/STERAC") {
+        ("RAC", label)
+    } else {
+        return Ok(None);
+    };
+    let atoms = match properties(&tokens[1..])
+        .into_iter()
+        .find(|(key, _)| *key == "ATOMS")
+    {
+        Some((_, atoms)) => read_list(atoms, number, indexes)?,
+        None => return Err(Error::Collection(number)),
+    };
+
+    match (kind, label.parse::<usize>()) {
+        ("ABS", _) => Ok(Some(StereoGroup::Absolute(atoms))),
+        ("REL", Ok(label)) => Ok(Some(StereoGroup::Or(label, atoms))),
+        ("RAC", Ok(label)) => Ok(Some(StereoGroup::And(label, atoms))),
+        _ => Err(Error::Collection(number)),
+    }
+}
+
+// Reads a parenthesized, counted list of atom indexes.
+fn read_list(
+    text: &str,
+    number: usize,
+    indexes: &HashMap<usize, usize>,
+) -> Result<Vec<usize>, Error> {
+    let inner = text
+        .strip_prefix('(')
+        .and_then(|text| text.strip_suffix(')'))
+        .ok_or(Error::Collection(number))?;
+    let values = inner
+        .split_whitespace()
+        .map(str::parse::<usize>)
+        .collect::<Result<Vec<_>, _>>()
+        .or(Err(Error::Collection(number)))?;
+    let (count, values) = match values.split_first() {
+        Some((count, values)) if *count == values.len() => (count, values),
+        _ => return Err(Error::Collection(number)),
+    };
+    let mut result = Vec::with_capacity(*count);
+
+    for value in values {
+        match indexes.get(value) {
+            Some(&id) => result.push(id),
+            None => return Err(Error::Collection(number)),
+        }
+    }
+
+    Ok(result)
+}
+
+// Splits text on whitespace outside of parentheses and double quotes.
+fn tokenize(text: &str) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut depth = 0;
+    let mut quoted = false;
+    let mut start = None;
+
+    for (index, character) in text.char_indices() {
+        match character {
+            '"' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted && depth > 0 => depth -= 1,
+            _ => (),
+        }
+
+        if character.is_whitespace() && depth == 0 && !quoted {
+            if let Some(start) = start.take() {
+                result.push(&text[start..index]);
+            }
+        } else if start.is_none() {
+            start = Some(index);
+        }
+    }
+
+    if let Some(start) = start {
+        result.push(&text[start..]);
+    }
+
+    result
+}
+
+fn properties<'a>(tokens: &[&'a str]) -> Vec<(&'a str, &'a str)> {
+    tokens
+        .iter()
+        .filter_map(|token| {
+            let mut parts = token.splitn(2, '=');
+
+            match (parts.next(), parts.next()) {
+                (Some(key), Some(value)) => Some((key, value)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::mdl::molfile::read::{read, Error};
+    use crate::molecule::{Molecule, Parity};
+    use crate::stereo::StereoGroup;
+
+    fn molfile(atoms: &[&str], bonds: &[&str], collection: &[&str]) -> String {
+        let mut result = vec![
+            String::from(""),
+            String::from("  chemcore"),
+            String::from(""),
+            String::from("  0  0  0     0  0            999 V3000"),
+            String::from("M  V30 BEGIN CTAB"),
+            format!("M  V30 COUNTS {} {} 0 0 0", atoms.len(), bonds.len()),
+            String::from("M  V30 BEGIN ATOM"),
+        ];
+
+        for atom in atoms {
+            result.push(format!("M  V30 {}", atom));
+        }
+
+        result.push(String::from("M  V30 END ATOM"));
+
+        if !bonds.is_empty() {
+            result.push(String::from("M  V30 BEGIN BOND"));
+
+            for bond in bonds {
+                result.push(format!("M  V30 {}", bond));
+            }
+
+            result.push(String::from("M  V30 END BOND"));
+        }
+
+        if !collection.is_empty() {
+            result.push(String::from("M  V30 BEGIN COLLECTION"));
+
+            for line in collection {
+                result.push(format!("M  V30 {}", line));
+            }
+
+            result.push(String::from("M  V30 END COLLECTION"));
+        }
+
+        result.push(String::from("M  V30 END CTAB"));
+        result.push(String::from("M  END"));
+        result.join("\n")
+    }
+
+    #[test]
+    fn missing_ctab() {
+        let text = molfile(&[], &[], &[]).replace("BEGIN CTAB", "BEGIN X");
+
+        assert_eq!(read(&text, None), Err(Error::Block(5)))
+    }
+
+    #[test]
+    fn unterminated_atom_block() {
+        let text = molfile(&["1 C 0 0 0 0"], &[], &[])
+            .replace("M  V30 END ATOM\n", "");
+
+        assert_eq!(read(&text, None), Err(Error::Atom(9)))
+    }
+
+    #[test]
+    fn missing_end() {
+        let text = molfile(&["1 C 0 0 0 0"], &[], &[]);
+
+        assert_eq!(
+            read(text.trim_end_matches("M  END"), None),
+            Err(Error::EndOfFile(11))
+        )
+    }
+
+    #[test]
+    fn count_mismatch() {
+        let text =
+            molfile(&["1 C 0 0 0 0"], &[], &[]).replace("COUNTS 1", "COUNTS 2");
+
+        assert_eq!(read(&text, None), Err(Error::Counts(6)))
+    }
+
+    #[test]
+    fn atom_list() {
+        let text = molfile(&["1 [C,N] 0 0 0 0"], &[], &[]);
+
+        assert_eq!(read(&text, None), Err(Error::Symbol(8)))
+    }
+
+    #[test]
+    fn bad_charge() {
+        let text = molfile(&["1 C 0 0 0 0 CHG=20"], &[], &[]);
+
+        assert_eq!(read(&text, None), Err(Error::Atom(8)))
+    }
+
+    #[test]
+    fn duplicate_atom_index() {
+        let text = molfile(&["1 C 0 0 0 0", "1 C 0 0 0 0"], &[], &[]);
+
+        assert_eq!(read(&text, None), Err(Error::Atom(9)))
+    }
+
+    #[test]
+    fn unknown_bond_atom() {
+        let text = molfile(&["1 C 0 0 0 0"], &["1 1 1 2"], &[]);
+
+        assert_eq!(read(&text, None), Err(Error::Bond(11)))
+    }
+
+    #[test]
+    fn query_bond() {
+        let text = molfile(&["1 C 0 0 0 0", "2 C 0 0 0 0"], &["1 8 1 2"], &[]);
+
+        assert_eq!(read(&text, None), Err(Error::BondType(12)))
+    }
+
+    #[test]
+    fn bad_collection_count() {
+        let text =
+            molfile(&["1 C 0 0 0 0"], &[], &["MDLV30/STEABS ATOMS=(2 1)"]);
+
+        assert_eq!(read(&text, None), Err(Error::Collection(11)))
+    }
+
+    #[test]
+    fn properties() {
+        let text = molfile(
+            &[
+                "1 C 0 0 0 0 MASS=13",
+                "2 N 0 0 0 0 CHG=1",
+                "3 C 0 0 0 0 RAD=2",
+                "4 C 0 0 0 0 VAL=-1",
+            ],
+            &["1 1 1 2"],
+            &[],
+        );
+        let molecule = read(&text, None).unwrap();
+
+        assert_eq!(molecule.atom(0).unwrap().isotope, Some(13));
+        assert_eq!(molecule.charge(1), Ok(1.));
+        assert_eq!(molecule.atom(1).unwrap().hydrogens, 3);
+        assert_eq!(molecule.atom(2).unwrap().hydrogens, 3);
+        assert_eq!(molecule.atom(2).unwrap().electrons, 1);
+        assert_eq!(molecule.atom(3).unwrap().hydrogens, 0)
+    }
+
+    #[test]
+    fn continued_lines() {
+        let text = molfile(&["1 N 0 0 0 0 -\nM  V30 CHG=1"], &[], &[]);
+
+        assert_eq!(read(&text, None).unwrap().charge(0), Ok(1.))
+    }
+
+    #[test]
+    fn arbitrary_indexes() {
+        let text =
+            molfile(&["10 C 0 0 0 0", "20 O 0 0 0 0"], &["1 2 10 20"], &[]);
+        let molecule = read(&text, None).unwrap();
+
+        assert_eq!(molecule.bond_order(0, 1), Ok(2.))
+    }
+
+    #[test]
+    fn skipped_blocks() {
+        let text = molfile(&["1 C 0 0 0 0"], &[], &[]).replace(
+            "M  V30 END CTAB",
+            "M  V30 BEGIN SGROUP\nM  V30 1 SUP 0\nM  V30 END SGROUP\nM  V30 END CTAB",
+        );
+
+        assert_eq!(read(&text, None).unwrap().atom(0).unwrap().hydrogens, 4)
+    }
+
+    #[test]
+    fn aromatic() {
+        let text = molfile(
+            &[
+                "1 C 0 0 0 0",
+                "2 C 0 0 0 0",
+                "3 C 0 0 0 0",
+                "4 C 0 0 0 0",
+                "5 C 0 0 0 0",
+                "6 C 0 0 0 0",
+            ],
+            &[
+                "1 4 1 2", "2 4 2 3", "3 4 3 4", "4 4 4 5", "5 4 5 6",
+                "6 4 6 1",
+            ],
+            &[],
+        );
+        let molecule = read(&text, None).unwrap();
+        let order = molecule.bond_order(0, 1).unwrap()
+            + molecule.bond_order(0, 5).unwrap();
+
+        assert_eq!(order, 3.)
+    }
+
+    #[test]
+    fn stereo_groups() {
+        let text = molfile(
+            &[
+                "1 C 0 0 0 0",
+                "2 C 0 0 0 0 CFG=1",
+                "3 F 0 0 0 0",
+                "4 C 0 0 0 0 CFG=2",
+                "5 Cl 0 0 0 0",
+                "6 C 0 0 0 0 CFG=1",
+                "7 Br 0 0 0 0",
+                "8 C 0 0 0 0",
+            ],
+            &[
+                "1 1 1 2", "2 1 2 3", "3 1 2 4", "4 1 4 5", "5 1 4 6",
+                "6 1 6 7", "7 1 6 8",
+            ],
+            &[
+                "MDLV30/STEABS ATOMS=(1 2)",
+                "MDLV30/STEREL1 ATOMS=(1 4)",
+                "MDLV30/STERAC2 ATOMS=(1 6)",
+                "MDLV30/HILITE ATOMS=(1 1)",
+            ],
+        );
+        let mut groups = Vec::new();
+        let molecule = read(&text, Some(&mut groups)).unwrap();
+
+        assert_eq!(
+            groups,
+            vec![
+                StereoGroup::Absolute(vec![1]),
+                StereoGroup::Or(1, vec![3]),
+                StereoGroup::And(2, vec![5])
+            ]
+        );
+        assert_eq!(molecule.atom(1).unwrap().parity, Some(Parity::Negative));
+        assert_eq!(molecule.atom(3).unwrap().parity, Some(Parity::Positive))
+    }
+}
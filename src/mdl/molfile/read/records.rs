@@ -0,0 +1,316 @@
+use std::str::FromStr;
+
+use super::Error;
+use crate::molecule::Element;
+
+/// An atom block entry. Charge and radical hold atom block values until
+/// overridden by properties.
+#[derive(Debug, PartialEq)]
+pub struct AtomRecord {
+    pub line: usize,
+    pub element: Option<Element>,
+    pub isotope: Option<u16>,
+    pub mass_difference: i8,
+    pub charge: i8,
+    pub radical: u8,
+    pub parity: u8,
+    pub valence: Option<u8>,
+}
+
+/// A bond block entry, with zero-based atom indexes.
+#[derive(Debug, PartialEq)]
+pub struct BondRecord {
+    pub line: usize,
+    pub sid: usize,
+    pub tid: usize,
+    pub kind: u8,
+}
+
+/// Reads the atom and bond counts from a V2000 counts line.
+pub fn read_counts(line: &str, number: usize) -> Result<(usize, usize), Error> {
+    let atoms = field(line, 0, 3).and_then(|atoms| atoms.parse().ok());
+    let bonds = field(line, 3, 6).and_then(|bonds| bonds.parse().ok());
+
+    match (atoms, bonds) {
+        (Some(atoms), Some(bonds)) => Ok((atoms, bonds)),
+        _ => Err(Error::Counts(number)),
+    }
+}
+
+pub fn read_atom(line: &str, number: usize) -> Result<AtomRecord, Error> {
+    let (element, isotope) = match field(line, 31, 34) {
+        Some("*") | Some("A") | Some("Q") | Some("R") | Some("R#") => {
+            (None, None)
+        }
+        Some("D") => (Some(Element::H), Some(2)),
+        Some("T") => (Some(Element::H), Some(3)),
+        Some(symbol) => match symbol.parse::<Element>() {
+            Ok(element) => (Some(element), None),
+            Err(_) => return Err(Error::Symbol(number)),
+        },
+        None => return Err(Error::Atom(number)),
+    };
+    let mass_difference = integer(line, 34, 36).ok_or(Error::Atom(number))?;
+    let (charge, radical) =
+        match integer(line, 36, 39).ok_or(Error::Atom(number))? {
+            0 => (0, 0),
+            1 => (3, 0),
+            2 => (2, 0),
+            3 => (1, 0),
+            4 => (0, 2),
+            5 => (-1, 0),
+            6 => (-2, 0),
+            7 => (-3, 0),
+            _ => return Err(Error::Atom(number)),
+        };
+    let parity = match integer(line, 39, 42).ok_or(Error::Atom(number))? {
+        parity @ 0..=3 => parity,
+        _ => return Err(Error::Atom(number)),
+    };
+    let valence = match integer(line, 48, 51).ok_or(Error::Atom(number))? {
+        0 => None,
+        15 => Some(0),
+        valence if valence > 0 && valence < 15 => Some(valence as u8),
+        _ => return Err(Error::Atom(number)),
+    };
+
+    Ok(AtomRecord {
+        line: number,
+        element,
+        isotope,
+        mass_difference,
+        charge,
+        radical,
+        parity,
+        valence,
+    })
+}
+
+/// Reads a V2000 bond line, checking atom numbers against atom count.
+pub fn read_bond(
+    line: &str,
+    number: usize,
+    atom_count: usize,
+) -> Result<BondRecord, Error> {
+    let sid = integer::<usize>(line, 0, 3).ok_or(Error::Bond(number))?;
+    let tid = integer::<usize>(line, 3, 6).ok_or(Error::Bond(number))?;
+    let kind = integer(line, 6, 9).ok_or(Error::Bond(number))?;
+
+    if sid == 0 || tid == 0 || sid > atom_count || tid > atom_count {
+        return Err(Error::Bond(number));
+    }
+
+    match kind {
+        1..=4 => Ok(BondRecord {
+            line: number,
+            sid: sid - 1,
+            tid: tid - 1,
+            kind,
+        }),
+        _ => Err(Error::BondType(number)),
+    }
+}
+
+// Returns the trimmed text between start and end, or None if the text is
+// blank or line is too short.
+fn field(line: &str, start: usize, end: usize) -> Option<&str> {
+    let end = end.min(line.len());
+    let result = line.get(start..end)?.trim();
+
+    if result.is_empty() {
+        None
+    } else {
+        Some(result)
+    }
+}
+
+// Returns the integer between start and end, zero if blank, or None if
+// the text can't be parsed.
+fn integer<T: FromStr + Default>(
+    line: &str,
+    start: usize,
+    end: usize,
+) -> Option<T> {
+    if start < line.len() && line.get(start..end.min(line.len())).is_none() {
+        return None;
+    }
+
+    match field(line, start, end) {
+        Some(text) => text.parse().ok(),
+        None => Some(T::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn counts_blank() {
+        assert_eq!(read_counts("", 4), Err(Error::Counts(4)))
+    }
+
+    #[test]
+    fn counts_letters() {
+        assert_eq!(
+            read_counts("  a  1  0  0  0  0  0  0  0  0999 V2000", 4),
+            Err(Error::Counts(4))
+        )
+    }
+
+    #[test]
+    fn counts() {
+        assert_eq!(
+            read_counts(" 12 11  0  0  0  0  0  0  0  0999 V2000", 4),
+            Ok((12, 11))
+        )
+    }
+
+    #[test]
+    fn atom_short() {
+        assert_eq!(read_atom("    0.0000    0.0000", 5), Err(Error::Atom(5)))
+    }
+
+    #[test]
+    fn atom_unknown_symbol() {
+        assert_eq!(
+            read_atom(
+                "    0.0000    0.0000    0.0000 Xx  0  0  0  0  0  0  0  0  0  0  0  0",
+                5
+            ),
+            Err(Error::Symbol(5))
+        )
+    }
+
+    #[test]
+    fn atom_bad_charge() {
+        assert_eq!(
+            read_atom(
+                "    0.0000    0.0000    0.0000 C   0  8  0  0  0  0  0  0  0  0  0  0",
+                5
+            ),
+            Err(Error::Atom(5))
+        )
+    }
+
+    #[test]
+    fn atom_minimal() {
+        assert_eq!(
+            read_atom("    0.0000    0.0000    0.0000 Cl", 5),
+            Ok(AtomRecord {
+                line: 5,
+                element: Some(Element::Cl),
+                isotope: None,
+                mass_difference: 0,
+                charge: 0,
+                radical: 0,
+                parity: 0,
+                valence: None
+            })
+        )
+    }
+
+    #[test]
+    fn atom_full() {
+        assert_eq!(
+            read_atom(
+                "    0.0000    0.0000    0.0000 N   1  3  0  0  0 15  0  0  0  0  0  0",
+                5
+            ),
+            Ok(AtomRecord {
+                line: 5,
+                element: Some(Element::N),
+                isotope: None,
+                mass_difference: 1,
+                charge: 1,
+                radical: 0,
+                parity: 0,
+                valence: Some(0)
+            })
+        )
+    }
+
+    #[test]
+    fn atom_doublet() {
+        assert_eq!(
+            read_atom(
+                "    0.0000    0.0000    0.0000 C   0  4  0  0  0  0  0  0  0  0  0  0",
+                5
+            ),
+            Ok(AtomRecord {
+                line: 5,
+                element: Some(Element::C),
+                isotope: None,
+                mass_difference: 0,
+                charge: 0,
+                radical: 2,
+                parity: 0,
+                valence: None
+            })
+        )
+    }
+
+    #[test]
+    fn atom_deuterium() {
+        assert_eq!(
+            read_atom("    0.0000    0.0000    0.0000 D   0  0", 5),
+            Ok(AtomRecord {
+                line: 5,
+                element: Some(Element::H),
+                isotope: Some(2),
+                mass_difference: 0,
+                charge: 0,
+                radical: 0,
+                parity: 0,
+                valence: None
+            })
+        )
+    }
+
+    #[test]
+    fn atom_star() {
+        assert_eq!(
+            read_atom("    0.0000    0.0000    0.0000 R#  0  0", 5),
+            Ok(AtomRecord {
+                line: 5,
+                element: None,
+                isotope: None,
+                mass_difference: 0,
+                charge: 0,
+                radical: 0,
+                parity: 0,
+                valence: None
+            })
+        )
+    }
+
+    #[test]
+    fn bond_unknown_atom() {
+        assert_eq!(read_bond("  1  3  1  0", 7, 2), Err(Error::Bond(7)))
+    }
+
+    #[test]
+    fn bond_zero_atom() {
+        assert_eq!(read_bond("  0  1  1  0", 7, 2), Err(Error::Bond(7)))
+    }
+
+    #[test]
+    fn bond_query_type() {
+        assert_eq!(read_bond("  1  2  8  0", 7, 2), Err(Error::BondType(7)))
+    }
+
+    #[test]
+    fn bond() {
+        assert_eq!(
+            read_bond("  1  2  2  0  0  0  0", 7, 2),
+            Ok(BondRecord {
+                line: 7,
+                sid: 0,
+                tid: 1,
+                kind: 2
+            })
+        )
+    }
+}
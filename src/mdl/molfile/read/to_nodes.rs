@@ -0,0 +1,133 @@
+use std::convert::TryFrom;
+
+use super::{implicit_hydrogens, kekulize, AtomRecord, BondRecord, Error};
+use crate::mdl::molfile::odd_sort;
+use crate::molecule::{Atom, Bond, Node, Parity};
+
+/// Builds Nodes from atom and bond records. Aromatic bonds are kekulized
+/// and implicit hydrogens are assigned using the MDL valence model.
+pub fn to_nodes(
+    atoms: &[AtomRecord],
+    bonds: &[BondRecord],
+) -> Result<Vec<Node>, Error> {
+    let mut outs = atoms.iter().map(|_| Vec::new()).collect::<Vec<_>>();
+    let mut aromatic = Vec::new();
+
+    for bond in bonds {
+        if bond.sid == bond.tid
+            || outs[bond.sid].iter().any(|out: &Bond| out.tid == bond.tid)
+        {
+            return Err(Error::Bond(bond.line));
+        }
+
+        let electrons = match bond.kind {
+            4 => {
+                aromatic.push((bond.sid, bond.tid));
+
+                2
+            }
+            kind => kind * 2,
+        };
+
+        outs[bond.sid].push(Bond::new(electrons, None, bond.tid));
+        outs[bond.tid].push(Bond::new(electrons, None, bond.sid));
+    }
+
+    kekulize(atoms, &mut outs, &aromatic)?;
+
+    let mut result = Vec::new();
+
+    for (record, bonds) in atoms.iter().zip(outs) {
+        result.push(Node {
+            atom: to_atom(record, &bonds)?,
+            bonds,
+            aromatic: false,
+        })
+    }
+
+    Ok(result)
+}
+
+fn to_atom(record: &AtomRecord, bonds: &[Bond]) -> Result<Atom, Error> {
+    let element = match &record.element {
+        Some(element) => element,
+        None => {
+            if record.charge != 0 {
+                return Err(Error::ChargedStar(record.line));
+            }
+
+            return Ok(Atom {
+                isotope: record.isotope,
+                ..Atom::default()
+            });
+        }
+    };
+
+    // A V2000 mass difference counts from the most abundant isotope.
+    let isotope = match record.isotope {
+        Some(isotope) => {
+            if !element.is_valid_mass_number(isotope) {
+                return Err(Error::Isotope(record.line));
+            }
+
+            Some(isotope)
+        }
+        None if record.mass_difference != 0 => {
+            let base = element.most_abundant_isotope().mass_number;
+
+            match u16::try_from(
+                i32::from(base) + i32::from(record.mass_difference),
+            ) {
+                Ok(isotope) if element.is_valid_mass_number(isotope) => {
+                    Some(isotope)
+                }
+                _ => return Err(Error::MassDifference(record.line)),
+            }
+        }
+        None => None,
+    };
+
+    let bonding = bonds.iter().fold(0, |sum, bond| sum + bond.electrons / 2);
+    let hydrogens = implicit_hydrogens(record, bonding);
+    let electrons = element.valence_electrons() as i16
+        - record.charge as i16
+        - bonding as i16
+        - hydrogens as i16;
+
+    match u8::try_from(electrons) {
+        Ok(electrons) => Ok(Atom {
+            element: Some(element.clone()),
+            isotope,
+            electrons,
+            hydrogens,
+            parity: to_parity(record.parity, hydrogens, bonds),
+            configuration: None,
+        }),
+        Err(_) => Err(Error::Valence(record.line)),
+    }
+}
+
+// Converts an MDL stereo parity into a Parity relative to the implicit
+// hydrogen, if any, followed by neighbors in bond order. Parities on atoms
+// that can't be tetrahedral centers are dropped.
+fn to_parity(parity: u8, hydrogens: u8, bonds: &[Bond]) -> Option<Parity> {
+    let parity = match parity {
+        1 => Parity::Positive,
+        2 => Parity::Negative,
+        _ => return None,
+    };
+    let mut reference =
+        bonds.iter().map(|bond| Some(bond.tid)).collect::<Vec<_>>();
+
+    match (hydrogens, reference.len()) {
+        (0, 4) => (),
+        (1, 3) => reference.insert(0, None),
+        _ => return None,
+    }
+
+    if odd_sort(&reference) {
+        Some(parity.negate())
+    } else {
+        Some(parity)
+    }
+}
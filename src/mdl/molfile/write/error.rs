@@ -0,0 +1,16 @@
+use gamma::graph::Error as GraphError;
+
+/// Errors encountered when writing a Molfile. Values are atom ids.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Charge(usize),
+    Hydrogens(usize),
+    BondOrder(usize, usize),
+    Graph(GraphError),
+}
+
+impl From<GraphError> for Error {
+    fn from(error: GraphError) -> Self {
+        Error::Graph(error)
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::{HashMap, HashSet};
+
+use gamma::graph::Error as GraphError;
+
+use super::Error;
+use crate::mdl::molfile::odd_sort;
+use crate::mdl::molfile::read::{implicit_hydrogens, AtomRecord};
+use crate::molecule::{Molecule, Parity};
+
+/// Atom attributes in Molfile terms, independent of version.
+#[derive(Debug, PartialEq)]
+pub struct AtomFields {
+    pub symbol: String,
+    pub charge: i8,
+    pub isotope: Option<u16>,
+    pub radical: u8,
+    pub valence: Option<u8>,
+    pub parity: u8,
+}
+
+/// Bond attributes in Molfile terms. Indexes are one-based.
+#[derive(Debug, PartialEq)]
+pub struct BondFields {
+    pub sid: usize,
+    pub tid: usize,
+    pub kind: u8,
+    pub either: bool,
+}
+
+/// Returns the fields of atom id. Indexes maps ids to one-based Molfile
+/// indexes. An explicit valence is set whenever the MDL valence model
+/// would not reproduce the hydrogen count.
+pub fn atom_fields<M: Molecule>(
+    molecule: &M,
+    id: usize,
+    indexes: &HashMap<usize, usize>,
+) -> Result<AtomFields, Error> {
+    let atom = molecule.atom(id)?;
+    let charge = molecule.charge(id)?;
+
+    if charge.fract() != 0. || charge.abs() > 15. {
+        return Err(Error::Charge(id));
+    }
+
+    let element = match &atom.element {
+        Some(element) => element,
+        None => {
+            if atom.hydrogens > 0 {
+                return Err(Error::Hydrogens(id));
+            }
+
+            return Ok(AtomFields {
+                symbol: String::from("*"),
+                charge: 0,
+                isotope: atom.isotope,
+                radical: 0,
+                valence: None,
+                parity: 0,
+            });
+        }
+    };
+    let bonding = molecule
+        .neighbors(id)?
+        .map(|tid| molecule.bond_order(id, tid))
+        .sum::<Result<f32, _>>()?;
+
+    if bonding.fract() != 0. || bonding > 14. {
+        return Err(Error::Hydrogens(id));
+    }
+
+    let bonding = bonding as u8;
+    let record = AtomRecord {
+        line: 0,
+        element: Some(element.clone()),
+        isotope: atom.isotope,
+        mass_difference: 0,
+        charge: charge as i8,
+        radical: if atom.electrons % 2 == 1 { 2 } else { 0 },
+        parity: 0,
+        valence: None,
+    };
+    let valence = if implicit_hydrogens(&record, bonding) == atom.hydrogens {
+        None
+    } else if bonding + atom.hydrogens < 15 {
+        Some(bonding + atom.hydrogens)
+    } else {
+        return Err(Error::Hydrogens(id));
+    };
+
+    Ok(AtomFields {
+        symbol: element.symbol().to_string(),
+        charge: record.charge,
+        isotope: record.isotope,
+        radical: record.radical,
+        valence,
+        parity: atom_parity(molecule, id, indexes)?,
+    })
+}
+
+/// Returns the fields of the bond joining sid and tid. Double bonds
+/// without parity outside of rings are marked as either cis or trans.
+/// Bonds with parity are left unmarked. Molfiles encode double bond
+/// configuration through coordinates alone, and those written here are
+/// zero, so the configuration is lost.
+pub fn bond_fields<M: Molecule>(
+    molecule: &M,
+    sid: usize,
+    tid: usize,
+    indexes: &HashMap<usize, usize>,
+) -> Result<BondFields, Error> {
+    let order = molecule.bond_order(sid, tid)?;
+    let kind = if order == 1. {
+        1
+    } else if order == 2. {
+        2
+    } else if order == 3. {
+        3
+    } else {
+        return Err(Error::BondOrder(sid, tid));
+    };
+    let parity = match molecule.bond(sid, tid)? {
+        Some(bond) => &bond.parity,
+        None => return Err(Error::Graph(GraphError::MissingEdge(sid, tid))),
+    };
+    let either = kind == 2
+        && parity.is_none()
+        && molecule.degree(sid)? > 1
+        && molecule.degree(tid)? > 1
+        && !is_cyclic(molecule, sid, tid)?;
+
+    Ok(BondFields {
+        sid: indexes[&sid],
+        tid: indexes[&tid],
+        kind,
+        either,
+    })
+}
+
+// Returns the MDL stereo parity of id: 1 if, viewed with the highest
+// indexed neighbor pointing away, the others run clockwise in ascending
+// order, 2 if counterclockwise, or 0 if no parity is set. Implicit
+// hydrogens count as highest.
+fn atom_parity<M: Molecule>(
+    molecule: &M,
+    id: usize,
+    indexes: &HashMap<usize, usize>,
+) -> Result<u8, Error> {
+    let parity = match &molecule.atom(id)?.parity {
+        Some(parity) => parity,
+        None => return Ok(0),
+    };
+    let mut reference = molecule
+        .neighbors(id)?
+        .map(|tid| Some(indexes[&tid]))
+        .collect::<Vec<_>>();
+
+    if reference.len() < 4 {
+        reference.insert(0, None);
+    }
+
+    let parity = if odd_sort(&reference) {
+        parity.negate()
+    } else {
+        parity.clone()
+    };
+
+    Ok(match parity {
+        Parity::Positive => 1,
+        Parity::Negative => 2,
+    })
+}
+
+// Returns true if sid and tid remain connected without their bond.
+fn is_cyclic<M: Molecule>(
+    molecule: &M,
+    sid: usize,
+    tid: usize,
+) -> Result<bool, Error> {
+    let mut visited = HashSet::new();
+    let mut stack = vec![sid];
+
+    visited.insert(sid);
+
+    while let Some(id) = stack.pop() {
+        for neighbor in molecule.neighbors(id)? {
+            if id == sid && neighbor == tid {
+                continue;
+            } else if neighbor == tid {
+                return Ok(true);
+            } else if visited.insert(neighbor) {
+                stack.push(neighbor);
+            }
+        }
+    }
+
+    Ok(false)
+}
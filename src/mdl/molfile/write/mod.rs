@@ -0,0 +1,8 @@
+mod error;
+mod fields;
+#[allow(clippy::module_inception)]
+mod write;
+
+pub use error::Error;
+pub use fields::{atom_fields, bond_fields, AtomFields, BondFields};
+pub use write::write;
@@ -0,0 +1,457 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use super::{atom_fields, bond_fields, AtomFields, BondFields, Error};
+use crate::molecule::Molecule;
+
+/// Writes a Molfile for molecule, with atoms numbered in the order given
+/// by `Graph::ids`. The V2000 format is used unless molecule has more than
+/// 999 atoms or bonds, in which case V3000 is used. Coordinates are zero,
+/// so double bond parities are not written.
+pub fn write<M: Molecule>(molecule: &M) -> Result<String, Error> {
+    let indexes = molecule
+        .ids()
+        .enumerate()
+        .map(|(index, id)| (id, index + 1))
+        .collect::<HashMap<_, _>>();
+    let mut atoms = Vec::new();
+    let mut bonds = Vec::new();
+
+    for id in molecule.ids() {
+        atoms.push(atom_fields(molecule, id, &indexes)?);
+    }
+
+    for (sid, tid) in molecule.edges() {
+        bonds.push(bond_fields(molecule, sid, tid, &indexes)?);
+    }
+
+    let mut out = String::from("\n  chemcore\n\n");
+
+    if atoms.len() > 999 || bonds.len() > 999 {
+        write_v3000(&atoms, &bonds, &mut out);
+    } else {
+        write_v2000(&atoms, &bonds, &mut out);
+    }
+
+    out.push_str("M  END\n");
+
+    Ok(out)
+}
+
+fn write_v2000(atoms: &[AtomFields], bonds: &[BondFields], out: &mut String) {
+    writeln!(
+        out,
+        "{:>3}{:>3}  0  0  0  0  0  0  0  0999 V2000",
+        atoms.len(),
+        bonds.len()
+    )
+    .expect("write");
+
+    for atom in atoms {
+        let charge = match atom.charge {
+            3 => 1,
+            2 => 2,
+            1 => 3,
+            -1 => 5,
+            -2 => 6,
+            -3 => 7,
+            _ => 0,
+        };
+
+        writeln!(
+            out,
+            "{:>10.4}{:>10.4}{:>10.4} {:<3} 0{:>3}{:>3}  0  0{:>3}  0  0  0  0  0  0",
+            0.,
+            0.,
+            0.,
+            atom.symbol,
+            charge,
+            atom.parity,
+            match atom.valence {
+                Some(0) => 15,
+                Some(valence) => valence,
+                None => 0,
+            }
+        )
+        .expect("write");
+    }
+
+    for bond in bonds {
+        writeln!(
+            out,
+            "{:>3}{:>3}{:>3}{:>3}  0  0  0",
+            bond.sid,
+            bond.tid,
+            bond.kind,
+            if bond.either { 3 } else { 0 }
+        )
+        .expect("write");
+    }
+
+    write_property(
+        "CHG",
+        atoms,
+        |atom| match atom.charge {
+            0 => None,
+            charge => Some(charge as i16),
+        },
+        out,
+    );
+    write_property(
+        "ISO",
+        atoms,
+        |atom| atom.isotope.map(|isotope| isotope as i16),
+        out,
+    );
+    write_property(
+        "RAD",
+        atoms,
+        |atom| match atom.radical {
+            0 => None,
+            radical => Some(radical as i16),
+        },
+        out,
+    );
+}
+
+// Writes a V2000 property block, with at most eight entries per line.
+fn write_property<F: Fn(&AtomFields) -> Option<i16>>(
+    name: &str,
+    atoms: &[AtomFields],
+    value: F,
+    out: &mut String,
+) {
+    let entries = atoms
+        .iter()
+        .enumerate()
+        .filter_map(|(index, atom)| value(atom).map(|value| (index + 1, value)))
+        .collect::<Vec<_>>();
+
+    for chunk in entries.chunks(8) {
+        write!(out, "M  {}{:>3}", name, chunk.len()).expect("write");
+
+        for (index, value) in chunk {
+            write!(out, " {:>3} {:>3}", index, value).expect("write");
+        }
+
+        out.push('\n');
+    }
+}
+
+fn write_v3000(atoms: &[AtomFields], bonds: &[BondFields], out: &mut String) {
+    out.push_str("  0  0  0     0  0            999 V3000\n");
+    out.push_str("M  V30 BEGIN CTAB\n");
+    writeln!(out, "M  V30 COUNTS {} {} 0 0 0", atoms.len(), bonds.len())
+        .expect("write");
+    out.push_str("M  V30 BEGIN ATOM\n");
+
+    for (index, atom) in atoms.iter().enumerate() {
+        write!(out, "M  V30 {} {} 0 0 0 0", index + 1, atom.symbol)
+            .expect("write");
+
+        if atom.charge != 0 {
+            write!(out, " CHG={}", atom.charge).expect("write");
+        }
+
+        if let Some(isotope) = atom.isotope {
+            write!(out, " MASS={}", isotope).expect("write");
+        }
+
+        if atom.radical != 0 {
+            write!(out, " RAD={}", atom.radical).expect("write");
+        }
+
+        if let Some(valence) = atom.valence {
+            let valence = if valence == 0 { -1 } else { valence as i8 };
+
+            write!(out, " VAL={}", valence).expect("write");
+        }
+
+        if atom.parity != 0 {
+            write!(out, " CFG={}", atom.parity).expect("write");
+        }
+
+        out.push('\n');
+    }
+
+    out.push_str("M  V30 END ATOM\n");
+    out.push_str("M  V30 BEGIN BOND\n");
+
+    for (index, bond) in bonds.iter().enumerate() {
+        write!(
+            out,
+            "M  V30 {} {} {} {}",
+            index + 1,
+            bond.kind,
+            bond.sid,
+            bond.tid
+        )
+        .expect("write");
+
+        if bond.either {
+            out.push_str(" CFG=2");
+        }
+
+        out.push('\n');
+    }
+
+    out.push_str("M  V30 END BOND\n");
+    out.push_str("M  V30 END CTAB\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::{read_smiles, write_canonical_smiles};
+    use crate::mdl::read_molfile;
+    use crate::molecule::{Atom, Bond, DefaultMolecule, Node};
+
+    fn round_trip(smiles: &str) -> String {
+        let molecule = read_smiles(smiles, None).unwrap();
+        let molfile = write(&molecule).unwrap();
+
+        write_canonical_smiles(&read_molfile(&molfile, None).unwrap()).unwrap()
+    }
+
+    fn canonical(smiles: &str) -> String {
+        write_canonical_smiles(&read_smiles(smiles, None).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn quadruple_bond() {
+        let molecule = read_smiles("[Cr]$[Cr]", None).unwrap();
+
+        assert_eq!(write(&molecule), Err(Error::BondOrder(0, 1)))
+    }
+
+    #[test]
+    fn star_hydrogens() {
+        let molecule = DefaultMolecule::new(vec![Node {
+            atom: Atom {
+                element: None,
+                electrons: 0,
+                hydrogens: 1,
+                isotope: None,
+                parity: None,
+                configuration: None,
+            },
+            bonds: vec![],
+            aromatic: false,
+        }]);
+
+        assert_eq!(write(&molecule), Err(Error::Hydrogens(0)))
+    }
+
+    #[test]
+    fn ethanol() {
+        let molecule = read_smiles("CCO", None).unwrap();
+
+        assert_eq!(
+            write(&molecule),
+            Ok([
+                "",
+                "  chemcore",
+                "",
+                "  3  2  0  0  0  0  0  0  0  0999 V2000",
+                "    0.0000    0.0000    0.0000 C   0  0  0  0  0  0  0  0  0  0  0  0",
+                "    0.0000    0.0000    0.0000 C   0  0  0  0  0  0  0  0  0  0  0  0",
+                "    0.0000    0.0000    0.0000 O   0  0  0  0  0  0  0  0  0  0  0  0",
+                "  1  2  1  0  0  0  0",
+                "  2  3  1  0  0  0  0",
+                "M  END",
+                ""
+            ]
+            .join("\n"))
+        )
+    }
+
+    #[test]
+    fn properties() {
+        let molecule = read_smiles("[13CH3][NH3+].[CH3].[O-2]", None).unwrap();
+        let molfile = write(&molecule).unwrap();
+        let lines = molfile.lines().collect::<Vec<_>>();
+
+        assert_eq!(
+            lines[lines.len() - 4..].to_vec(),
+            vec![
+                "M  CHG  2   2   1   4  -2",
+                "M  ISO  1   1  13",
+                "M  RAD  1   3   2",
+                "M  END"
+            ]
+        )
+    }
+
+    #[test]
+    fn atom_block_charge() {
+        let molecule = read_smiles("[NH4+]", None).unwrap();
+        let molfile = write(&molecule).unwrap();
+
+        assert_eq!(&molfile.lines().nth(4).unwrap()[31..42], "N   0  3  0")
+    }
+
+    #[test]
+    fn explicit_valence() {
+        let molecule = read_smiles("[CH2]", None).unwrap();
+        let molfile = write(&molecule).unwrap();
+
+        assert_eq!(&molfile.lines().nth(4).unwrap()[48..51], "  2")
+    }
+
+    #[test]
+    fn no_hydrogens() {
+        let molecule = read_smiles("[C]", None).unwrap();
+        let molfile = write(&molecule).unwrap();
+
+        assert_eq!(&molfile.lines().nth(4).unwrap()[48..51], " 15")
+    }
+
+    #[test]
+    fn tetrahedral_parity() {
+        let molecule = read_smiles("N[C@@H](C)C(=O)O", None).unwrap();
+        let molfile = write(&molecule).unwrap();
+
+        assert_eq!(&molfile.lines().nth(5).unwrap()[39..42], "  1")
+    }
+
+    #[test]
+    fn tetrahedral_parity_reversed() {
+        let molecule = read_smiles("N[C@H](C)C(=O)O", None).unwrap();
+        let molfile = write(&molecule).unwrap();
+
+        assert_eq!(&molfile.lines().nth(5).unwrap()[39..42], "  2")
+    }
+
+    #[test]
+    fn tetrahedral_parity_permuted() {
+        let molecule = read_smiles("C[C@@H](C(=O)O)N", None).unwrap();
+        let molfile = write(&molecule).unwrap();
+
+        assert_eq!(&molfile.lines().nth(5).unwrap()[39..42], "  1")
+    }
+
+    #[test]
+    fn either_double_bond() {
+        let molecule = read_smiles("CC=CC", None).unwrap();
+        let molfile = write(&molecule).unwrap();
+
+        assert_eq!(molfile.lines().nth(9), Some("  2  3  2  3  0  0  0"))
+    }
+
+    #[test]
+    fn parity_double_bond() {
+        let molecule = read_smiles("F/C=C/F", None).unwrap();
+        let molfile = write(&molecule).unwrap();
+
+        assert_eq!(molfile.lines().nth(9), Some("  2  3  2  0  0  0  0"))
+    }
+
+    #[test]
+    fn ring_double_bond() {
+        let molecule = read_smiles("C1CCC=CC1", None).unwrap();
+        let molfile = write(&molecule).unwrap();
+
+        assert_eq!(molfile.matches("  2  3  0  0  0").count(), 0)
+    }
+
+    #[test]
+    fn v3000() {
+        let molecule = read_smiles(&"C".repeat(1000), None).unwrap();
+        let molfile = write(&molecule).unwrap();
+        let lines = molfile.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines[3], "  0  0  0     0  0            999 V3000");
+        assert_eq!(lines[5], "M  V30 COUNTS 1000 999 0 0 0");
+        assert_eq!(lines[7], "M  V30 1 C 0 0 0 0");
+        assert_eq!(lines[1009], "M  V30 1 1 1 2");
+        assert_eq!(lines[lines.len() - 1], "M  END")
+    }
+
+    #[test]
+    fn v3000_round_trip() {
+        let smiles = format!("{}[C@@H](F)Cl", "C".repeat(999));
+
+        assert_eq!(round_trip(&smiles), canonical(&smiles))
+    }
+
+    #[test]
+    fn v3000_fields() {
+        let atoms = vec![AtomFields {
+            symbol: String::from("C"),
+            charge: -1,
+            isotope: Some(13),
+            radical: 2,
+            valence: Some(0),
+            parity: 2,
+        }];
+        let mut out = String::new();
+
+        write_v3000(&atoms, &[], &mut out);
+
+        assert_eq!(
+            out.lines().nth(4),
+            Some("M  V30 1 C 0 0 0 0 CHG=-1 MASS=13 RAD=2 VAL=-1 CFG=2")
+        )
+    }
+
+    #[test]
+    fn round_trips() {
+        for smiles in &[
+            "CCO",
+            "c1ccccc1",
+            "c1ccc2ccccc2c1",
+            "C[N+](C)(C)C",
+            "[O-]C(=O)CC",
+            "[CH2]C",
+            "[CH]C",
+            "CS(=O)(=O)C",
+            "[13CH4]",
+            "C#N",
+            "[Na+].[Cl-]",
+            "B(F)(F)F",
+            "[BH4-]",
+            "C[S+](C)C",
+            "N[C@@H](C)C(=O)O",
+            "N[C@H](C)C(=O)O",
+            "C[C@](F)(Cl)Br",
+            "F[C@H]1CC[C@@H](Cl)CC1",
+        ] {
+            assert_eq!(round_trip(smiles), canonical(smiles), "{}", smiles)
+        }
+    }
+
+    #[test]
+    fn bond_block_order() {
+        let molecule = DefaultMolecule::new(vec![
+            Node {
+                atom: Atom {
+                    element: Some(crate::molecule::Element::O),
+                    electrons: 4,
+                    hydrogens: 0,
+                    isotope: None,
+                    parity: None,
+                    configuration: None,
+                },
+                bonds: vec![Bond::new(4, None, 1)],
+                aromatic: false,
+            },
+            Node {
+                atom: Atom {
+                    element: Some(crate::molecule::Element::O),
+                    electrons: 4,
+                    hydrogens: 0,
+                    isotope: None,
+                    parity: None,
+                    configuration: None,
+                },
+                bonds: vec![Bond::new(4, None, 0)],
+                aromatic: false,
+            },
+        ]);
+        let molfile = write(&molecule).unwrap();
+
+        assert_eq!(molfile.lines().nth(6), Some("  1  2  2  0  0  0  0"))
+    }
+}
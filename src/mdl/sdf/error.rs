@@ -0,0 +1,44 @@
+use std::io;
+
+use crate::mdl::molfile::{
+    ReadError as MolfileReadError, WriteError as MolfileWriteError,
+};
+
+/// Errors encountered when reading an SD file. Line numbers are one-based
+/// and count from the start of the file. Molfile gives the first line of
+/// the offending record, from which the molfile error's own line numbers
+/// count. DataHeader gives the line of the malformed header.
+#[derive(Debug)]
+pub enum ReadError {
+    Molfile(usize, MolfileReadError),
+    DataHeader(usize),
+    Io(io::Error),
+}
+
+impl From<io::Error> for ReadError {
+    fn from(error: io::Error) -> Self {
+        ReadError::Io(error)
+    }
+}
+
+/// Errors encountered when writing an SD file. DataName and DataValue give
+/// the index of the offending property.
+#[derive(Debug)]
+pub enum WriteError {
+    Molfile(MolfileWriteError),
+    DataName(usize),
+    DataValue(usize),
+    Io(io::Error),
+}
+
+impl From<MolfileWriteError> for WriteError {
+    fn from(error: MolfileWriteError) -> Self {
+        WriteError::Molfile(error)
+    }
+}
+
+impl From<io::Error> for WriteError {
+    fn from(error: io::Error) -> Self {
+        WriteError::Io(error)
+    }
+}
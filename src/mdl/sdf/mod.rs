@@ -0,0 +1,9 @@
+mod error;
+mod reader;
+mod writer;
+
+pub use error::{ReadError, WriteError};
+pub use reader::{Record, SdfReader};
+pub use writer::SdfWriter;
+
+use reader::is_blank;
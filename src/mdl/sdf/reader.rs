@@ -0,0 +1,266 @@
+use std::io::BufRead;
+
+use super::ReadError;
+use crate::mdl::molfile::read as read_molfile;
+use crate::molecule::DefaultMolecule;
+
+/// A molecule and its data fields, in file order.
+pub type Record = (DefaultMolecule, Vec<(String, String)>);
+
+/// Reads SD file records one at a time. A malformed record yields an
+/// error, after which reading resumes at the next record. Bytes that are
+/// not valid UTF-8 are replaced with U+FFFD.
+pub struct SdfReader<R: BufRead> {
+    reader: R,
+    line: usize,
+    done: bool,
+}
+
+impl<R: BufRead> SdfReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: 0,
+            done: false,
+        }
+    }
+
+    // Returns the lines of the next record, excluding its terminator, and
+    // the number of its first line. Returns None at end of input.
+    fn next_lines(
+        &mut self,
+    ) -> Result<Option<(usize, Vec<String>)>, ReadError> {
+        let start = self.line + 1;
+        let mut lines = Vec::new();
+        let mut buffer = Vec::new();
+
+        loop {
+            buffer.clear();
+
+            if self.reader.read_until(b'\n', &mut buffer)? == 0 {
+                self.done = true;
+
+                if lines.iter().all(|line: &String| is_blank(line)) {
+                    return Ok(None);
+                } else {
+                    return Ok(Some((start, lines)));
+                }
+            }
+
+            self.line += 1;
+
+            let text = String::from_utf8_lossy(&buffer);
+            let line = text.trim_end_matches(&['\n', '\r'][..]);
+
+            if line.starts_with("$$$$") {
+                return Ok(Some((start, lines)));
+            }
+
+            lines.push(line.to_string());
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for SdfReader<R> {
+    type Item = Result<Record, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.next_lines() {
+            Ok(Some((start, lines))) => Some(read_record(start, &lines)),
+            Ok(None) => None,
+            Err(error) => {
+                self.done = true;
+
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+// Returns true if line separates data fields.
+pub fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+fn read_record(start: usize, lines: &[String]) -> Result<Record, ReadError> {
+    let end = lines
+        .iter()
+        .position(|line| line.starts_with("M  END"))
+        .map_or(lines.len(), |index| index + 1);
+    let molecule = match read_molfile(&lines[..end].join("\n"), None) {
+        Ok(molecule) => molecule,
+        Err(error) => return Err(ReadError::Molfile(start, error)),
+    };
+    let mut properties = Vec::new();
+    let mut index = end;
+
+    while index < lines.len() {
+        let line = &lines[index];
+
+        index += 1;
+
+        if is_blank(line) {
+            continue;
+        }
+
+        let name =
+            match (line.starts_with('>'), line.find('<'), line.rfind('>')) {
+                (true, Some(open), Some(close)) if close > open => {
+                    &line[open + 1..close]
+                }
+                _ => return Err(ReadError::DataHeader(start + index - 1)),
+            };
+        let mut value = Vec::new();
+
+        while index < lines.len() && !is_blank(&lines[index]) {
+            value.push(lines[index].as_str());
+            index += 1;
+        }
+
+        properties.push((name.to_string(), value.join("\n")));
+    }
+
+    Ok((molecule, properties))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::write_canonical_smiles;
+    use crate::mdl::molfile::ReadError as MolfileReadError;
+
+    const METHANE: &str = "methane
+  chemcore
+
+  1  0  0  0  0  0  0  0  0  0999 V2000
+    0.0000    0.0000    0.0000 C   0  0  0  0  0  0  0  0  0  0  0  0
+M  END";
+
+    fn smiles(record: Option<Result<Record, ReadError>>) -> String {
+        write_canonical_smiles(&record.unwrap().unwrap().0).unwrap()
+    }
+
+    #[test]
+    fn empty() {
+        let mut reader = SdfReader::new("".as_bytes());
+
+        assert!(reader.next().is_none())
+    }
+
+    #[test]
+    fn trailing_whitespace() {
+        let text = format!("{}\n$$$$\n\n", METHANE);
+        let mut reader = SdfReader::new(text.as_bytes());
+
+        assert_eq!(smiles(reader.next()), "C");
+        assert!(reader.next().is_none())
+    }
+
+    #[test]
+    fn missing_terminator() {
+        let mut reader = SdfReader::new(METHANE.as_bytes());
+
+        assert_eq!(smiles(reader.next()), "C");
+        assert!(reader.next().is_none())
+    }
+
+    #[test]
+    fn properties() {
+        let text = format!(
+            "{}\n> <ID>\nMOL-1\n\n>  <NOTES> (1)\nfirst\nsecond\n\n$$$$\n",
+            METHANE
+        );
+        let mut reader = SdfReader::new(text.as_bytes());
+
+        assert_eq!(
+            reader.next().unwrap().unwrap().1,
+            vec![
+                (String::from("ID"), String::from("MOL-1")),
+                (String::from("NOTES"), String::from("first\nsecond"))
+            ]
+        )
+    }
+
+    #[test]
+    fn whitespace_separator() {
+        let text =
+            format!("{}\n> <ID>\nMOL-1\n  \n> <N>\n1\n\n$$$$\n", METHANE);
+        let mut reader = SdfReader::new(text.as_bytes());
+
+        assert_eq!(
+            reader.next().unwrap().unwrap().1,
+            vec![
+                (String::from("ID"), String::from("MOL-1")),
+                (String::from("N"), String::from("1"))
+            ]
+        )
+    }
+
+    #[test]
+    fn bad_data_header() {
+        let text = format!("{}\nID\nMOL-1\n\n$$$$\n", METHANE);
+        let mut reader = SdfReader::new(text.as_bytes());
+
+        assert!(matches!(reader.next(), Some(Err(ReadError::DataHeader(7)))))
+    }
+
+    #[test]
+    fn recovers_from_bad_record() {
+        let bad = METHANE.replace(" C   0", " Xx  0");
+        let text = format!(
+            "{}\n$$$$\n{}\n$$$$\n{}\n$$$$\n",
+            METHANE,
+            bad,
+            METHANE.replace(" C ", " O ")
+        );
+        let mut reader = SdfReader::new(text.as_bytes());
+
+        assert_eq!(smiles(reader.next()), "C");
+        assert!(matches!(
+            reader.next(),
+            Some(Err(ReadError::Molfile(8, MolfileReadError::Symbol(5))))
+        ));
+        assert_eq!(smiles(reader.next()), "O");
+        assert!(reader.next().is_none())
+    }
+
+    #[test]
+    fn latin_1_data() {
+        let mut bytes = format!("{}\n> <NAME>\ncaf", METHANE).into_bytes();
+
+        bytes.push(0xe9);
+        bytes.extend_from_slice(b"\n\n$$$$\n");
+        bytes.extend_from_slice(METHANE.replace(" C ", " O ").as_bytes());
+
+        let mut reader = SdfReader::new(&bytes[..]);
+
+        assert_eq!(
+            reader.next().unwrap().unwrap().1,
+            vec![(String::from("NAME"), String::from("caf\u{fffd}"))]
+        );
+        assert_eq!(smiles(reader.next()), "O");
+        assert!(reader.next().is_none())
+    }
+
+    #[test]
+    fn recovers_from_missing_end() {
+        let text = format!(
+            "{}\n$$$$\n{}\n$$$$\n",
+            METHANE.replace("M  END", ""),
+            METHANE
+        );
+        let mut reader = SdfReader::new(text.as_bytes());
+
+        assert!(matches!(
+            reader.next(),
+            Some(Err(ReadError::Molfile(1, MolfileReadError::EndOfFile(6))))
+        ));
+        assert_eq!(smiles(reader.next()), "C")
+    }
+}
@@ -0,0 +1,171 @@
+use std::io::Write;
+
+use super::{is_blank, WriteError};
+use crate::mdl::molfile::write as write_molfile;
+use crate::molecule::Molecule;
+
+/// Writes SD file records, each a Molfile followed by its data fields.
+pub struct SdfWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> SdfWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Writes molecule with properties as `> <NAME>` data fields. Nothing
+    /// is written if a name or value couldn't be read back: a name
+    /// containing `>` or a line break, or a value with a blank line or a
+    /// line starting with `$$$$`.
+    pub fn write<M: Molecule>(
+        &mut self,
+        molecule: &M,
+        properties: &[(String, String)],
+    ) -> Result<(), WriteError> {
+        for (index, (name, value)) in properties.iter().enumerate() {
+            if name.contains(&['>', '\n', '\r'][..]) {
+                return Err(WriteError::DataName(index));
+            }
+
+            if !value.is_empty()
+                && value
+                    .split('\n')
+                    .any(|line| is_blank(line) || line.starts_with("$$$$"))
+            {
+                return Err(WriteError::DataValue(index));
+            }
+        }
+
+        let molfile = write_molfile(molecule)?;
+
+        self.writer.write_all(molfile.as_bytes())?;
+
+        for (name, value) in properties {
+            write!(self.writer, "> <{}>\n{}\n\n", name, value)?;
+        }
+
+        self.writer.write_all(b"$$$$\n")?;
+
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::{read_smiles, write_canonical_smiles};
+    use crate::mdl::molfile::WriteError as MolfileWriteError;
+    use crate::mdl::sdf::SdfReader;
+
+    #[test]
+    fn molfile_error() {
+        let mut writer = SdfWriter::new(Vec::new());
+        let molecule = read_smiles("[Cr]$[Cr]", None).unwrap();
+
+        assert!(matches!(
+            writer.write(&molecule, &[]),
+            Err(WriteError::Molfile(MolfileWriteError::BondOrder(0, 1)))
+        ))
+    }
+
+    #[test]
+    fn invalid_data_name() {
+        let mut writer = SdfWriter::new(Vec::new());
+        let molecule = read_smiles("C", None).unwrap();
+
+        for name in &["A>B", "A\nB"] {
+            let properties = vec![
+                (String::from("ID"), String::from("1")),
+                (name.to_string(), String::from("1")),
+            ];
+
+            assert!(matches!(
+                writer.write(&molecule, &properties),
+                Err(WriteError::DataName(1))
+            ))
+        }
+
+        assert!(writer.into_inner().is_empty())
+    }
+
+    #[test]
+    fn invalid_data_value() {
+        let mut writer = SdfWriter::new(Vec::new());
+        let molecule = read_smiles("C", None).unwrap();
+
+        for value in &["A\n\nB", "A\n \nB", "A\n", "$$$$"] {
+            let properties = vec![(String::from("ID"), value.to_string())];
+
+            assert!(matches!(
+                writer.write(&molecule, &properties),
+                Err(WriteError::DataValue(0))
+            ))
+        }
+
+        assert!(writer.into_inner().is_empty())
+    }
+
+    #[test]
+    fn data_fields() {
+        let mut writer = SdfWriter::new(Vec::new());
+        let molecule = read_smiles("C", None).unwrap();
+        let properties = vec![(String::from("ID"), String::from("MOL-1"))];
+
+        writer.write(&molecule, &properties).unwrap();
+
+        let text = String::from_utf8(writer.into_inner()).unwrap();
+
+        assert_eq!(
+            text.lines().skip(5).collect::<Vec<_>>(),
+            vec!["M  END", "> <ID>", "MOL-1", "", "$$$$"]
+        )
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut writer = SdfWriter::new(Vec::new());
+        let records = vec![
+            ("CCO", vec![(String::from("ID"), String::from("1"))]),
+            ("c1ccccc1", vec![(String::from("ID"), String::new())]),
+            (
+                "[NH4+].[Cl-]",
+                vec![
+                    (String::from("ID"), String::from("3")),
+                    (String::from("NAME"), String::from("ammonium\nchloride")),
+                ],
+            ),
+        ];
+
+        for (smiles, properties) in &records {
+            let molecule = read_smiles(smiles, None).unwrap();
+
+            writer.write(&molecule, properties).unwrap();
+        }
+
+        let bytes = writer.into_inner();
+        let read = SdfReader::new(&bytes[..])
+            .map(|record| {
+                let (molecule, properties) = record.unwrap();
+
+                (write_canonical_smiles(&molecule).unwrap(), properties)
+            })
+            .collect::<Vec<_>>();
+        let expected = records
+            .into_iter()
+            .map(|(smiles, properties)| {
+                let molecule = read_smiles(smiles, None).unwrap();
+
+                (write_canonical_smiles(&molecule).unwrap(), properties)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(read, expected)
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Element, Parity};
+use super::{Configuration, Element, Parity};
 
 #[derive(Debug, PartialEq, Clone, Default)]
 pub struct Atom {
@@ -7,4 +7,5 @@ pub struct Atom {
     pub electrons: u8,
     pub parity: Option<Parity>,
     pub hydrogens: u8,
+    pub configuration: Option<Configuration>,
 }
@@ -0,0 +1,7 @@
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Block {
+    S,
+    P,
+    D,
+    F,
+}
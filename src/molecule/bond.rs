@@ -5,6 +5,7 @@ pub struct Bond {
     pub electrons: u8,
     pub parity: Option<Parity>,
     pub tid: usize,
+    pub aromatic: bool,
 }
 
 impl Bond {
@@ -13,10 +14,11 @@ impl Bond {
             electrons,
             parity,
             tid,
+            aromatic: false,
         }
     }
 
     pub fn order(&self) -> f32 {
-        self.electrons as f32 / 2 as f32
+        self.electrons as f32 / 2f32
     }
 }
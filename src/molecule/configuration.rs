@@ -0,0 +1,469 @@
+use super::Parity;
+
+/// Non-tetrahedral stereo configurations, numbered as in OpenSMILES.
+///
+/// Like Parity, a configuration is relative to the order of neighbors
+/// reported by the Molecule, with any implicit hydrogen leading. An allene
+/// configuration is carried by the central atom of a cumulene, and refers
+/// to the substituents of the two terminal atoms: those of the terminal
+/// reached through the center's first neighbor, then those of the other.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Configuration {
+    Allene(Parity),
+    SquarePlanar(u8),
+    TrigonalBipyramidal(u8),
+    Octahedral(u8),
+}
+
+impl Configuration {
+    /// Returns the number of neighbors a configuration applies to.
+    pub fn neighbor_count(&self) -> usize {
+        match self {
+            Configuration::Allene(_) => 4,
+            Configuration::SquarePlanar(_) => 4,
+            Configuration::TrigonalBipyramidal(_) => 5,
+            Configuration::Octahedral(_) => 6,
+        }
+    }
+
+    /// Returns the equivalent configuration relative to order, given that
+    /// this one is relative to reference. Both must list the same
+    /// neighbors. None is returned for mismatched neighbors or an
+    /// out-of-range class.
+    pub fn permute<T: PartialEq>(
+        &self,
+        reference: &[T],
+        order: &[T],
+    ) -> Option<Self> {
+        if reference.len() != self.neighbor_count()
+            || order.len() != reference.len()
+        {
+            return None;
+        }
+
+        let positions = order
+            .iter()
+            .map(|item| reference.iter().position(|other| other == item))
+            .collect::<Option<Vec<_>>>()?;
+
+        match self {
+            Configuration::Allene(parity) => {
+                let mut swaps = 0;
+
+                for (i, left) in positions.iter().enumerate() {
+                    for right in positions.iter().skip(i + 1) {
+                        if left > right {
+                            swaps += 1;
+                        }
+                    }
+                }
+
+                Some(Configuration::Allene(if swaps % 2 == 0 {
+                    parity.clone()
+                } else {
+                    parity.negate()
+                }))
+            }
+            // The three classes differ by the neighbor trans to the first:
+            // the third (SP1, U), the second (SP2, 4) or the fourth (SP3, Z).
+            Configuration::SquarePlanar(class) => {
+                let trans = match class {
+                    1 => 2,
+                    2 => 1,
+                    3 => 3,
+                    _ => return None,
+                };
+                let partner = |index: usize| match index {
+                    0 => trans,
+                    _ if index == trans => 0,
+                    _ => (1..4)
+                        .find(|&other| other != index && other != trans)
+                        .expect("partner"),
+                };
+                let target = partner(positions[0]);
+
+                match positions.iter().position(|&index| index == target) {
+                    Some(2) => Some(Configuration::SquarePlanar(1)),
+                    Some(1) => Some(Configuration::SquarePlanar(2)),
+                    Some(3) => Some(Configuration::SquarePlanar(3)),
+                    _ => None,
+                }
+            }
+            Configuration::TrigonalBipyramidal(class) => {
+                let identity = (0..5).collect::<Vec<_>>();
+                let target = bipyramid(*class, &identity)?;
+
+                (1..=20)
+                    .find(|&other| {
+                        bipyramid(other, &positions).as_ref() == Some(&target)
+                    })
+                    .map(Configuration::TrigonalBipyramidal)
+            }
+            Configuration::Octahedral(class) => {
+                let identity = (0..6).collect::<Vec<_>>();
+                let target = octahedron(*class, &identity)?;
+
+                (1..=30)
+                    .find(|&other| {
+                        octahedron(other, &positions).as_ref() == Some(&target)
+                    })
+                    .map(Configuration::Octahedral)
+            }
+        }
+    }
+}
+
+// Trigonal bipyramidal classes as the positions of the axial neighbors,
+// from and to, and whether the equatorial neighbors in list order run
+// anticlockwise looking from the first.
+const TRIGONAL_BIPYRAMIDAL: [(usize, usize, bool); 20] = [
+    (0, 4, true),
+    (0, 4, false),
+    (0, 3, true),
+    (0, 3, false),
+    (0, 2, true),
+    (0, 2, false),
+    (0, 1, true),
+    (0, 1, false),
+    (1, 4, true),
+    (1, 3, true),
+    (1, 4, false),
+    (1, 3, false),
+    (1, 2, true),
+    (1, 2, false),
+    (2, 4, true),
+    (2, 3, true),
+    (3, 4, true),
+    (3, 4, false),
+    (2, 3, false),
+    (2, 4, false),
+];
+
+// Octahedral classes as the position of the neighbor trans to the first,
+// the position among the remaining four of the one trans to the first of
+// them (2 for U, 3 for Z, 1 for 4), and whether those four run
+// anticlockwise looking from the first neighbor.
+const OCTAHEDRAL: [(usize, usize, bool); 30] = [
+    (5, 2, true),
+    (5, 2, false),
+    (4, 2, true),
+    (4, 3, true),
+    (4, 1, true),
+    (4, 1, false),
+    (4, 3, false),
+    (4, 2, false),
+    (3, 2, true),
+    (3, 3, true),
+    (2, 2, true),
+    (2, 3, true),
+    (1, 2, true),
+    (1, 3, true),
+    (1, 1, true),
+    (5, 3, true),
+    (5, 1, true),
+    (3, 1, true),
+    (2, 1, true),
+    (1, 1, false),
+    (2, 1, false),
+    (3, 1, false),
+    (5, 1, false),
+    (1, 3, false),
+    (1, 2, false),
+    (2, 3, false),
+    (2, 2, false),
+    (3, 3, false),
+    (3, 2, false),
+    (5, 3, false),
+];
+
+// Returns the axial pair, lower first, and the equatorial ring
+// anticlockwise from the lower, beginning with its least member.
+fn bipyramid(class: u8, items: &[usize]) -> Option<(usize, usize, Vec<usize>)> {
+    let &(from, to, anticlockwise) =
+        TRIGONAL_BIPYRAMIDAL.get(usize::from(class).checked_sub(1)?)?;
+    let mut ring = items
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index != from && *index != to)
+        .map(|(_, &item)| item)
+        .collect::<Vec<_>>();
+    let (mut top, mut bottom) = (items[from], items[to]);
+
+    if !anticlockwise {
+        ring.reverse();
+    }
+
+    if top > bottom {
+        std::mem::swap(&mut top, &mut bottom);
+        ring.reverse();
+    }
+
+    let least = *ring.iter().min()?;
+    let start = ring.iter().position(|&item| item == least)?;
+
+    ring.rotate_left(start);
+
+    Some((top, bottom, ring))
+}
+
+// Returns the item trans to item 0 and the ring around that axis
+// anticlockwise from item 0, beginning with its least member.
+fn octahedron(class: u8, items: &[usize]) -> Option<(usize, Vec<usize>)> {
+    let &(trans, across, anticlockwise) =
+        OCTAHEDRAL.get(usize::from(class).checked_sub(1)?)?;
+    let rest = items
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(index, _)| *index != trans)
+        .map(|(_, &item)| item)
+        .collect::<Vec<_>>();
+    let mut ring = match across {
+        1 => vec![rest[0], rest[2], rest[1], rest[3]],
+        2 => rest,
+        _ => vec![rest[0], rest[1], rest[3], rest[2]],
+    };
+
+    if !anticlockwise {
+        ring.reverse();
+    }
+
+    // place the first item on +z with the ring anticlockwise about it
+    let vectors = [
+        [0, 0, 1],
+        [0, 0, -1],
+        [1, 0, 0],
+        [0, 1, 0],
+        [-1, 0, 0],
+        [0, -1, 0],
+    ];
+    let mut placed = vec![items[0], items[trans]];
+    placed.extend(ring);
+
+    let vector = |item: usize| -> Option<[i32; 3]> {
+        Some(vectors[placed.iter().position(|&other| other == item)?])
+    };
+    let find = |target: [i32; 3]| -> Option<usize> {
+        placed
+            .iter()
+            .zip(vectors.iter())
+            .find(|(_, vector)| **vector == target)
+            .map(|(&item, _)| item)
+    };
+    let top = vector(0)?;
+    let bottom = find([-top[0], -top[1], -top[2]])?;
+    let mut current = *placed
+        .iter()
+        .filter(|&&item| item != 0 && item != bottom)
+        .min()?;
+    let mut result = Vec::new();
+
+    for _ in 0..4 {
+        result.push(current);
+
+        let [x, y, z] = vector(current)?;
+
+        current = find([
+            top[1] * z - top[2] * y,
+            top[2] * x - top[0] * z,
+            top[0] * y - top[1] * x,
+        ])?;
+    }
+
+    Some((bottom, result))
+}
+
+#[cfg(test)]
+mod permute {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn allene_identity() {
+        let allene = Configuration::Allene(Parity::Negative);
+
+        assert_eq!(
+            allene.permute(&[0, 1, 2, 3], &[0, 1, 2, 3]),
+            Some(Configuration::Allene(Parity::Negative))
+        )
+    }
+
+    #[test]
+    fn allene_swap() {
+        let allene = Configuration::Allene(Parity::Negative);
+
+        assert_eq!(
+            allene.permute(&[0, 1, 2, 3], &[1, 0, 2, 3]),
+            Some(Configuration::Allene(Parity::Positive))
+        )
+    }
+
+    #[test]
+    fn allene_mismatch() {
+        let allene = Configuration::Allene(Parity::Negative);
+
+        assert_eq!(allene.permute(&[0, 1, 2, 3], &[0, 1, 2, 4]), None)
+    }
+
+    #[test]
+    fn square_planar_identity() {
+        for class in 1..=3 {
+            assert_eq!(
+                Configuration::SquarePlanar(class)
+                    .permute(&[0, 1, 2, 3], &[0, 1, 2, 3]),
+                Some(Configuration::SquarePlanar(class))
+            )
+        }
+    }
+
+    #[test]
+    fn square_planar_u_to_z() {
+        // U: 0-1-2-3 around the square; reading 0, 1, 3, 2 traces a Z.
+        assert_eq!(
+            Configuration::SquarePlanar(1)
+                .permute(&[0, 1, 2, 3], &[0, 1, 3, 2]),
+            Some(Configuration::SquarePlanar(3))
+        )
+    }
+
+    #[test]
+    fn square_planar_u_to_four() {
+        assert_eq!(
+            Configuration::SquarePlanar(1)
+                .permute(&[0, 1, 2, 3], &[0, 2, 1, 3]),
+            Some(Configuration::SquarePlanar(2))
+        )
+    }
+
+    #[test]
+    fn square_planar_rotated() {
+        assert_eq!(
+            Configuration::SquarePlanar(1)
+                .permute(&[0, 1, 2, 3], &[1, 2, 3, 0]),
+            Some(Configuration::SquarePlanar(1))
+        )
+    }
+
+    #[test]
+    fn square_planar_out_of_range() {
+        assert_eq!(
+            Configuration::SquarePlanar(4)
+                .permute(&[0, 1, 2, 3], &[0, 1, 2, 3]),
+            None
+        )
+    }
+
+    #[test]
+    fn trigonal_bipyramidal_identity() {
+        let order = [0, 1, 2, 3, 4];
+
+        for class in 1..=20 {
+            assert_eq!(
+                Configuration::TrigonalBipyramidal(class)
+                    .permute(&order, &order),
+                Some(Configuration::TrigonalBipyramidal(class))
+            )
+        }
+    }
+
+    #[test]
+    fn trigonal_bipyramidal_first_swapped() {
+        assert_eq!(
+            Configuration::TrigonalBipyramidal(1)
+                .permute(&[0, 1, 2, 3, 4], &[1, 0, 2, 3, 4]),
+            Some(Configuration::TrigonalBipyramidal(9))
+        )
+    }
+
+    #[test]
+    fn trigonal_bipyramidal_axis_reversed() {
+        assert_eq!(
+            Configuration::TrigonalBipyramidal(1)
+                .permute(&[0, 1, 2, 3, 4], &[4, 1, 2, 3, 0]),
+            Some(Configuration::TrigonalBipyramidal(2))
+        )
+    }
+
+    #[test]
+    fn trigonal_bipyramidal_round_trip() {
+        let reference = [0, 1, 2, 3, 4];
+        let order = [3, 0, 4, 2, 1];
+
+        for class in 1..=20 {
+            let permuted = Configuration::TrigonalBipyramidal(class)
+                .permute(&reference, &order)
+                .unwrap();
+
+            assert_eq!(
+                permuted.permute(&order, &reference),
+                Some(Configuration::TrigonalBipyramidal(class))
+            )
+        }
+    }
+
+    #[test]
+    fn trigonal_bipyramidal_out_of_range() {
+        assert_eq!(
+            Configuration::TrigonalBipyramidal(21)
+                .permute(&[0, 1, 2, 3, 4], &[0, 1, 2, 3, 4]),
+            None
+        )
+    }
+
+    #[test]
+    fn octahedral_identity() {
+        let order = [0, 1, 2, 3, 4, 5];
+
+        for class in 1..=30 {
+            assert_eq!(
+                Configuration::Octahedral(class).permute(&order, &order),
+                Some(Configuration::Octahedral(class))
+            )
+        }
+    }
+
+    #[test]
+    fn octahedral_axis_reversed() {
+        assert_eq!(
+            Configuration::Octahedral(1)
+                .permute(&[0, 1, 2, 3, 4, 5], &[5, 1, 2, 3, 4, 0]),
+            Some(Configuration::Octahedral(2))
+        )
+    }
+
+    #[test]
+    fn octahedral_u_to_z() {
+        assert_eq!(
+            Configuration::Octahedral(1)
+                .permute(&[0, 1, 2, 3, 4, 5], &[0, 1, 2, 4, 3, 5]),
+            Some(Configuration::Octahedral(16))
+        )
+    }
+
+    #[test]
+    fn octahedral_round_trip() {
+        let reference = [0, 1, 2, 3, 4, 5];
+        let order = [4, 2, 5, 0, 3, 1];
+
+        for class in 1..=30 {
+            let permuted = Configuration::Octahedral(class)
+                .permute(&reference, &order)
+                .unwrap();
+
+            assert_eq!(
+                permuted.permute(&order, &reference),
+                Some(Configuration::Octahedral(class))
+            )
+        }
+    }
+
+    #[test]
+    fn trigonal_bipyramidal_wrong_count() {
+        assert_eq!(
+            Configuration::TrigonalBipyramidal(1)
+                .permute(&[0, 1, 2, 3], &[0, 1, 2, 3]),
+            None
+        )
+    }
+}
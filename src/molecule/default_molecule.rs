@@ -1,6 +1,8 @@
+use std::convert::TryFrom;
+
 use gamma::graph::{Error as GraphError, Graph};
 
-use super::{Atom, Molecule, Node};
+use super::{Atom, Bond, Configuration, Error, Molecule, MoleculeMut, Node};
 
 #[derive(Debug, PartialEq)]
 pub struct DefaultMolecule {
@@ -9,6 +11,9 @@ pub struct DefaultMolecule {
 }
 
 impl DefaultMolecule {
+    /// Builds a DefaultMolecule from nodes without checking them, except
+    /// that an odd count of bonds panics. Ids run from zero in node order.
+    /// Use `try_new` for nodes from an untrusted source.
     pub fn new(nodes: Vec<Node>) -> Self {
         let size = nodes.iter().fold(0, |sum, node| sum + node.bonds.len());
 
@@ -20,12 +25,245 @@ impl DefaultMolecule {
         }
     }
 
+    /// Builds a DefaultMolecule from nodes, returning Error if they don't
+    /// describe a valid structure:
+    ///
+    /// - `Graph(UnknownId)` for a bond to a missing node
+    /// - `Graph(DuplicateEdge)` for two bonds to the same node
+    /// - `Graph(MissingEdge)` for a bond without its reverse
+    /// - `Bond` for a bond to its own node, one with zero or an odd count
+    ///   of electrons, or one whose reverse differs in electrons, parity
+    ///   or aromaticity
+    /// - `Isotope` for a mass number not among `Element::isotopes`
+    /// - `Valence` for an atom with a charge beyond ±15, or holding more
+    ///   electrons than its valence shell can expand to, from two in
+    ///   period 1 to forty-two in periods 6 and 7
+    /// - `Parity` for a parity or configuration on an atom with the wrong
+    ///   count of neighbors, or a parity on a bond that isn't double
+    pub fn try_new(nodes: Vec<Node>) -> Result<Self, Error> {
+        for (sid, node) in nodes.iter().enumerate() {
+            for (index, bond) in node.bonds.iter().enumerate() {
+                let tid = bond.tid;
+                let target = match nodes.get(tid) {
+                    Some(target) => target,
+                    None => {
+                        return Err(Error::Graph(GraphError::UnknownId(tid)))
+                    }
+                };
+
+                if tid == sid || bond.electrons == 0 || bond.electrons % 2 == 1
+                {
+                    return Err(Error::Bond(sid, tid));
+                } else if node.bonds[..index]
+                    .iter()
+                    .any(|other| other.tid == tid)
+                {
+                    return Err(Error::Graph(GraphError::DuplicateEdge(
+                        sid, tid,
+                    )));
+                }
+
+                match target.bonds.iter().find(|reverse| reverse.tid == sid) {
+                    Some(reverse) => {
+                        if reverse.electrons != bond.electrons
+                            || reverse.parity != bond.parity
+                            || reverse.aromatic != bond.aromatic
+                        {
+                            return Err(Error::Bond(sid, tid));
+                        }
+                    }
+                    None => {
+                        return Err(Error::Graph(GraphError::MissingEdge(
+                            tid, sid,
+                        )))
+                    }
+                }
+
+                if bond.parity.is_some() && bond.electrons != 4 {
+                    return Err(Error::Parity(sid));
+                }
+            }
+
+            validate_atom(sid, node)?;
+        }
+
+        Ok(Self::new(nodes))
+    }
+
     fn node_for(&self, id: usize) -> Result<&Node, GraphError> {
         match self.nodes.get(id) {
             Some(node) => Ok(node),
             None => Err(GraphError::UnknownId(id)),
         }
     }
+
+    fn node_for_mut(&mut self, id: usize) -> Result<&mut Node, GraphError> {
+        match self.nodes.get_mut(id) {
+            Some(node) => Ok(node),
+            None => Err(GraphError::UnknownId(id)),
+        }
+    }
+
+    fn bond_for_mut(
+        &mut self,
+        sid: usize,
+        tid: usize,
+    ) -> Result<&mut Bond, GraphError> {
+        self.node_for(tid)?;
+
+        match self
+            .node_for_mut(sid)?
+            .bonds
+            .iter_mut()
+            .find(|bond| bond.tid == tid)
+        {
+            Some(bond) => Ok(bond),
+            None => Err(GraphError::MissingEdge(sid, tid)),
+        }
+    }
+
+    // Returns the nonbonding electrons of the atom at id after adding
+    // delta, or Error if that leaves too few or too many. Atoms without an
+    // element carry no electrons.
+    fn shifted_electrons(&self, id: usize, delta: i16) -> Result<u8, Error> {
+        let atom = &self.node_for(id)?.atom;
+
+        if atom.element.is_none() {
+            return Ok(atom.electrons);
+        }
+
+        match u8::try_from(i16::from(atom.electrons) + delta) {
+            Ok(electrons) => Ok(electrons),
+            Err(_) => Err(Error::Valence(id)),
+        }
+    }
+
+    // Clears the parity and configuration of the atom at id, the parities
+    // of the double bonds it takes part in, and the configuration of any
+    // cumulene center reached through its double bonds.
+    fn clear_stereo(&mut self, id: usize) {
+        let node = &mut self.nodes[id];
+        let mut partners = Vec::new();
+
+        node.atom.parity = None;
+        node.atom.configuration = None;
+
+        for bond in node.bonds.iter_mut() {
+            if bond.parity.take().is_some() {
+                partners.push(bond.tid);
+            }
+        }
+
+        for partner in partners {
+            for bond in self.nodes[partner].bonds.iter_mut() {
+                if bond.tid == id {
+                    bond.parity = None;
+                }
+            }
+        }
+
+        self.clear_cumulene(id);
+    }
+
+    // Clears the configurations of the cumulated atoms reached from id
+    // through double bonds.
+    fn clear_cumulene(&mut self, id: usize) {
+        let mut cumulated = Vec::new();
+
+        for bond in self.nodes[id].bonds.iter() {
+            if bond.electrons != 4 {
+                continue;
+            }
+
+            let mut previous = id;
+            let mut current = bond.tid;
+
+            while current != id && is_cumulated(&self.nodes[current]) {
+                let next = self.nodes[current]
+                    .bonds
+                    .iter()
+                    .map(|bond| bond.tid)
+                    .find(|&tid| tid != previous)
+                    .expect("next");
+
+                cumulated.push(current);
+                previous = current;
+                current = next;
+            }
+        }
+
+        for id in cumulated {
+            self.nodes[id].atom.configuration = None;
+        }
+    }
+}
+
+fn is_cumulated(node: &Node) -> bool {
+    node.atom.hydrogens == 0
+        && node.bonds.len() == 2
+        && node.bonds.iter().all(|bond| bond.electrons == 4)
+}
+
+fn validate_atom(id: usize, node: &Node) -> Result<(), Error> {
+    let atom = &node.atom;
+    let hydrogens = usize::from(atom.hydrogens);
+    let neighbors = hydrogens + node.bonds.len();
+
+    if let Some(element) = &atom.element {
+        if let Some(isotope) = atom.isotope {
+            if !element.is_valid_mass_number(isotope) {
+                return Err(Error::Isotope(id));
+            }
+        }
+
+        let bonding = node
+            .bonds
+            .iter()
+            .fold(0, |sum, bond| sum + i32::from(bond.electrons / 2));
+        let charge = i32::from(element.valence_electrons())
+            - hydrogens as i32
+            - bonding
+            - i32::from(atom.electrons);
+        // The shell of the period's noble gas, as counted by
+        // valence_electrons, with room for hypervalent forms such as nitro
+        // written with two double bonds.
+        let capacity = match element.period() {
+            1 => 2,
+            2 => 12,
+            3 => 18,
+            4 | 5 => 28,
+            _ => 42,
+        };
+
+        if charge.abs() > 15
+            || 2 * (hydrogens as i32 + bonding) + i32::from(atom.electrons)
+                > capacity
+        {
+            return Err(Error::Valence(id));
+        }
+    }
+
+    if atom.parity.is_some() && (hydrogens > 1 || neighbors != 4) {
+        return Err(Error::Parity(id));
+    }
+
+    let valid = match &atom.configuration {
+        Some(Configuration::Allene(_)) => {
+            hydrogens == 0
+                && node.bonds.len() == 2
+                && node.bonds.iter().all(|bond| bond.electrons == 4)
+        }
+        Some(configuration) => {
+            hydrogens <= 1 && neighbors == configuration.neighbor_count()
+        }
+        None => true,
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::Parity(id))
+    }
 }
 
 impl Graph for DefaultMolecule {
@@ -109,6 +347,10 @@ impl Molecule for DefaultMolecule {
         Ok(result)
     }
 
+    fn is_aromatic(&self, id: usize) -> Result<bool, GraphError> {
+        Ok(self.node_for(id)?.aromatic)
+    }
+
     fn bond_order(&self, sid: usize, tid: usize) -> Result<f32, GraphError> {
         let source = self.node_for(sid)?;
 
@@ -121,6 +363,195 @@ impl Molecule for DefaultMolecule {
             None => Ok(0f32),
         }
     }
+
+    fn bond(
+        &self,
+        sid: usize,
+        tid: usize,
+    ) -> Result<Option<&Bond>, GraphError> {
+        let source = self.node_for(sid)?;
+
+        if tid >= self.nodes.len() {
+            return Err(GraphError::UnknownId(tid));
+        }
+
+        Ok(source.bonds.iter().find(|bond| bond.tid == tid))
+    }
+}
+
+impl MoleculeMut for DefaultMolecule {
+    fn add_atom(&mut self, atom: Atom) -> usize {
+        self.nodes.push(Node {
+            atom,
+            bonds: vec![],
+            aromatic: false,
+        });
+
+        self.nodes.len() - 1
+    }
+
+    fn remove_atom(&mut self, id: usize) -> Result<Atom, Error> {
+        let neighbors = self.neighbors(id)?.collect::<Vec<_>>();
+
+        for neighbor in neighbors {
+            self.remove_bond(id, neighbor)?;
+        }
+
+        let node = self.nodes.remove(id);
+
+        for node in self.nodes.iter_mut() {
+            for bond in node.bonds.iter_mut() {
+                if bond.tid > id {
+                    bond.tid -= 1;
+                }
+            }
+        }
+
+        Ok(node.atom)
+    }
+
+    fn add_bond(
+        &mut self,
+        sid: usize,
+        tid: usize,
+        electrons: u8,
+    ) -> Result<(), Error> {
+        if self.has_edge(sid, tid)? {
+            return Err(Error::Graph(GraphError::DuplicateEdge(sid, tid)));
+        } else if sid == tid || electrons == 0 || electrons % 2 == 1 {
+            return Err(Error::Bond(sid, tid));
+        }
+
+        let order = i16::from(electrons / 2);
+        let source = self.shifted_electrons(sid, -order)?;
+        let target = self.shifted_electrons(tid, -order)?;
+
+        self.clear_stereo(sid);
+        self.clear_stereo(tid);
+        self.nodes[sid].atom.electrons = source;
+        self.nodes[tid].atom.electrons = target;
+        self.nodes[sid].bonds.push(Bond::new(electrons, None, tid));
+        self.nodes[tid].bonds.push(Bond::new(electrons, None, sid));
+        self.size += 1;
+
+        Ok(())
+    }
+
+    fn remove_bond(&mut self, sid: usize, tid: usize) -> Result<(), Error> {
+        let order = match self.bond(sid, tid)? {
+            Some(bond) => i16::from(bond.electrons / 2),
+            None => {
+                return Err(Error::Graph(GraphError::MissingEdge(sid, tid)))
+            }
+        };
+        let source = self.shifted_electrons(sid, order)?;
+        let target = self.shifted_electrons(tid, order)?;
+
+        self.clear_stereo(sid);
+        self.clear_stereo(tid);
+        self.nodes[sid].atom.electrons = source;
+        self.nodes[tid].atom.electrons = target;
+        self.nodes[sid].bonds.retain(|bond| bond.tid != tid);
+        self.nodes[tid].bonds.retain(|bond| bond.tid != sid);
+        self.size -= 1;
+
+        Ok(())
+    }
+
+    fn set_bond_electrons(
+        &mut self,
+        sid: usize,
+        tid: usize,
+        electrons: u8,
+    ) -> Result<(), Error> {
+        let (delta, double) = match self.bond(sid, tid)? {
+            Some(bond) => (
+                i16::from(bond.electrons / 2) - i16::from(electrons / 2),
+                bond.electrons == 4,
+            ),
+            None => {
+                return Err(Error::Graph(GraphError::MissingEdge(sid, tid)))
+            }
+        };
+
+        if electrons == 0 || electrons % 2 == 1 {
+            return Err(Error::Bond(sid, tid));
+        }
+
+        let source = self.shifted_electrons(sid, delta)?;
+        let target = self.shifted_electrons(tid, delta)?;
+
+        if double && electrons != 4 {
+            for &id in [sid, tid].iter() {
+                if let Some(Configuration::Allene(_)) =
+                    self.nodes[id].atom.configuration
+                {
+                    self.nodes[id].atom.configuration = None;
+                }
+
+                self.clear_cumulene(id);
+            }
+        }
+
+        self.nodes[sid].atom.electrons = source;
+        self.nodes[tid].atom.electrons = target;
+
+        for &(source, target) in [(sid, tid), (tid, sid)].iter() {
+            let bond = self.bond_for_mut(source, target)?;
+
+            if electrons != 4 {
+                bond.parity = None;
+            }
+
+            bond.electrons = electrons;
+        }
+
+        Ok(())
+    }
+
+    fn set_charge(&mut self, id: usize, charge: i8) -> Result<(), Error> {
+        let node = self.node_for_mut(id)?;
+        let element = match &node.atom.element {
+            Some(element) => element,
+            None if charge == 0 => return Ok(()),
+            None => return Err(Error::Valence(id)),
+        };
+        let bonding = node
+            .bonds
+            .iter()
+            .fold(0, |sum, bond| sum + i16::from(bond.electrons / 2));
+        let electrons = i16::from(element.valence_electrons())
+            - i16::from(charge)
+            - bonding
+            - i16::from(node.atom.hydrogens);
+
+        match u8::try_from(electrons) {
+            Ok(electrons) => {
+                node.atom.electrons = electrons;
+
+                Ok(())
+            }
+            Err(_) => Err(Error::Valence(id)),
+        }
+    }
+
+    fn set_isotope(
+        &mut self,
+        id: usize,
+        isotope: Option<u16>,
+    ) -> Result<(), Error> {
+        let atom = &mut self.node_for_mut(id)?.atom;
+
+        if let (Some(element), Some(isotope)) = (&atom.element, isotope) {
+            if !element.is_valid_mass_number(isotope) {
+                return Err(Error::Isotope(id));
+            }
+        }
+
+        atom.isotope = isotope;
+
+        Ok(())
+    }
 }
 
 struct EdgeIterator<'a> {
@@ -182,6 +613,7 @@ mod is_empty {
         let molecule = DefaultMolecule::new(vec![Node {
             atom: Atom::default(),
             bonds: vec![],
+            aromatic: false,
         }]);
 
         assert_eq!(molecule.is_empty(), false)
@@ -206,6 +638,7 @@ mod order {
         let molecule = DefaultMolecule::new(vec![Node {
             atom: Atom::default(),
             bonds: vec![],
+            aromatic: false,
         }]);
 
         assert_eq!(molecule.order(), 1)
@@ -232,10 +665,12 @@ mod size {
             Node {
                 atom: Atom::default(),
                 bonds: vec![Bond::new(2, None, 1)],
+                aromatic: false,
             },
             Node {
                 atom: Atom::default(),
                 bonds: vec![Bond::new(2, None, 0)],
+                aromatic: false,
             },
         ]);
 
@@ -262,14 +697,17 @@ mod nodes {
             Node {
                 atom: Atom::default(),
                 bonds: vec![],
+                aromatic: false,
             },
             Node {
                 atom: Atom::default(),
                 bonds: vec![],
+                aromatic: false,
             },
             Node {
                 atom: Atom::default(),
                 bonds: vec![],
+                aromatic: false,
             },
         ]);
 
@@ -297,14 +735,17 @@ mod neighbors {
             Node {
                 atom: Atom::default(),
                 bonds: vec![Bond::new(2, None, 1), Bond::new(2, None, 2)],
+                aromatic: false,
             },
             Node {
                 atom: Atom::default(),
                 bonds: vec![Bond::new(2, None, 0)],
+                aromatic: false,
             },
             Node {
                 atom: Atom::default(),
                 bonds: vec![Bond::new(2, None, 0)],
+                aromatic: false,
             },
         ]);
 
@@ -330,6 +771,7 @@ mod has_node {
         let molecule = DefaultMolecule::new(vec![Node {
             atom: Atom::default(),
             bonds: vec![],
+            aromatic: false,
         }]);
 
         assert_eq!(molecule.has_id(0), true)
@@ -356,14 +798,17 @@ mod degree {
             Node {
                 atom: Atom::default(),
                 bonds: vec![Bond::new(2, None, 1), Bond::new(2, None, 2)],
+                aromatic: false,
             },
             Node {
                 atom: Atom::default(),
                 bonds: vec![Bond::new(2, None, 0)],
+                aromatic: false,
             },
             Node {
                 atom: Atom::default(),
                 bonds: vec![Bond::new(2, None, 0)],
+                aromatic: false,
             },
         ]);
 
@@ -383,6 +828,7 @@ mod edges {
         let molecule = DefaultMolecule::new(vec![Node {
             atom: Atom::default(),
             bonds: vec![],
+            aromatic: false,
         }]);
 
         assert_eq!(molecule.edges().collect::<Vec<_>>(), [])
@@ -398,18 +844,22 @@ mod edges {
                     Bond::new(2, None, 2),
                     Bond::new(2, None, 3),
                 ],
+                aromatic: false,
             },
             Node {
                 atom: Atom::default(),
                 bonds: vec![Bond::new(2, None, 0)],
+                aromatic: false,
             },
             Node {
                 atom: Atom::default(),
                 bonds: vec![Bond::new(2, None, 0)],
+                aromatic: false,
             },
             Node {
                 atom: Atom::default(),
                 bonds: vec![Bond::new(2, None, 0)],
+                aromatic: false,
             },
         ]);
 
@@ -432,6 +882,7 @@ mod has_edge {
         let molecule = DefaultMolecule::new(vec![Node {
             atom: Atom::default(),
             bonds: vec![],
+            aromatic: false,
         }]);
 
         assert_eq!(molecule.has_edge(1, 0), Err(GraphError::UnknownId(1)))
@@ -442,6 +893,7 @@ mod has_edge {
         let molecule = DefaultMolecule::new(vec![Node {
             atom: Atom::default(),
             bonds: vec![],
+            aromatic: false,
         }]);
 
         assert_eq!(molecule.has_edge(0, 1), Err(GraphError::UnknownId(1)))
@@ -453,10 +905,12 @@ mod has_edge {
             Node {
                 atom: Atom::default(),
                 bonds: vec![],
+                aromatic: false,
             },
             Node {
                 atom: Atom::default(),
                 bonds: vec![],
+                aromatic: false,
             },
         ]);
 
@@ -469,10 +923,12 @@ mod has_edge {
             Node {
                 atom: Atom::default(),
                 bonds: vec![Bond::new(2, None, 1)],
+                aromatic: false,
             },
             Node {
                 atom: Atom::default(),
                 bonds: vec![Bond::new(2, None, 0)],
+                aromatic: false,
             },
         ]);
 
@@ -503,8 +959,10 @@ mod atom {
                 hydrogens: 4,
                 electrons: 0,
                 parity: None,
+                configuration: None,
             },
             bonds: vec![],
+            aromatic: false,
         }]);
 
         assert_eq!(
@@ -515,6 +973,7 @@ mod atom {
                 hydrogens: 4,
                 electrons: 0,
                 parity: None,
+                configuration: None,
             })
         )
     }
@@ -544,8 +1003,10 @@ mod charge {
                     hydrogens: 3,
                     electrons: 0,
                     parity: None,
+                    configuration: None,
                 },
                 bonds: vec![Bond::new(2, None, 1)],
+                aromatic: false,
             },
             Node {
                 atom: Atom {
@@ -554,8 +1015,10 @@ mod charge {
                     hydrogens: 2,
                     electrons: 0,
                     parity: None,
+                    configuration: None,
                 },
                 bonds: vec![Bond::new(2, None, 0)],
+                aromatic: false,
             },
         ]);
 
@@ -572,8 +1035,10 @@ mod charge {
                     hydrogens: 3,
                     electrons: 0,
                     parity: None,
+                    configuration: None,
                 },
                 bonds: vec![Bond::new(2, None, 1)],
+                aromatic: false,
             },
             Node {
                 atom: Atom {
@@ -582,8 +1047,10 @@ mod charge {
                     hydrogens: 2,
                     electrons: 2,
                     parity: None,
+                    configuration: None,
                 },
                 bonds: vec![Bond::new(2, None, 0)],
+                aromatic: false,
             },
         ]);
 
@@ -603,6 +1070,7 @@ mod bond_order {
         let molecule = DefaultMolecule::new(vec![Node {
             atom: Atom::default(),
             bonds: vec![],
+            aromatic: false,
         }]);
 
         assert_eq!(molecule.bond_order(1, 0), Err(GraphError::UnknownId(1)))
@@ -613,6 +1081,7 @@ mod bond_order {
         let molecule = DefaultMolecule::new(vec![Node {
             atom: Atom::default(),
             bonds: vec![],
+            aromatic: false,
         }]);
 
         assert_eq!(molecule.bond_order(0, 1), Err(GraphError::UnknownId(1)))
@@ -624,10 +1093,12 @@ mod bond_order {
             Node {
                 atom: Atom::default(),
                 bonds: vec![],
+                aromatic: false,
             },
             Node {
                 atom: Atom::default(),
                 bonds: vec![],
+                aromatic: false,
             },
         ]);
 
@@ -644,8 +1115,10 @@ mod bond_order {
                     hydrogens: 3,
                     electrons: 0,
                     parity: None,
+                    configuration: None,
                 },
                 bonds: vec![Bond::new(2, None, 1)],
+                aromatic: false,
             },
             Node {
                 atom: Atom {
@@ -654,8 +1127,10 @@ mod bond_order {
                     hydrogens: 3,
                     electrons: 0,
                     parity: None,
+                    configuration: None,
                 },
                 bonds: vec![Bond::new(2, None, 0)],
+                aromatic: false,
             },
         ]);
 
@@ -672,8 +1147,10 @@ mod bond_order {
                     hydrogens: 3,
                     electrons: 0,
                     parity: None,
+                    configuration: None,
                 },
                 bonds: vec![Bond::new(1, None, 1)],
+                aromatic: false,
             },
             Node {
                 atom: Atom {
@@ -682,11 +1159,670 @@ mod bond_order {
                     hydrogens: 3,
                     electrons: 0,
                     parity: None,
+                    configuration: None,
                 },
                 bonds: vec![Bond::new(1, None, 0)],
+                aromatic: false,
             },
         ]);
 
         assert_eq!(molecule.bond_order(0, 1), Ok(0.5f32))
     }
 }
+
+#[cfg(test)]
+mod bond {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::molecule::Parity;
+
+    #[test]
+    fn unknown_sid() {
+        let molecule = DefaultMolecule::new(vec![Node {
+            atom: Atom::default(),
+            bonds: vec![],
+            aromatic: false,
+        }]);
+
+        assert_eq!(molecule.bond(1, 0), Err(GraphError::UnknownId(1)))
+    }
+
+    #[test]
+    fn unknown_tid() {
+        let molecule = DefaultMolecule::new(vec![Node {
+            atom: Atom::default(),
+            bonds: vec![],
+            aromatic: false,
+        }]);
+
+        assert_eq!(molecule.bond(0, 1), Err(GraphError::UnknownId(1)))
+    }
+
+    #[test]
+    fn no_bond() {
+        let molecule = DefaultMolecule::new(vec![
+            Node {
+                atom: Atom::default(),
+                bonds: vec![],
+                aromatic: false,
+            },
+            Node {
+                atom: Atom::default(),
+                bonds: vec![],
+                aromatic: false,
+            },
+        ]);
+
+        assert_eq!(molecule.bond(0, 1), Ok(None))
+    }
+
+    #[test]
+    fn double_with_parity() {
+        let molecule = DefaultMolecule::new(vec![
+            Node {
+                atom: Atom::default(),
+                bonds: vec![Bond::new(4, Some(Parity::Negative), 1)],
+                aromatic: false,
+            },
+            Node {
+                atom: Atom::default(),
+                bonds: vec![Bond::new(4, Some(Parity::Negative), 0)],
+                aromatic: false,
+            },
+        ]);
+
+        assert_eq!(
+            molecule.bond(1, 0),
+            Ok(Some(&Bond::new(4, Some(Parity::Negative), 0)))
+        )
+    }
+}
+
+#[cfg(test)]
+mod add_atom {
+    use pretty_assertions::assert_eq;
+
+    use super::super::Element;
+    use super::*;
+
+    #[test]
+    fn empty() {
+        let mut molecule = DefaultMolecule::new(vec![]);
+        let id = molecule.add_atom(Atom {
+            element: Some(Element::C),
+            hydrogens: 4,
+            ..Default::default()
+        });
+
+        assert_eq!(id, 0);
+        assert_eq!(molecule.order(), 1);
+        assert_eq!(molecule.charge(0), Ok(0.))
+    }
+}
+
+#[cfg(test)]
+mod remove_atom {
+    use pretty_assertions::assert_eq;
+
+    use super::super::Element;
+    use super::*;
+    use crate::daylight::{read_smiles, write_smiles};
+
+    #[test]
+    fn unknown_id() {
+        let mut molecule = DefaultMolecule::new(vec![]);
+
+        assert_eq!(
+            molecule.remove_atom(0),
+            Err(Error::Graph(GraphError::UnknownId(0)))
+        )
+    }
+
+    #[test]
+    fn middle() {
+        let mut molecule = read_smiles("CON", None).unwrap();
+        let atom = molecule.remove_atom(1).unwrap();
+
+        assert_eq!(atom.element, Some(Element::O));
+        assert_eq!(molecule.order(), 2);
+        assert_eq!(molecule.size(), 0);
+        assert_eq!(molecule.atom(1).unwrap().element, Some(Element::N))
+    }
+
+    #[test]
+    fn renumbers_bonds() {
+        let mut molecule = read_smiles("OCCN", None).unwrap();
+
+        molecule.remove_atom(0).unwrap();
+
+        assert_eq!(molecule.edges().collect::<Vec<_>>(), [(0, 1), (1, 2)]);
+        assert_eq!(molecule.neighbors(2).unwrap().collect::<Vec<_>>(), [1])
+    }
+
+    #[test]
+    fn clears_neighbor_parity() {
+        let mut molecule = read_smiles("C[C@H](O)CCl", None).unwrap();
+
+        molecule.remove_atom(2).unwrap();
+
+        assert_eq!(molecule.atom(1).unwrap().parity, None)
+    }
+
+    #[test]
+    fn clears_allene_configuration() {
+        let mut molecule = read_smiles("OC(Cl)=[C@AL1]=C(F)C", None).unwrap();
+
+        molecule.remove_atom(0).unwrap();
+
+        assert_eq!(molecule.atom(2).unwrap().configuration, None);
+        assert!(write_smiles(&molecule).is_ok())
+    }
+}
+
+#[cfg(test)]
+mod add_bond {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::read_smiles;
+
+    #[test]
+    fn unknown_id() {
+        let mut molecule = read_smiles("C", None).unwrap();
+
+        assert_eq!(
+            molecule.add_bond(0, 1, 2),
+            Err(Error::Graph(GraphError::UnknownId(1)))
+        )
+    }
+
+    #[test]
+    fn duplicate() {
+        let mut molecule = read_smiles("CC", None).unwrap();
+
+        assert_eq!(
+            molecule.add_bond(1, 0, 2),
+            Err(Error::Graph(GraphError::DuplicateEdge(1, 0)))
+        )
+    }
+
+    #[test]
+    fn no_electrons() {
+        let mut molecule = read_smiles("C.[CH3]", None).unwrap();
+
+        assert_eq!(molecule.add_bond(1, 0, 2), Err(Error::Valence(0)));
+        assert_eq!(molecule.size(), 0)
+    }
+
+    #[test]
+    fn self_loop() {
+        let mut molecule = read_smiles("C", None).unwrap();
+
+        assert_eq!(molecule.add_bond(0, 0, 2), Err(Error::Bond(0, 0)))
+    }
+
+    #[test]
+    fn odd_electrons() {
+        let mut molecule = read_smiles("[CH3].[CH3]", None).unwrap();
+
+        assert_eq!(molecule.add_bond(0, 1, 3), Err(Error::Bond(0, 1)));
+        assert_eq!(molecule.add_bond(0, 1, 0), Err(Error::Bond(0, 1)));
+        assert_eq!(molecule.size(), 0);
+        assert_eq!(molecule.charge(0), Ok(0.))
+    }
+
+    #[test]
+    fn disconnected() {
+        let mut molecule = read_smiles("[CH3].[CH3]", None).unwrap();
+
+        molecule.add_bond(0, 1, 2).unwrap();
+
+        assert_eq!(molecule.size(), 1);
+        assert_eq!(molecule.bond_order(0, 1), Ok(1.));
+        assert_eq!(molecule.bond_order(1, 0), Ok(1.));
+        assert_eq!(molecule.charge(0), Ok(0.))
+    }
+
+    #[test]
+    fn clears_double_bond_parity() {
+        let mut molecule = read_smiles("C/C=C/C.[CH3]", None).unwrap();
+
+        molecule.remove_bond(2, 3).unwrap();
+        molecule.add_bond(2, 4, 2).unwrap();
+
+        assert_eq!(molecule.bond(1, 2).unwrap().unwrap().parity, None);
+        assert_eq!(molecule.bond(2, 1).unwrap().unwrap().parity, None)
+    }
+}
+
+#[cfg(test)]
+mod remove_bond {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::{read_smiles, write_smiles};
+
+    #[test]
+    fn missing() {
+        let mut molecule = read_smiles("C.C", None).unwrap();
+
+        assert_eq!(
+            molecule.remove_bond(0, 1),
+            Err(Error::Graph(GraphError::MissingEdge(0, 1)))
+        )
+    }
+
+    #[test]
+    fn ring() {
+        let mut molecule = read_smiles("C1CC1", None).unwrap();
+
+        molecule.remove_bond(2, 0).unwrap();
+
+        assert_eq!(molecule.size(), 2);
+        assert_eq!(molecule.has_edge(0, 2), Ok(false));
+        assert_eq!(molecule.edges().collect::<Vec<_>>(), [(0, 1), (1, 2)]);
+        assert_eq!(molecule.charge(0), Ok(0.));
+        assert_eq!(molecule.atom(0).unwrap().electrons, 1)
+    }
+
+    #[test]
+    fn clears_allene_configuration() {
+        let mut molecule = read_smiles("OC(Cl)=[C@AL1]=C(F)C", None).unwrap();
+
+        molecule.remove_bond(0, 1).unwrap();
+
+        assert_eq!(molecule.atom(3).unwrap().configuration, None);
+        assert!(write_smiles(&molecule).is_ok())
+    }
+
+    #[test]
+    fn clears_cumulene_configuration() {
+        let mut molecule =
+            read_smiles("FC(Cl)=C=[C@AL1]=C=C(Br)I", None).unwrap();
+
+        molecule.remove_bond(6, 7).unwrap();
+
+        assert_eq!(molecule.atom(4).unwrap().configuration, None)
+    }
+}
+
+#[cfg(test)]
+mod set_bond_electrons {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::read_smiles;
+
+    #[test]
+    fn missing() {
+        let mut molecule = read_smiles("C.C", None).unwrap();
+
+        assert_eq!(
+            molecule.set_bond_electrons(0, 1, 4),
+            Err(Error::Graph(GraphError::MissingEdge(0, 1)))
+        )
+    }
+
+    #[test]
+    fn both_directions() {
+        let mut molecule = read_smiles("[CH2][CH2]", None).unwrap();
+
+        molecule.set_bond_electrons(1, 0, 4).unwrap();
+
+        assert_eq!(molecule.bond_order(0, 1), Ok(2.));
+        assert_eq!(molecule.bond_order(1, 0), Ok(2.));
+        assert_eq!(molecule.atom(0).unwrap().electrons, 0)
+    }
+
+    #[test]
+    fn no_electrons() {
+        let mut molecule = read_smiles("CC", None).unwrap();
+
+        assert_eq!(
+            molecule.set_bond_electrons(0, 1, 4),
+            Err(Error::Valence(0))
+        );
+        assert_eq!(molecule.bond_order(0, 1), Ok(1.))
+    }
+
+    #[test]
+    fn odd_electrons() {
+        let mut molecule = read_smiles("C=C", None).unwrap();
+
+        assert_eq!(
+            molecule.set_bond_electrons(0, 1, 3),
+            Err(Error::Bond(0, 1))
+        );
+        assert_eq!(
+            molecule.set_bond_electrons(0, 1, 0),
+            Err(Error::Bond(0, 1))
+        );
+        assert_eq!(molecule.bond_order(0, 1), Ok(2.))
+    }
+
+    #[test]
+    fn clears_parity() {
+        let mut molecule = read_smiles("C/C=C/C", None).unwrap();
+
+        molecule.set_bond_electrons(1, 2, 2).unwrap();
+
+        assert_eq!(molecule.bond(1, 2).unwrap().unwrap().parity, None);
+        assert_eq!(molecule.bond(2, 1).unwrap().unwrap().parity, None)
+    }
+
+    #[test]
+    fn clears_allene_configuration() {
+        let mut molecule = read_smiles("OC(Cl)=[C@AL1]=C(F)C", None).unwrap();
+
+        molecule.set_bond_electrons(3, 4, 2).unwrap();
+
+        assert_eq!(molecule.atom(3).unwrap().configuration, None)
+    }
+}
+
+#[cfg(test)]
+mod set_charge {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::read_smiles;
+
+    #[test]
+    fn unknown_id() {
+        let mut molecule = DefaultMolecule::new(vec![]);
+
+        assert_eq!(
+            molecule.set_charge(0, 1),
+            Err(Error::Graph(GraphError::UnknownId(0)))
+        )
+    }
+
+    #[test]
+    fn anion() {
+        let mut molecule = read_smiles("C[O]", None).unwrap();
+
+        molecule.set_charge(1, -1).unwrap();
+
+        assert_eq!(molecule.charge(1), Ok(-1.));
+        assert_eq!(molecule.atom(1).unwrap().electrons, 6)
+    }
+
+    #[test]
+    fn too_few_electrons() {
+        let mut molecule = read_smiles("C", None).unwrap();
+
+        assert_eq!(molecule.set_charge(0, 1), Err(Error::Valence(0)))
+    }
+}
+
+#[cfg(test)]
+mod set_isotope {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::read_smiles;
+
+    #[test]
+    fn deuterium() {
+        let mut molecule = read_smiles("[H]Cl", None).unwrap();
+
+        molecule.set_isotope(0, Some(2)).unwrap();
+
+        assert_eq!(molecule.atom(0).unwrap().isotope, Some(2))
+    }
+
+    #[test]
+    fn below_atomic_number() {
+        let mut molecule = read_smiles("C", None).unwrap();
+
+        assert_eq!(molecule.set_isotope(0, Some(5)), Err(Error::Isotope(0)))
+    }
+
+    #[test]
+    fn cleared() {
+        let mut molecule = read_smiles("[13CH4]", None).unwrap();
+
+        molecule.set_isotope(0, None).unwrap();
+
+        assert_eq!(molecule.atom(0).unwrap().isotope, None)
+    }
+}
+
+#[cfg(test)]
+mod try_new {
+    use pretty_assertions::assert_eq;
+
+    use super::super::{Element, Parity};
+    use super::*;
+    use crate::daylight::read_smiles;
+
+    fn carbon(hydrogens: u8, bonds: Vec<Bond>) -> Node {
+        Node {
+            atom: Atom {
+                element: Some(Element::C),
+                hydrogens,
+                ..Default::default()
+            },
+            bonds,
+            aromatic: false,
+        }
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            DefaultMolecule::try_new(vec![]),
+            Ok(DefaultMolecule::new(vec![]))
+        )
+    }
+
+    #[test]
+    fn ethane() {
+        let molecule = DefaultMolecule::try_new(vec![
+            carbon(3, vec![Bond::new(2, None, 1)]),
+            carbon(3, vec![Bond::new(2, None, 0)]),
+        ])
+        .unwrap();
+
+        assert_eq!(molecule.size(), 1)
+    }
+
+    #[test]
+    fn dangling() {
+        assert_eq!(
+            DefaultMolecule::try_new(vec![carbon(
+                3,
+                vec![Bond::new(2, None, 1)]
+            )]),
+            Err(Error::Graph(GraphError::UnknownId(1)))
+        )
+    }
+
+    #[test]
+    fn one_directional() {
+        assert_eq!(
+            DefaultMolecule::try_new(vec![
+                carbon(3, vec![Bond::new(2, None, 1)]),
+                carbon(3, vec![]),
+            ]),
+            Err(Error::Graph(GraphError::MissingEdge(1, 0)))
+        )
+    }
+
+    #[test]
+    fn self_loop() {
+        assert_eq!(
+            DefaultMolecule::try_new(vec![carbon(
+                2,
+                vec![Bond::new(2, None, 0), Bond::new(2, None, 0)]
+            )]),
+            Err(Error::Bond(0, 0))
+        )
+    }
+
+    #[test]
+    fn duplicate() {
+        assert_eq!(
+            DefaultMolecule::try_new(vec![
+                carbon(2, vec![Bond::new(2, None, 1), Bond::new(2, None, 1)]),
+                carbon(2, vec![Bond::new(2, None, 0), Bond::new(2, None, 0)]),
+            ]),
+            Err(Error::Graph(GraphError::DuplicateEdge(0, 1)))
+        )
+    }
+
+    #[test]
+    fn mismatched_electrons() {
+        assert_eq!(
+            DefaultMolecule::try_new(vec![
+                carbon(2, vec![Bond::new(4, None, 1)]),
+                carbon(3, vec![Bond::new(2, None, 0)]),
+            ]),
+            Err(Error::Bond(0, 1))
+        )
+    }
+
+    #[test]
+    fn mismatched_parity() {
+        assert_eq!(
+            DefaultMolecule::try_new(vec![
+                carbon(2, vec![Bond::new(4, Some(Parity::Positive), 1)]),
+                carbon(2, vec![Bond::new(4, None, 0)]),
+            ]),
+            Err(Error::Bond(0, 1))
+        )
+    }
+
+    #[test]
+    fn single_bond_parity() {
+        assert_eq!(
+            DefaultMolecule::try_new(vec![
+                carbon(3, vec![Bond::new(2, Some(Parity::Positive), 1)]),
+                carbon(3, vec![Bond::new(2, Some(Parity::Positive), 0)]),
+            ]),
+            Err(Error::Parity(0))
+        )
+    }
+
+    #[test]
+    fn isotope() {
+        let mut node = carbon(4, vec![]);
+
+        node.atom.isotope = Some(5);
+
+        assert_eq!(DefaultMolecule::try_new(vec![node]), Err(Error::Isotope(0)))
+    }
+
+    #[test]
+    fn excess_charge() {
+        let mut node = carbon(0, vec![]);
+
+        node.atom.electrons = 200;
+
+        assert_eq!(DefaultMolecule::try_new(vec![node]), Err(Error::Valence(0)))
+    }
+
+    #[test]
+    fn excess_hydrogens() {
+        assert_eq!(
+            DefaultMolecule::try_new(vec![carbon(9, vec![])]),
+            Err(Error::Valence(0))
+        )
+    }
+
+    #[test]
+    fn zero_electron_bond() {
+        assert_eq!(
+            DefaultMolecule::try_new(vec![
+                carbon(4, vec![Bond::new(0, None, 1)]),
+                carbon(4, vec![Bond::new(0, None, 0)]),
+            ]),
+            Err(Error::Bond(0, 1))
+        )
+    }
+
+    #[test]
+    fn odd_electron_bond() {
+        assert_eq!(
+            DefaultMolecule::try_new(vec![
+                carbon(3, vec![Bond::new(1, None, 1)]),
+                carbon(3, vec![Bond::new(1, None, 0)]),
+            ]),
+            Err(Error::Bond(0, 1))
+        )
+    }
+
+    #[test]
+    fn reader_output() {
+        let inputs = vec![
+            "CN(=O)=O",
+            "CS(=O)(=O)C",
+            "FP(F)(F)(F)F",
+            "F[Xe](F)(F)F",
+            "FI(F)(F)(F)(F)(F)F",
+            "[ReH9-2]",
+            "[CH5-]",
+            "[H-]",
+            "[Fe-4](C#N)(C#N)(C#N)(C#N)(C#N)C#N",
+            "C[C@H](N)O",
+            "F/C=C/F",
+        ];
+
+        for input in inputs {
+            let molecule = read_smiles(input, None).unwrap();
+
+            assert_eq!(
+                DefaultMolecule::try_new(molecule.nodes),
+                Ok(read_smiles(input, None).unwrap())
+            )
+        }
+    }
+
+    #[test]
+    fn hexavalent_sulfur() {
+        let node = Node {
+            atom: Atom {
+                element: Some(Element::S),
+                hydrogens: 6,
+                ..Default::default()
+            },
+            bonds: vec![],
+            aromatic: false,
+        };
+
+        assert!(DefaultMolecule::try_new(vec![node]).is_ok())
+    }
+
+    #[test]
+    fn wildcard() {
+        let node = Node {
+            atom: Atom {
+                hydrogens: 9,
+                ..Default::default()
+            },
+            bonds: vec![],
+            aromatic: false,
+        };
+
+        assert!(DefaultMolecule::try_new(vec![node]).is_ok())
+    }
+
+    #[test]
+    fn parity_without_neighbors() {
+        let mut node = carbon(4, vec![]);
+
+        node.atom.parity = Some(Parity::Positive);
+
+        assert_eq!(DefaultMolecule::try_new(vec![node]), Err(Error::Parity(0)))
+    }
+
+    #[test]
+    fn configuration_neighbor_count() {
+        let mut node = carbon(4, vec![]);
+
+        node.atom.configuration = Some(Configuration::Octahedral(1));
+
+        assert_eq!(DefaultMolecule::try_new(vec![node]), Err(Error::Parity(0)))
+    }
+}
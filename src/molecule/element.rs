@@ -1,7 +1,12 @@
+use std::fmt;
+use std::str::FromStr;
+
 use purr::parts;
 
+use super::{Block, Isotope};
+
 #[rustfmt::skip]
-#[derive(PartialEq,Debug,Clone)]
+#[derive(PartialEq,Eq,Hash,Debug,Clone)]
 pub enum Element {
 //  0   1   2   3   4   5   6   7   8   9
         H,  He, Li, Be, B,  C,  N,  O,  F,  //  0
@@ -18,9 +23,9 @@ pub enum Element {
     Ds, Rg, Cn, Nh, Fl, Mc, Lv, Ts, Og      // 11
 }
 
-impl Into<Element> for &parts::Aliphatic {
-    fn into(self) -> Element {
-        match self {
+impl From<&parts::Aliphatic> for Element {
+    fn from(kind: &parts::Aliphatic) -> Self {
+        match kind {
             parts::Aliphatic::B => Element::B,
             parts::Aliphatic::C => Element::C,
             parts::Aliphatic::N => Element::N,
@@ -37,9 +42,9 @@ impl Into<Element> for &parts::Aliphatic {
     }
 }
 
-impl Into<Element> for &parts::Aromatic {
-    fn into(self) -> Element {
-        match self {
+impl From<&parts::Aromatic> for Element {
+    fn from(kind: &parts::Aromatic) -> Self {
+        match kind {
             parts::Aromatic::B => Element::B,
             parts::Aromatic::C => Element::C,
             parts::Aromatic::N => Element::N,
@@ -50,9 +55,9 @@ impl Into<Element> for &parts::Aromatic {
     }
 }
 
-impl Into<Element> for &parts::BracketAromatic {
-    fn into(self) -> Element {
-        match self {
+impl From<&parts::BracketAromatic> for Element {
+    fn from(kind: &parts::BracketAromatic) -> Self {
+        match kind {
             parts::BracketAromatic::As => Element::As,
             parts::BracketAromatic::B => Element::B,
             parts::BracketAromatic::C => Element::C,
@@ -65,9 +70,9 @@ impl Into<Element> for &parts::BracketAromatic {
     }
 }
 
-impl Into<Element> for &parts::Element {
-    fn into(self) -> Element {
-        match self {
+impl From<&parts::Element> for Element {
+    fn from(kind: &parts::Element) -> Self {
+        match kind {
             parts::Element::H => Element::H,
             parts::Element::He => Element::He,
             parts::Element::Li => Element::Li,
@@ -190,6 +195,140 @@ impl Into<Element> for &parts::Element {
     }
 }
 
+impl FromStr for Element {
+    type Err = ();
+
+    fn from_str(symbol: &str) -> Result<Self, Self::Err> {
+        match symbol {
+            "H" => Ok(Element::H),
+            "He" => Ok(Element::He),
+            "Li" => Ok(Element::Li),
+            "Be" => Ok(Element::Be),
+            "B" => Ok(Element::B),
+            "C" => Ok(Element::C),
+            "N" => Ok(Element::N),
+            "O" => Ok(Element::O),
+            "F" => Ok(Element::F),
+            "Ne" => Ok(Element::Ne),
+            "Na" => Ok(Element::Na),
+            "Mg" => Ok(Element::Mg),
+            "Al" => Ok(Element::Al),
+            "Si" => Ok(Element::Si),
+            "P" => Ok(Element::P),
+            "S" => Ok(Element::S),
+            "Cl" => Ok(Element::Cl),
+            "Ar" => Ok(Element::Ar),
+            "K" => Ok(Element::K),
+            "Ca" => Ok(Element::Ca),
+            "Sc" => Ok(Element::Sc),
+            "Ti" => Ok(Element::Ti),
+            "V" => Ok(Element::V),
+            "Cr" => Ok(Element::Cr),
+            "Mn" => Ok(Element::Mn),
+            "Fe" => Ok(Element::Fe),
+            "Co" => Ok(Element::Co),
+            "Ni" => Ok(Element::Ni),
+            "Cu" => Ok(Element::Cu),
+            "Zn" => Ok(Element::Zn),
+            "Ga" => Ok(Element::Ga),
+            "Ge" => Ok(Element::Ge),
+            "As" => Ok(Element::As),
+            "Se" => Ok(Element::Se),
+            "Br" => Ok(Element::Br),
+            "Kr" => Ok(Element::Kr),
+            "Rb" => Ok(Element::Rb),
+            "Sr" => Ok(Element::Sr),
+            "Y" => Ok(Element::Y),
+            "Zr" => Ok(Element::Zr),
+            "Nb" => Ok(Element::Nb),
+            "Mo" => Ok(Element::Mo),
+            "Tc" => Ok(Element::Tc),
+            "Ru" => Ok(Element::Ru),
+            "Rh" => Ok(Element::Rh),
+            "Pd" => Ok(Element::Pd),
+            "Ag" => Ok(Element::Ag),
+            "Cd" => Ok(Element::Cd),
+            "In" => Ok(Element::In),
+            "Sn" => Ok(Element::Sn),
+            "Sb" => Ok(Element::Sb),
+            "Te" => Ok(Element::Te),
+            "I" => Ok(Element::I),
+            "Xe" => Ok(Element::Xe),
+            "Cs" => Ok(Element::Cs),
+            "Ba" => Ok(Element::Ba),
+            "La" => Ok(Element::La),
+            "Ce" => Ok(Element::Ce),
+            "Pr" => Ok(Element::Pr),
+            "Nd" => Ok(Element::Nd),
+            "Pm" => Ok(Element::Pm),
+            "Sm" => Ok(Element::Sm),
+            "Eu" => Ok(Element::Eu),
+            "Gd" => Ok(Element::Gd),
+            "Tb" => Ok(Element::Tb),
+            "Dy" => Ok(Element::Dy),
+            "Ho" => Ok(Element::Ho),
+            "Er" => Ok(Element::Er),
+            "Tm" => Ok(Element::Tm),
+            "Yb" => Ok(Element::Yb),
+            "Lu" => Ok(Element::Lu),
+            "Hf" => Ok(Element::Hf),
+            "Ta" => Ok(Element::Ta),
+            "W" => Ok(Element::W),
+            "Re" => Ok(Element::Re),
+            "Os" => Ok(Element::Os),
+            "Ir" => Ok(Element::Ir),
+            "Pt" => Ok(Element::Pt),
+            "Au" => Ok(Element::Au),
+            "Hg" => Ok(Element::Hg),
+            "Tl" => Ok(Element::Tl),
+            "Pb" => Ok(Element::Pb),
+            "Bi" => Ok(Element::Bi),
+            "Po" => Ok(Element::Po),
+            "At" => Ok(Element::At),
+            "Rn" => Ok(Element::Rn),
+            "Fr" => Ok(Element::Fr),
+            "Ra" => Ok(Element::Ra),
+            "Ac" => Ok(Element::Ac),
+            "Th" => Ok(Element::Th),
+            "Pa" => Ok(Element::Pa),
+            "U" => Ok(Element::U),
+            "Np" => Ok(Element::Np),
+            "Pu" => Ok(Element::Pu),
+            "Am" => Ok(Element::Am),
+            "Cm" => Ok(Element::Cm),
+            "Bk" => Ok(Element::Bk),
+            "Cf" => Ok(Element::Cf),
+            "Es" => Ok(Element::Es),
+            "Fm" => Ok(Element::Fm),
+            "Md" => Ok(Element::Md),
+            "No" => Ok(Element::No),
+            "Lr" => Ok(Element::Lr),
+            "Rf" => Ok(Element::Rf),
+            "Db" => Ok(Element::Db),
+            "Sg" => Ok(Element::Sg),
+            "Bh" => Ok(Element::Bh),
+            "Hs" => Ok(Element::Hs),
+            "Mt" => Ok(Element::Mt),
+            "Ds" => Ok(Element::Ds),
+            "Rg" => Ok(Element::Rg),
+            "Cn" => Ok(Element::Cn),
+            "Nh" => Ok(Element::Nh),
+            "Fl" => Ok(Element::Fl),
+            "Mc" => Ok(Element::Mc),
+            "Lv" => Ok(Element::Lv),
+            "Ts" => Ok(Element::Ts),
+            "Og" => Ok(Element::Og),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
 impl Element {
     pub fn valence_electrons(&self) -> u8 {
         let mut result = self.atomic_number();
@@ -198,7 +337,7 @@ impl Element {
             result -= core.atomic_number();
         }
 
-        result.into()
+        result
     }
 
     pub fn atomic_number(&self) -> u8 {
@@ -324,6 +463,2533 @@ impl Element {
         }
     }
 
+    pub fn symbol(&self) -> &str {
+        match self {
+            Element::H => "H",
+            Element::He => "He",
+            Element::Li => "Li",
+            Element::Be => "Be",
+            Element::B => "B",
+            Element::C => "C",
+            Element::N => "N",
+            Element::O => "O",
+            Element::F => "F",
+            Element::Ne => "Ne",
+            Element::Na => "Na",
+            Element::Mg => "Mg",
+            Element::Al => "Al",
+            Element::Si => "Si",
+            Element::P => "P",
+            Element::S => "S",
+            Element::Cl => "Cl",
+            Element::Ar => "Ar",
+            Element::K => "K",
+            Element::Ca => "Ca",
+            Element::Sc => "Sc",
+            Element::Ti => "Ti",
+            Element::V => "V",
+            Element::Cr => "Cr",
+            Element::Mn => "Mn",
+            Element::Fe => "Fe",
+            Element::Co => "Co",
+            Element::Ni => "Ni",
+            Element::Cu => "Cu",
+            Element::Zn => "Zn",
+            Element::Ga => "Ga",
+            Element::Ge => "Ge",
+            Element::As => "As",
+            Element::Se => "Se",
+            Element::Br => "Br",
+            Element::Kr => "Kr",
+            Element::Rb => "Rb",
+            Element::Sr => "Sr",
+            Element::Y => "Y",
+            Element::Zr => "Zr",
+            Element::Nb => "Nb",
+            Element::Mo => "Mo",
+            Element::Tc => "Tc",
+            Element::Ru => "Ru",
+            Element::Rh => "Rh",
+            Element::Pd => "Pd",
+            Element::Ag => "Ag",
+            Element::Cd => "Cd",
+            Element::In => "In",
+            Element::Sn => "Sn",
+            Element::Sb => "Sb",
+            Element::Te => "Te",
+            Element::I => "I",
+            Element::Xe => "Xe",
+            Element::Cs => "Cs",
+            Element::Ba => "Ba",
+            Element::La => "La",
+            Element::Ce => "Ce",
+            Element::Pr => "Pr",
+            Element::Nd => "Nd",
+            Element::Pm => "Pm",
+            Element::Sm => "Sm",
+            Element::Eu => "Eu",
+            Element::Gd => "Gd",
+            Element::Tb => "Tb",
+            Element::Dy => "Dy",
+            Element::Ho => "Ho",
+            Element::Er => "Er",
+            Element::Tm => "Tm",
+            Element::Yb => "Yb",
+            Element::Lu => "Lu",
+            Element::Hf => "Hf",
+            Element::Ta => "Ta",
+            Element::W => "W",
+            Element::Re => "Re",
+            Element::Os => "Os",
+            Element::Ir => "Ir",
+            Element::Pt => "Pt",
+            Element::Au => "Au",
+            Element::Hg => "Hg",
+            Element::Tl => "Tl",
+            Element::Pb => "Pb",
+            Element::Bi => "Bi",
+            Element::Po => "Po",
+            Element::At => "At",
+            Element::Rn => "Rn",
+            Element::Fr => "Fr",
+            Element::Ra => "Ra",
+            Element::Ac => "Ac",
+            Element::Th => "Th",
+            Element::Pa => "Pa",
+            Element::U => "U",
+            Element::Np => "Np",
+            Element::Pu => "Pu",
+            Element::Am => "Am",
+            Element::Cm => "Cm",
+            Element::Bk => "Bk",
+            Element::Cf => "Cf",
+            Element::Es => "Es",
+            Element::Fm => "Fm",
+            Element::Md => "Md",
+            Element::No => "No",
+            Element::Lr => "Lr",
+            Element::Rf => "Rf",
+            Element::Db => "Db",
+            Element::Sg => "Sg",
+            Element::Bh => "Bh",
+            Element::Hs => "Hs",
+            Element::Mt => "Mt",
+            Element::Ds => "Ds",
+            Element::Rg => "Rg",
+            Element::Cn => "Cn",
+            Element::Nh => "Nh",
+            Element::Fl => "Fl",
+            Element::Mc => "Mc",
+            Element::Lv => "Lv",
+            Element::Ts => "Ts",
+            Element::Og => "Og",
+        }
+    }
+
+    pub fn from_atomic_number(atomic_number: u8) -> Option<Self> {
+        match atomic_number {
+            1 => Some(Element::H),
+            2 => Some(Element::He),
+            3 => Some(Element::Li),
+            4 => Some(Element::Be),
+            5 => Some(Element::B),
+            6 => Some(Element::C),
+            7 => Some(Element::N),
+            8 => Some(Element::O),
+            9 => Some(Element::F),
+            10 => Some(Element::Ne),
+            11 => Some(Element::Na),
+            12 => Some(Element::Mg),
+            13 => Some(Element::Al),
+            14 => Some(Element::Si),
+            15 => Some(Element::P),
+            16 => Some(Element::S),
+            17 => Some(Element::Cl),
+            18 => Some(Element::Ar),
+            19 => Some(Element::K),
+            20 => Some(Element::Ca),
+            21 => Some(Element::Sc),
+            22 => Some(Element::Ti),
+            23 => Some(Element::V),
+            24 => Some(Element::Cr),
+            25 => Some(Element::Mn),
+            26 => Some(Element::Fe),
+            27 => Some(Element::Co),
+            28 => Some(Element::Ni),
+            29 => Some(Element::Cu),
+            30 => Some(Element::Zn),
+            31 => Some(Element::Ga),
+            32 => Some(Element::Ge),
+            33 => Some(Element::As),
+            34 => Some(Element::Se),
+            35 => Some(Element::Br),
+            36 => Some(Element::Kr),
+            37 => Some(Element::Rb),
+            38 => Some(Element::Sr),
+            39 => Some(Element::Y),
+            40 => Some(Element::Zr),
+            41 => Some(Element::Nb),
+            42 => Some(Element::Mo),
+            43 => Some(Element::Tc),
+            44 => Some(Element::Ru),
+            45 => Some(Element::Rh),
+            46 => Some(Element::Pd),
+            47 => Some(Element::Ag),
+            48 => Some(Element::Cd),
+            49 => Some(Element::In),
+            50 => Some(Element::Sn),
+            51 => Some(Element::Sb),
+            52 => Some(Element::Te),
+            53 => Some(Element::I),
+            54 => Some(Element::Xe),
+            55 => Some(Element::Cs),
+            56 => Some(Element::Ba),
+            57 => Some(Element::La),
+            58 => Some(Element::Ce),
+            59 => Some(Element::Pr),
+            60 => Some(Element::Nd),
+            61 => Some(Element::Pm),
+            62 => Some(Element::Sm),
+            63 => Some(Element::Eu),
+            64 => Some(Element::Gd),
+            65 => Some(Element::Tb),
+            66 => Some(Element::Dy),
+            67 => Some(Element::Ho),
+            68 => Some(Element::Er),
+            69 => Some(Element::Tm),
+            70 => Some(Element::Yb),
+            71 => Some(Element::Lu),
+            72 => Some(Element::Hf),
+            73 => Some(Element::Ta),
+            74 => Some(Element::W),
+            75 => Some(Element::Re),
+            76 => Some(Element::Os),
+            77 => Some(Element::Ir),
+            78 => Some(Element::Pt),
+            79 => Some(Element::Au),
+            80 => Some(Element::Hg),
+            81 => Some(Element::Tl),
+            82 => Some(Element::Pb),
+            83 => Some(Element::Bi),
+            84 => Some(Element::Po),
+            85 => Some(Element::At),
+            86 => Some(Element::Rn),
+            87 => Some(Element::Fr),
+            88 => Some(Element::Ra),
+            89 => Some(Element::Ac),
+            90 => Some(Element::Th),
+            91 => Some(Element::Pa),
+            92 => Some(Element::U),
+            93 => Some(Element::Np),
+            94 => Some(Element::Pu),
+            95 => Some(Element::Am),
+            96 => Some(Element::Cm),
+            97 => Some(Element::Bk),
+            98 => Some(Element::Cf),
+            99 => Some(Element::Es),
+            100 => Some(Element::Fm),
+            101 => Some(Element::Md),
+            102 => Some(Element::No),
+            103 => Some(Element::Lr),
+            104 => Some(Element::Rf),
+            105 => Some(Element::Db),
+            106 => Some(Element::Sg),
+            107 => Some(Element::Bh),
+            108 => Some(Element::Hs),
+            109 => Some(Element::Mt),
+            110 => Some(Element::Ds),
+            111 => Some(Element::Rg),
+            112 => Some(Element::Cn),
+            113 => Some(Element::Nh),
+            114 => Some(Element::Fl),
+            115 => Some(Element::Mc),
+            116 => Some(Element::Lv),
+            117 => Some(Element::Ts),
+            118 => Some(Element::Og),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Element::H => "Hydrogen",
+            Element::He => "Helium",
+            Element::Li => "Lithium",
+            Element::Be => "Beryllium",
+            Element::B => "Boron",
+            Element::C => "Carbon",
+            Element::N => "Nitrogen",
+            Element::O => "Oxygen",
+            Element::F => "Fluorine",
+            Element::Ne => "Neon",
+            Element::Na => "Sodium",
+            Element::Mg => "Magnesium",
+            Element::Al => "Aluminium",
+            Element::Si => "Silicon",
+            Element::P => "Phosphorus",
+            Element::S => "Sulfur",
+            Element::Cl => "Chlorine",
+            Element::Ar => "Argon",
+            Element::K => "Potassium",
+            Element::Ca => "Calcium",
+            Element::Sc => "Scandium",
+            Element::Ti => "Titanium",
+            Element::V => "Vanadium",
+            Element::Cr => "Chromium",
+            Element::Mn => "Manganese",
+            Element::Fe => "Iron",
+            Element::Co => "Cobalt",
+            Element::Ni => "Nickel",
+            Element::Cu => "Copper",
+            Element::Zn => "Zinc",
+            Element::Ga => "Gallium",
+            Element::Ge => "Germanium",
+            Element::As => "Arsenic",
+            Element::Se => "Selenium",
+            Element::Br => "Bromine",
+            Element::Kr => "Krypton",
+            Element::Rb => "Rubidium",
+            Element::Sr => "Strontium",
+            Element::Y => "Yttrium",
+            Element::Zr => "Zirconium",
+            Element::Nb => "Niobium",
+            Element::Mo => "Molybdenum",
+            Element::Tc => "Technetium",
+            Element::Ru => "Ruthenium",
+            Element::Rh => "Rhodium",
+            Element::Pd => "Palladium",
+            Element::Ag => "Silver",
+            Element::Cd => "Cadmium",
+            Element::In => "Indium",
+            Element::Sn => "Tin",
+            Element::Sb => "Antimony",
+            Element::Te => "Tellurium",
+            Element::I => "Iodine",
+            Element::Xe => "Xenon",
+            Element::Cs => "Caesium",
+            Element::Ba => "Barium",
+            Element::La => "Lanthanum",
+            Element::Ce => "Cerium",
+            Element::Pr => "Praseodymium",
+            Element::Nd => "Neodymium",
+            Element::Pm => "Promethium",
+            Element::Sm => "Samarium",
+            Element::Eu => "Europium",
+            Element::Gd => "Gadolinium",
+            Element::Tb => "Terbium",
+            Element::Dy => "Dysprosium",
+            Element::Ho => "Holmium",
+            Element::Er => "Erbium",
+            Element::Tm => "Thulium",
+            Element::Yb => "Ytterbium",
+            Element::Lu => "Lutetium",
+            Element::Hf => "Hafnium",
+            Element::Ta => "Tantalum",
+            Element::W => "Tungsten",
+            Element::Re => "Rhenium",
+            Element::Os => "Osmium",
+            Element::Ir => "Iridium",
+            Element::Pt => "Platinum",
+            Element::Au => "Gold",
+            Element::Hg => "Mercury",
+            Element::Tl => "Thallium",
+            Element::Pb => "Lead",
+            Element::Bi => "Bismuth",
+            Element::Po => "Polonium",
+            Element::At => "Astatine",
+            Element::Rn => "Radon",
+            Element::Fr => "Francium",
+            Element::Ra => "Radium",
+            Element::Ac => "Actinium",
+            Element::Th => "Thorium",
+            Element::Pa => "Protactinium",
+            Element::U => "Uranium",
+            Element::Np => "Neptunium",
+            Element::Pu => "Plutonium",
+            Element::Am => "Americium",
+            Element::Cm => "Curium",
+            Element::Bk => "Berkelium",
+            Element::Cf => "Californium",
+            Element::Es => "Einsteinium",
+            Element::Fm => "Fermium",
+            Element::Md => "Mendelevium",
+            Element::No => "Nobelium",
+            Element::Lr => "Lawrencium",
+            Element::Rf => "Rutherfordium",
+            Element::Db => "Dubnium",
+            Element::Sg => "Seaborgium",
+            Element::Bh => "Bohrium",
+            Element::Hs => "Hassium",
+            Element::Mt => "Meitnerium",
+            Element::Ds => "Darmstadtium",
+            Element::Rg => "Roentgenium",
+            Element::Cn => "Copernicium",
+            Element::Nh => "Nihonium",
+            Element::Fl => "Flerovium",
+            Element::Mc => "Moscovium",
+            Element::Lv => "Livermorium",
+            Element::Ts => "Tennessine",
+            Element::Og => "Oganesson",
+        }
+    }
+
+    pub fn period(&self) -> u8 {
+        match self.atomic_number() {
+            1..=2 => 1,
+            3..=10 => 2,
+            11..=18 => 3,
+            19..=36 => 4,
+            37..=54 => 5,
+            55..=86 => 6,
+            _ => 7,
+        }
+    }
+
+    /// Returns the IUPAC group (1-18), or None for the lanthanides Ce-Lu
+    /// and the actinides Th-Lr. La and Ac are placed in group 3.
+    pub fn group(&self) -> Option<u8> {
+        let offset = self.period_offset();
+
+        match self.block() {
+            Block::S => {
+                if self == &Element::He {
+                    Some(18)
+                } else {
+                    Some(offset + 1)
+                }
+            }
+            Block::P => Some(18 - (self.period_length() - 1 - offset)),
+            Block::D => {
+                if self.period() < 6 || offset == 2 {
+                    Some(offset + 1)
+                } else {
+                    Some(offset - 13)
+                }
+            }
+            Block::F => None,
+        }
+    }
+
+    pub fn block(&self) -> Block {
+        let offset = self.period_offset();
+
+        match self.period() {
+            1 => Block::S,
+            2 | 3 => match offset {
+                0..=1 => Block::S,
+                _ => Block::P,
+            },
+            4 | 5 => match offset {
+                0..=1 => Block::S,
+                2..=11 => Block::D,
+                _ => Block::P,
+            },
+            _ => match offset {
+                0..=1 => Block::S,
+                2 => Block::D,
+                3..=16 => Block::F,
+                17..=25 => Block::D,
+                _ => Block::P,
+            },
+        }
+    }
+
+    /// Returns the standard atomic weight. Elements without a stable
+    /// isotope or characteristic terrestrial composition return the mass
+    /// number of their longest-lived isotope.
+    pub fn atomic_weight(&self) -> f64 {
+        match self {
+            Element::H => 1.008,
+            Element::He => 4.002602,
+            Element::Li => 6.94,
+            Element::Be => 9.0121831,
+            Element::B => 10.81,
+            Element::C => 12.011,
+            Element::N => 14.007,
+            Element::O => 15.999,
+            Element::F => 18.998403163,
+            Element::Ne => 20.1797,
+            Element::Na => 22.98976928,
+            Element::Mg => 24.305,
+            Element::Al => 26.9815385,
+            Element::Si => 28.085,
+            Element::P => 30.973761998,
+            Element::S => 32.06,
+            Element::Cl => 35.45,
+            Element::Ar => 39.948,
+            Element::K => 39.0983,
+            Element::Ca => 40.078,
+            Element::Sc => 44.955908,
+            Element::Ti => 47.867,
+            Element::V => 50.9415,
+            Element::Cr => 51.9961,
+            Element::Mn => 54.938044,
+            Element::Fe => 55.845,
+            Element::Co => 58.933194,
+            Element::Ni => 58.6934,
+            Element::Cu => 63.546,
+            Element::Zn => 65.38,
+            Element::Ga => 69.723,
+            Element::Ge => 72.63,
+            Element::As => 74.921595,
+            Element::Se => 78.971,
+            Element::Br => 79.904,
+            Element::Kr => 83.798,
+            Element::Rb => 85.4678,
+            Element::Sr => 87.62,
+            Element::Y => 88.90584,
+            Element::Zr => 91.224,
+            Element::Nb => 92.90637,
+            Element::Mo => 95.95,
+            Element::Tc => 98.,
+            Element::Ru => 101.07,
+            Element::Rh => 102.9055,
+            Element::Pd => 106.42,
+            Element::Ag => 107.8682,
+            Element::Cd => 112.414,
+            Element::In => 114.818,
+            Element::Sn => 118.71,
+            Element::Sb => 121.76,
+            Element::Te => 127.6,
+            Element::I => 126.90447,
+            Element::Xe => 131.293,
+            Element::Cs => 132.90545196,
+            Element::Ba => 137.327,
+            Element::La => 138.90547,
+            Element::Ce => 140.116,
+            Element::Pr => 140.90766,
+            Element::Nd => 144.242,
+            Element::Pm => 145.,
+            Element::Sm => 150.36,
+            Element::Eu => 151.964,
+            Element::Gd => 157.25,
+            Element::Tb => 158.92535,
+            Element::Dy => 162.5,
+            Element::Ho => 164.93033,
+            Element::Er => 167.259,
+            Element::Tm => 168.93422,
+            Element::Yb => 173.045,
+            Element::Lu => 174.9668,
+            Element::Hf => 178.49,
+            Element::Ta => 180.94788,
+            Element::W => 183.84,
+            Element::Re => 186.207,
+            Element::Os => 190.23,
+            Element::Ir => 192.217,
+            Element::Pt => 195.084,
+            Element::Au => 196.966569,
+            Element::Hg => 200.592,
+            Element::Tl => 204.38,
+            Element::Pb => 207.2,
+            Element::Bi => 208.9804,
+            Element::Po => 209.,
+            Element::At => 210.,
+            Element::Rn => 222.,
+            Element::Fr => 223.,
+            Element::Ra => 226.,
+            Element::Ac => 227.,
+            Element::Th => 232.0377,
+            Element::Pa => 231.03588,
+            Element::U => 238.02891,
+            Element::Np => 237.,
+            Element::Pu => 244.,
+            Element::Am => 243.,
+            Element::Cm => 247.,
+            Element::Bk => 247.,
+            Element::Cf => 251.,
+            Element::Es => 252.,
+            Element::Fm => 257.,
+            Element::Md => 258.,
+            Element::No => 259.,
+            Element::Lr => 266.,
+            Element::Rf => 267.,
+            Element::Db => 268.,
+            Element::Sg => 269.,
+            Element::Bh => 270.,
+            Element::Hs => 269.,
+            Element::Mt => 278.,
+            Element::Ds => 281.,
+            Element::Rg => 282.,
+            Element::Cn => 285.,
+            Element::Nh => 286.,
+            Element::Fl => 289.,
+            Element::Mc => 290.,
+            Element::Lv => 293.,
+            Element::Ts => 294.,
+            Element::Og => 294.,
+        }
+    }
+
+    /// Returns the mass of the most abundant isotope. Elements with no
+    /// natural isotope return the mass of the isotope whose mass number is
+    /// reported by `atomic_weight`.
+    pub fn monoisotopic_mass(&self) -> f64 {
+        self.most_abundant_isotope().mass
+    }
+
+    /// Returns the most abundant isotope, or for elements with no natural
+    /// isotope, the one whose mass number is reported by `atomic_weight`.
+    pub fn most_abundant_isotope(&self) -> &'static Isotope {
+        let natural = self
+            .isotopes()
+            .iter()
+            .filter(|isotope| isotope.abundance > 0.)
+            .max_by(|a, b| a.abundance.partial_cmp(&b.abundance).unwrap());
+
+        match natural {
+            Some(isotope) => isotope,
+            None => self.isotope(self.atomic_weight() as u16).expect("isotope"),
+        }
+    }
+
+    /// Returns true if `mass_number` names one of the known isotopes
+    /// reported by `isotopes`.
+    pub fn is_valid_mass_number(&self, mass_number: u16) -> bool {
+        self.isotope(mass_number).is_some()
+    }
+
+    /// Returns the Isotope with `mass_number`, if known.
+    pub fn isotope(&self, mass_number: u16) -> Option<&'static Isotope> {
+        self.isotopes()
+            .iter()
+            .find(|isotope| isotope.mass_number == mass_number)
+    }
+
+    /// Returns the known isotopes in order of mass number. These are the
+    /// naturally-occurring isotopes together with the longest-lived and
+    /// commonly-labelled radioisotopes.
+    pub fn isotopes(&self) -> &'static [Isotope] {
+        match self {
+            Element::H => &[
+                Isotope {
+                    mass_number: 1,
+                    mass: 1.00782503223,
+                    abundance: 0.999885,
+                },
+                Isotope {
+                    mass_number: 2,
+                    mass: 2.01410177812,
+                    abundance: 0.000115,
+                },
+                Isotope {
+                    mass_number: 3,
+                    mass: 3.0160492779,
+                    abundance: 0.,
+                },
+            ],
+            Element::He => &[
+                Isotope {
+                    mass_number: 3,
+                    mass: 3.0160293201,
+                    abundance: 0.00000134,
+                },
+                Isotope {
+                    mass_number: 4,
+                    mass: 4.00260325413,
+                    abundance: 0.99999866,
+                },
+            ],
+            Element::Li => &[
+                Isotope {
+                    mass_number: 6,
+                    mass: 6.0151228874,
+                    abundance: 0.0759,
+                },
+                Isotope {
+                    mass_number: 7,
+                    mass: 7.0160034366,
+                    abundance: 0.9241,
+                },
+            ],
+            Element::Be => &[Isotope {
+                mass_number: 9,
+                mass: 9.012183065,
+                abundance: 1.,
+            }],
+            Element::B => &[
+                Isotope {
+                    mass_number: 10,
+                    mass: 10.01293695,
+                    abundance: 0.199,
+                },
+                Isotope {
+                    mass_number: 11,
+                    mass: 11.00930536,
+                    abundance: 0.801,
+                },
+            ],
+            Element::C => &[
+                Isotope {
+                    mass_number: 11,
+                    mass: 11.0114336,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 12,
+                    mass: 12.0,
+                    abundance: 0.9893,
+                },
+                Isotope {
+                    mass_number: 13,
+                    mass: 13.00335483507,
+                    abundance: 0.0107,
+                },
+                Isotope {
+                    mass_number: 14,
+                    mass: 14.0032419884,
+                    abundance: 0.,
+                },
+            ],
+            Element::N => &[
+                Isotope {
+                    mass_number: 13,
+                    mass: 13.00573861,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 14,
+                    mass: 14.00307400443,
+                    abundance: 0.99636,
+                },
+                Isotope {
+                    mass_number: 15,
+                    mass: 15.00010889888,
+                    abundance: 0.00364,
+                },
+            ],
+            Element::O => &[
+                Isotope {
+                    mass_number: 15,
+                    mass: 15.0030656,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 16,
+                    mass: 15.99491461957,
+                    abundance: 0.99757,
+                },
+                Isotope {
+                    mass_number: 17,
+                    mass: 16.9991317565,
+                    abundance: 0.00038,
+                },
+                Isotope {
+                    mass_number: 18,
+                    mass: 17.99915961286,
+                    abundance: 0.00205,
+                },
+            ],
+            Element::F => &[
+                Isotope {
+                    mass_number: 18,
+                    mass: 18.000938,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 19,
+                    mass: 18.99840316273,
+                    abundance: 1.,
+                },
+            ],
+            Element::Ne => &[
+                Isotope {
+                    mass_number: 20,
+                    mass: 19.9924401762,
+                    abundance: 0.9048,
+                },
+                Isotope {
+                    mass_number: 21,
+                    mass: 20.993846685,
+                    abundance: 0.0027,
+                },
+                Isotope {
+                    mass_number: 22,
+                    mass: 21.991385114,
+                    abundance: 0.0925,
+                },
+            ],
+            Element::Na => &[
+                Isotope {
+                    mass_number: 22,
+                    mass: 21.9944364,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 23,
+                    mass: 22.989769282,
+                    abundance: 1.,
+                },
+            ],
+            Element::Mg => &[
+                Isotope {
+                    mass_number: 24,
+                    mass: 23.985041697,
+                    abundance: 0.7899,
+                },
+                Isotope {
+                    mass_number: 25,
+                    mass: 24.985836976,
+                    abundance: 0.1,
+                },
+                Isotope {
+                    mass_number: 26,
+                    mass: 25.982592968,
+                    abundance: 0.1101,
+                },
+            ],
+            Element::Al => &[Isotope {
+                mass_number: 27,
+                mass: 26.98153853,
+                abundance: 1.,
+            }],
+            Element::Si => &[
+                Isotope {
+                    mass_number: 28,
+                    mass: 27.97692653465,
+                    abundance: 0.92223,
+                },
+                Isotope {
+                    mass_number: 29,
+                    mass: 28.9764946649,
+                    abundance: 0.04685,
+                },
+                Isotope {
+                    mass_number: 30,
+                    mass: 29.973770136,
+                    abundance: 0.03092,
+                },
+            ],
+            Element::P => &[
+                Isotope {
+                    mass_number: 31,
+                    mass: 30.97376199842,
+                    abundance: 1.,
+                },
+                Isotope {
+                    mass_number: 32,
+                    mass: 31.9739076,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 33,
+                    mass: 32.9717257,
+                    abundance: 0.,
+                },
+            ],
+            Element::S => &[
+                Isotope {
+                    mass_number: 32,
+                    mass: 31.9720711744,
+                    abundance: 0.9499,
+                },
+                Isotope {
+                    mass_number: 33,
+                    mass: 32.9714589098,
+                    abundance: 0.0075,
+                },
+                Isotope {
+                    mass_number: 34,
+                    mass: 33.967867004,
+                    abundance: 0.0425,
+                },
+                Isotope {
+                    mass_number: 35,
+                    mass: 34.96903231,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 36,
+                    mass: 35.96708071,
+                    abundance: 0.0001,
+                },
+            ],
+            Element::Cl => &[
+                Isotope {
+                    mass_number: 35,
+                    mass: 34.968852682,
+                    abundance: 0.7576,
+                },
+                Isotope {
+                    mass_number: 36,
+                    mass: 35.968306809,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 37,
+                    mass: 36.965902602,
+                    abundance: 0.2424,
+                },
+            ],
+            Element::Ar => &[
+                Isotope {
+                    mass_number: 36,
+                    mass: 35.967545105,
+                    abundance: 0.003336,
+                },
+                Isotope {
+                    mass_number: 38,
+                    mass: 37.96273211,
+                    abundance: 0.000629,
+                },
+                Isotope {
+                    mass_number: 40,
+                    mass: 39.9623831237,
+                    abundance: 0.996035,
+                },
+            ],
+            Element::K => &[
+                Isotope {
+                    mass_number: 39,
+                    mass: 38.9637064864,
+                    abundance: 0.932581,
+                },
+                Isotope {
+                    mass_number: 40,
+                    mass: 39.963998166,
+                    abundance: 0.000117,
+                },
+                Isotope {
+                    mass_number: 41,
+                    mass: 40.9618252579,
+                    abundance: 0.067302,
+                },
+            ],
+            Element::Ca => &[
+                Isotope {
+                    mass_number: 40,
+                    mass: 39.962590863,
+                    abundance: 0.96941,
+                },
+                Isotope {
+                    mass_number: 42,
+                    mass: 41.95861783,
+                    abundance: 0.00647,
+                },
+                Isotope {
+                    mass_number: 43,
+                    mass: 42.95876644,
+                    abundance: 0.00135,
+                },
+                Isotope {
+                    mass_number: 44,
+                    mass: 43.95548156,
+                    abundance: 0.02086,
+                },
+                Isotope {
+                    mass_number: 46,
+                    mass: 45.953689,
+                    abundance: 0.00004,
+                },
+                Isotope {
+                    mass_number: 48,
+                    mass: 47.95252276,
+                    abundance: 0.00187,
+                },
+            ],
+            Element::Sc => &[Isotope {
+                mass_number: 45,
+                mass: 44.95590828,
+                abundance: 1.,
+            }],
+            Element::Ti => &[
+                Isotope {
+                    mass_number: 46,
+                    mass: 45.95262772,
+                    abundance: 0.0825,
+                },
+                Isotope {
+                    mass_number: 47,
+                    mass: 46.95175879,
+                    abundance: 0.0744,
+                },
+                Isotope {
+                    mass_number: 48,
+                    mass: 47.94794198,
+                    abundance: 0.7372,
+                },
+                Isotope {
+                    mass_number: 49,
+                    mass: 48.94786568,
+                    abundance: 0.0541,
+                },
+                Isotope {
+                    mass_number: 50,
+                    mass: 49.94478689,
+                    abundance: 0.0518,
+                },
+            ],
+            Element::V => &[
+                Isotope {
+                    mass_number: 50,
+                    mass: 49.94715601,
+                    abundance: 0.0025,
+                },
+                Isotope {
+                    mass_number: 51,
+                    mass: 50.94395704,
+                    abundance: 0.9975,
+                },
+            ],
+            Element::Cr => &[
+                Isotope {
+                    mass_number: 50,
+                    mass: 49.94604183,
+                    abundance: 0.04345,
+                },
+                Isotope {
+                    mass_number: 52,
+                    mass: 51.94050623,
+                    abundance: 0.83789,
+                },
+                Isotope {
+                    mass_number: 53,
+                    mass: 52.94064815,
+                    abundance: 0.09501,
+                },
+                Isotope {
+                    mass_number: 54,
+                    mass: 53.93887916,
+                    abundance: 0.02365,
+                },
+            ],
+            Element::Mn => &[Isotope {
+                mass_number: 55,
+                mass: 54.93804391,
+                abundance: 1.,
+            }],
+            Element::Fe => &[
+                Isotope {
+                    mass_number: 54,
+                    mass: 53.93960899,
+                    abundance: 0.05845,
+                },
+                Isotope {
+                    mass_number: 56,
+                    mass: 55.93493633,
+                    abundance: 0.91754,
+                },
+                Isotope {
+                    mass_number: 57,
+                    mass: 56.93539284,
+                    abundance: 0.02119,
+                },
+                Isotope {
+                    mass_number: 58,
+                    mass: 57.93327443,
+                    abundance: 0.00282,
+                },
+            ],
+            Element::Co => &[
+                Isotope {
+                    mass_number: 59,
+                    mass: 58.93319429,
+                    abundance: 1.,
+                },
+                Isotope {
+                    mass_number: 60,
+                    mass: 59.9338171,
+                    abundance: 0.,
+                },
+            ],
+            Element::Ni => &[
+                Isotope {
+                    mass_number: 58,
+                    mass: 57.93534241,
+                    abundance: 0.68077,
+                },
+                Isotope {
+                    mass_number: 60,
+                    mass: 59.93078588,
+                    abundance: 0.26223,
+                },
+                Isotope {
+                    mass_number: 61,
+                    mass: 60.93105557,
+                    abundance: 0.011399,
+                },
+                Isotope {
+                    mass_number: 62,
+                    mass: 61.92834537,
+                    abundance: 0.036346,
+                },
+                Isotope {
+                    mass_number: 64,
+                    mass: 63.92796682,
+                    abundance: 0.009255,
+                },
+            ],
+            Element::Cu => &[
+                Isotope {
+                    mass_number: 63,
+                    mass: 62.92959772,
+                    abundance: 0.6915,
+                },
+                Isotope {
+                    mass_number: 65,
+                    mass: 64.9277897,
+                    abundance: 0.3085,
+                },
+            ],
+            Element::Zn => &[
+                Isotope {
+                    mass_number: 64,
+                    mass: 63.92914201,
+                    abundance: 0.4917,
+                },
+                Isotope {
+                    mass_number: 66,
+                    mass: 65.92603381,
+                    abundance: 0.2773,
+                },
+                Isotope {
+                    mass_number: 67,
+                    mass: 66.92712775,
+                    abundance: 0.0404,
+                },
+                Isotope {
+                    mass_number: 68,
+                    mass: 67.92484455,
+                    abundance: 0.1845,
+                },
+                Isotope {
+                    mass_number: 70,
+                    mass: 69.9253192,
+                    abundance: 0.0061,
+                },
+            ],
+            Element::Ga => &[
+                Isotope {
+                    mass_number: 69,
+                    mass: 68.9255735,
+                    abundance: 0.60108,
+                },
+                Isotope {
+                    mass_number: 71,
+                    mass: 70.92470258,
+                    abundance: 0.39892,
+                },
+            ],
+            Element::Ge => &[
+                Isotope {
+                    mass_number: 70,
+                    mass: 69.92424875,
+                    abundance: 0.2057,
+                },
+                Isotope {
+                    mass_number: 72,
+                    mass: 71.922075826,
+                    abundance: 0.2745,
+                },
+                Isotope {
+                    mass_number: 73,
+                    mass: 72.923458956,
+                    abundance: 0.0775,
+                },
+                Isotope {
+                    mass_number: 74,
+                    mass: 73.921177761,
+                    abundance: 0.365,
+                },
+                Isotope {
+                    mass_number: 76,
+                    mass: 75.921402726,
+                    abundance: 0.0773,
+                },
+            ],
+            Element::As => &[Isotope {
+                mass_number: 75,
+                mass: 74.92159457,
+                abundance: 1.,
+            }],
+            Element::Se => &[
+                Isotope {
+                    mass_number: 74,
+                    mass: 73.922475934,
+                    abundance: 0.0086,
+                },
+                Isotope {
+                    mass_number: 76,
+                    mass: 75.919213704,
+                    abundance: 0.0923,
+                },
+                Isotope {
+                    mass_number: 77,
+                    mass: 76.919914154,
+                    abundance: 0.076,
+                },
+                Isotope {
+                    mass_number: 78,
+                    mass: 77.91730928,
+                    abundance: 0.2369,
+                },
+                Isotope {
+                    mass_number: 80,
+                    mass: 79.9165218,
+                    abundance: 0.498,
+                },
+                Isotope {
+                    mass_number: 82,
+                    mass: 81.9166995,
+                    abundance: 0.0882,
+                },
+            ],
+            Element::Br => &[
+                Isotope {
+                    mass_number: 79,
+                    mass: 78.9183376,
+                    abundance: 0.5069,
+                },
+                Isotope {
+                    mass_number: 81,
+                    mass: 80.9162897,
+                    abundance: 0.4931,
+                },
+            ],
+            Element::Kr => &[
+                Isotope {
+                    mass_number: 78,
+                    mass: 77.92036494,
+                    abundance: 0.00355,
+                },
+                Isotope {
+                    mass_number: 80,
+                    mass: 79.91637808,
+                    abundance: 0.02286,
+                },
+                Isotope {
+                    mass_number: 82,
+                    mass: 81.91348273,
+                    abundance: 0.11593,
+                },
+                Isotope {
+                    mass_number: 83,
+                    mass: 82.91412716,
+                    abundance: 0.115,
+                },
+                Isotope {
+                    mass_number: 84,
+                    mass: 83.9114977282,
+                    abundance: 0.56987,
+                },
+                Isotope {
+                    mass_number: 86,
+                    mass: 85.9106106269,
+                    abundance: 0.17279,
+                },
+            ],
+            Element::Rb => &[
+                Isotope {
+                    mass_number: 85,
+                    mass: 84.9117897379,
+                    abundance: 0.7217,
+                },
+                Isotope {
+                    mass_number: 87,
+                    mass: 86.909180531,
+                    abundance: 0.2783,
+                },
+            ],
+            Element::Sr => &[
+                Isotope {
+                    mass_number: 84,
+                    mass: 83.9134191,
+                    abundance: 0.0056,
+                },
+                Isotope {
+                    mass_number: 86,
+                    mass: 85.9092606,
+                    abundance: 0.0986,
+                },
+                Isotope {
+                    mass_number: 87,
+                    mass: 86.9088775,
+                    abundance: 0.07,
+                },
+                Isotope {
+                    mass_number: 88,
+                    mass: 87.9056125,
+                    abundance: 0.8258,
+                },
+            ],
+            Element::Y => &[Isotope {
+                mass_number: 89,
+                mass: 88.9058403,
+                abundance: 1.,
+            }],
+            Element::Zr => &[
+                Isotope {
+                    mass_number: 90,
+                    mass: 89.9046977,
+                    abundance: 0.5145,
+                },
+                Isotope {
+                    mass_number: 91,
+                    mass: 90.9056396,
+                    abundance: 0.1122,
+                },
+                Isotope {
+                    mass_number: 92,
+                    mass: 91.9050347,
+                    abundance: 0.1715,
+                },
+                Isotope {
+                    mass_number: 94,
+                    mass: 93.9063108,
+                    abundance: 0.1738,
+                },
+                Isotope {
+                    mass_number: 96,
+                    mass: 95.9082714,
+                    abundance: 0.028,
+                },
+            ],
+            Element::Nb => &[Isotope {
+                mass_number: 93,
+                mass: 92.906373,
+                abundance: 1.,
+            }],
+            Element::Mo => &[
+                Isotope {
+                    mass_number: 92,
+                    mass: 91.90680796,
+                    abundance: 0.1453,
+                },
+                Isotope {
+                    mass_number: 94,
+                    mass: 93.9050849,
+                    abundance: 0.0915,
+                },
+                Isotope {
+                    mass_number: 95,
+                    mass: 94.90583877,
+                    abundance: 0.1584,
+                },
+                Isotope {
+                    mass_number: 96,
+                    mass: 95.90467612,
+                    abundance: 0.1667,
+                },
+                Isotope {
+                    mass_number: 97,
+                    mass: 96.90601812,
+                    abundance: 0.096,
+                },
+                Isotope {
+                    mass_number: 98,
+                    mass: 97.90540482,
+                    abundance: 0.2439,
+                },
+                Isotope {
+                    mass_number: 100,
+                    mass: 99.9074718,
+                    abundance: 0.0982,
+                },
+            ],
+            Element::Tc => &[
+                Isotope {
+                    mass_number: 97,
+                    mass: 96.9063667,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 98,
+                    mass: 97.9072124,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 99,
+                    mass: 98.9062508,
+                    abundance: 0.,
+                },
+            ],
+            Element::Ru => &[
+                Isotope {
+                    mass_number: 96,
+                    mass: 95.90759025,
+                    abundance: 0.0554,
+                },
+                Isotope {
+                    mass_number: 98,
+                    mass: 97.9052868,
+                    abundance: 0.0187,
+                },
+                Isotope {
+                    mass_number: 99,
+                    mass: 98.9059341,
+                    abundance: 0.1276,
+                },
+                Isotope {
+                    mass_number: 100,
+                    mass: 99.9042143,
+                    abundance: 0.126,
+                },
+                Isotope {
+                    mass_number: 101,
+                    mass: 100.9055769,
+                    abundance: 0.1706,
+                },
+                Isotope {
+                    mass_number: 102,
+                    mass: 101.9043441,
+                    abundance: 0.3155,
+                },
+                Isotope {
+                    mass_number: 104,
+                    mass: 103.9054275,
+                    abundance: 0.1862,
+                },
+            ],
+            Element::Rh => &[Isotope {
+                mass_number: 103,
+                mass: 102.905498,
+                abundance: 1.,
+            }],
+            Element::Pd => &[
+                Isotope {
+                    mass_number: 102,
+                    mass: 101.9056022,
+                    abundance: 0.0102,
+                },
+                Isotope {
+                    mass_number: 104,
+                    mass: 103.9040305,
+                    abundance: 0.1114,
+                },
+                Isotope {
+                    mass_number: 105,
+                    mass: 104.9050796,
+                    abundance: 0.2233,
+                },
+                Isotope {
+                    mass_number: 106,
+                    mass: 105.9034804,
+                    abundance: 0.2733,
+                },
+                Isotope {
+                    mass_number: 108,
+                    mass: 107.9038916,
+                    abundance: 0.2646,
+                },
+                Isotope {
+                    mass_number: 110,
+                    mass: 109.9051722,
+                    abundance: 0.1172,
+                },
+            ],
+            Element::Ag => &[
+                Isotope {
+                    mass_number: 107,
+                    mass: 106.9050916,
+                    abundance: 0.51839,
+                },
+                Isotope {
+                    mass_number: 109,
+                    mass: 108.9047553,
+                    abundance: 0.48161,
+                },
+            ],
+            Element::Cd => &[
+                Isotope {
+                    mass_number: 106,
+                    mass: 105.9064599,
+                    abundance: 0.0125,
+                },
+                Isotope {
+                    mass_number: 108,
+                    mass: 107.9041834,
+                    abundance: 0.0089,
+                },
+                Isotope {
+                    mass_number: 110,
+                    mass: 109.90300661,
+                    abundance: 0.1249,
+                },
+                Isotope {
+                    mass_number: 111,
+                    mass: 110.90418287,
+                    abundance: 0.128,
+                },
+                Isotope {
+                    mass_number: 112,
+                    mass: 111.90276287,
+                    abundance: 0.2413,
+                },
+                Isotope {
+                    mass_number: 113,
+                    mass: 112.90440813,
+                    abundance: 0.1222,
+                },
+                Isotope {
+                    mass_number: 114,
+                    mass: 113.90336509,
+                    abundance: 0.2873,
+                },
+                Isotope {
+                    mass_number: 116,
+                    mass: 115.90476315,
+                    abundance: 0.0749,
+                },
+            ],
+            Element::In => &[
+                Isotope {
+                    mass_number: 113,
+                    mass: 112.90406184,
+                    abundance: 0.0429,
+                },
+                Isotope {
+                    mass_number: 115,
+                    mass: 114.903878776,
+                    abundance: 0.9571,
+                },
+            ],
+            Element::Sn => &[
+                Isotope {
+                    mass_number: 112,
+                    mass: 111.90482387,
+                    abundance: 0.0097,
+                },
+                Isotope {
+                    mass_number: 114,
+                    mass: 113.9027827,
+                    abundance: 0.0066,
+                },
+                Isotope {
+                    mass_number: 115,
+                    mass: 114.903344699,
+                    abundance: 0.0034,
+                },
+                Isotope {
+                    mass_number: 116,
+                    mass: 115.9017428,
+                    abundance: 0.1454,
+                },
+                Isotope {
+                    mass_number: 117,
+                    mass: 116.90295398,
+                    abundance: 0.0768,
+                },
+                Isotope {
+                    mass_number: 118,
+                    mass: 117.90160657,
+                    abundance: 0.2422,
+                },
+                Isotope {
+                    mass_number: 119,
+                    mass: 118.90331117,
+                    abundance: 0.0859,
+                },
+                Isotope {
+                    mass_number: 120,
+                    mass: 119.90220163,
+                    abundance: 0.3258,
+                },
+                Isotope {
+                    mass_number: 122,
+                    mass: 121.9034438,
+                    abundance: 0.0463,
+                },
+                Isotope {
+                    mass_number: 124,
+                    mass: 123.9052766,
+                    abundance: 0.0579,
+                },
+            ],
+            Element::Sb => &[
+                Isotope {
+                    mass_number: 121,
+                    mass: 120.903812,
+                    abundance: 0.5721,
+                },
+                Isotope {
+                    mass_number: 123,
+                    mass: 122.9042132,
+                    abundance: 0.4279,
+                },
+            ],
+            Element::Te => &[
+                Isotope {
+                    mass_number: 120,
+                    mass: 119.9040593,
+                    abundance: 0.0009,
+                },
+                Isotope {
+                    mass_number: 122,
+                    mass: 121.9030435,
+                    abundance: 0.0255,
+                },
+                Isotope {
+                    mass_number: 123,
+                    mass: 122.9042698,
+                    abundance: 0.0089,
+                },
+                Isotope {
+                    mass_number: 124,
+                    mass: 123.9028171,
+                    abundance: 0.0474,
+                },
+                Isotope {
+                    mass_number: 125,
+                    mass: 124.9044299,
+                    abundance: 0.0707,
+                },
+                Isotope {
+                    mass_number: 126,
+                    mass: 125.9033109,
+                    abundance: 0.1884,
+                },
+                Isotope {
+                    mass_number: 128,
+                    mass: 127.90446128,
+                    abundance: 0.3174,
+                },
+                Isotope {
+                    mass_number: 130,
+                    mass: 129.906222748,
+                    abundance: 0.3408,
+                },
+            ],
+            Element::I => &[
+                Isotope {
+                    mass_number: 123,
+                    mass: 122.905589,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 125,
+                    mass: 124.9046294,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 127,
+                    mass: 126.9044719,
+                    abundance: 1.,
+                },
+                Isotope {
+                    mass_number: 129,
+                    mass: 128.9049837,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 131,
+                    mass: 130.9061263,
+                    abundance: 0.,
+                },
+            ],
+            Element::Xe => &[
+                Isotope {
+                    mass_number: 124,
+                    mass: 123.905892,
+                    abundance: 0.000952,
+                },
+                Isotope {
+                    mass_number: 126,
+                    mass: 125.9042983,
+                    abundance: 0.00089,
+                },
+                Isotope {
+                    mass_number: 128,
+                    mass: 127.903531,
+                    abundance: 0.019102,
+                },
+                Isotope {
+                    mass_number: 129,
+                    mass: 128.9047808611,
+                    abundance: 0.264006,
+                },
+                Isotope {
+                    mass_number: 130,
+                    mass: 129.903509349,
+                    abundance: 0.04071,
+                },
+                Isotope {
+                    mass_number: 131,
+                    mass: 130.90508406,
+                    abundance: 0.212324,
+                },
+                Isotope {
+                    mass_number: 132,
+                    mass: 131.9041550856,
+                    abundance: 0.269086,
+                },
+                Isotope {
+                    mass_number: 134,
+                    mass: 133.90539466,
+                    abundance: 0.104357,
+                },
+                Isotope {
+                    mass_number: 136,
+                    mass: 135.907214484,
+                    abundance: 0.088573,
+                },
+            ],
+            Element::Cs => &[
+                Isotope {
+                    mass_number: 133,
+                    mass: 132.905451961,
+                    abundance: 1.,
+                },
+                Isotope {
+                    mass_number: 137,
+                    mass: 136.9070895,
+                    abundance: 0.,
+                },
+            ],
+            Element::Ba => &[
+                Isotope {
+                    mass_number: 130,
+                    mass: 129.9063207,
+                    abundance: 0.00106,
+                },
+                Isotope {
+                    mass_number: 132,
+                    mass: 131.9050611,
+                    abundance: 0.00101,
+                },
+                Isotope {
+                    mass_number: 134,
+                    mass: 133.90450818,
+                    abundance: 0.02417,
+                },
+                Isotope {
+                    mass_number: 135,
+                    mass: 134.90568838,
+                    abundance: 0.06592,
+                },
+                Isotope {
+                    mass_number: 136,
+                    mass: 135.90457573,
+                    abundance: 0.07854,
+                },
+                Isotope {
+                    mass_number: 137,
+                    mass: 136.90582714,
+                    abundance: 0.11232,
+                },
+                Isotope {
+                    mass_number: 138,
+                    mass: 137.905247,
+                    abundance: 0.71698,
+                },
+            ],
+            Element::La => &[
+                Isotope {
+                    mass_number: 138,
+                    mass: 137.9071149,
+                    abundance: 0.0008881,
+                },
+                Isotope {
+                    mass_number: 139,
+                    mass: 138.9063563,
+                    abundance: 0.9991119,
+                },
+            ],
+            Element::Ce => &[
+                Isotope {
+                    mass_number: 136,
+                    mass: 135.90712921,
+                    abundance: 0.00185,
+                },
+                Isotope {
+                    mass_number: 138,
+                    mass: 137.905991,
+                    abundance: 0.00251,
+                },
+                Isotope {
+                    mass_number: 140,
+                    mass: 139.9054431,
+                    abundance: 0.8845,
+                },
+                Isotope {
+                    mass_number: 142,
+                    mass: 141.9092504,
+                    abundance: 0.11114,
+                },
+            ],
+            Element::Pr => &[Isotope {
+                mass_number: 141,
+                mass: 140.9076576,
+                abundance: 1.,
+            }],
+            Element::Nd => &[
+                Isotope {
+                    mass_number: 142,
+                    mass: 141.907729,
+                    abundance: 0.27152,
+                },
+                Isotope {
+                    mass_number: 143,
+                    mass: 142.90982,
+                    abundance: 0.12174,
+                },
+                Isotope {
+                    mass_number: 144,
+                    mass: 143.910093,
+                    abundance: 0.23798,
+                },
+                Isotope {
+                    mass_number: 145,
+                    mass: 144.9125793,
+                    abundance: 0.08293,
+                },
+                Isotope {
+                    mass_number: 146,
+                    mass: 145.9131226,
+                    abundance: 0.17189,
+                },
+                Isotope {
+                    mass_number: 148,
+                    mass: 147.9168993,
+                    abundance: 0.05756,
+                },
+                Isotope {
+                    mass_number: 150,
+                    mass: 149.9209022,
+                    abundance: 0.05638,
+                },
+            ],
+            Element::Pm => &[
+                Isotope {
+                    mass_number: 145,
+                    mass: 144.9127559,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 147,
+                    mass: 146.915145,
+                    abundance: 0.,
+                },
+            ],
+            Element::Sm => &[
+                Isotope {
+                    mass_number: 144,
+                    mass: 143.9120065,
+                    abundance: 0.0307,
+                },
+                Isotope {
+                    mass_number: 147,
+                    mass: 146.9149044,
+                    abundance: 0.1499,
+                },
+                Isotope {
+                    mass_number: 148,
+                    mass: 147.9148292,
+                    abundance: 0.1124,
+                },
+                Isotope {
+                    mass_number: 149,
+                    mass: 148.9171921,
+                    abundance: 0.1382,
+                },
+                Isotope {
+                    mass_number: 150,
+                    mass: 149.9172829,
+                    abundance: 0.0738,
+                },
+                Isotope {
+                    mass_number: 152,
+                    mass: 151.9197397,
+                    abundance: 0.2675,
+                },
+                Isotope {
+                    mass_number: 154,
+                    mass: 153.9222169,
+                    abundance: 0.2275,
+                },
+            ],
+            Element::Eu => &[
+                Isotope {
+                    mass_number: 151,
+                    mass: 150.9198578,
+                    abundance: 0.4781,
+                },
+                Isotope {
+                    mass_number: 153,
+                    mass: 152.921238,
+                    abundance: 0.5219,
+                },
+            ],
+            Element::Gd => &[
+                Isotope {
+                    mass_number: 152,
+                    mass: 151.9197995,
+                    abundance: 0.002,
+                },
+                Isotope {
+                    mass_number: 154,
+                    mass: 153.9208741,
+                    abundance: 0.0218,
+                },
+                Isotope {
+                    mass_number: 155,
+                    mass: 154.9226305,
+                    abundance: 0.148,
+                },
+                Isotope {
+                    mass_number: 156,
+                    mass: 155.9221312,
+                    abundance: 0.2047,
+                },
+                Isotope {
+                    mass_number: 157,
+                    mass: 156.9239686,
+                    abundance: 0.1565,
+                },
+                Isotope {
+                    mass_number: 158,
+                    mass: 157.9241123,
+                    abundance: 0.2484,
+                },
+                Isotope {
+                    mass_number: 160,
+                    mass: 159.9270624,
+                    abundance: 0.2186,
+                },
+            ],
+            Element::Tb => &[Isotope {
+                mass_number: 159,
+                mass: 158.9253547,
+                abundance: 1.,
+            }],
+            Element::Dy => &[
+                Isotope {
+                    mass_number: 156,
+                    mass: 155.9242847,
+                    abundance: 0.00056,
+                },
+                Isotope {
+                    mass_number: 158,
+                    mass: 157.9244159,
+                    abundance: 0.00095,
+                },
+                Isotope {
+                    mass_number: 160,
+                    mass: 159.9252046,
+                    abundance: 0.02329,
+                },
+                Isotope {
+                    mass_number: 161,
+                    mass: 160.9269405,
+                    abundance: 0.18889,
+                },
+                Isotope {
+                    mass_number: 162,
+                    mass: 161.9268056,
+                    abundance: 0.25475,
+                },
+                Isotope {
+                    mass_number: 163,
+                    mass: 162.9287383,
+                    abundance: 0.24896,
+                },
+                Isotope {
+                    mass_number: 164,
+                    mass: 163.9291819,
+                    abundance: 0.2826,
+                },
+            ],
+            Element::Ho => &[Isotope {
+                mass_number: 165,
+                mass: 164.9303288,
+                abundance: 1.,
+            }],
+            Element::Er => &[
+                Isotope {
+                    mass_number: 162,
+                    mass: 161.9287884,
+                    abundance: 0.00139,
+                },
+                Isotope {
+                    mass_number: 164,
+                    mass: 163.9292088,
+                    abundance: 0.01601,
+                },
+                Isotope {
+                    mass_number: 166,
+                    mass: 165.9302995,
+                    abundance: 0.33503,
+                },
+                Isotope {
+                    mass_number: 167,
+                    mass: 166.9320546,
+                    abundance: 0.22869,
+                },
+                Isotope {
+                    mass_number: 168,
+                    mass: 167.9323767,
+                    abundance: 0.26978,
+                },
+                Isotope {
+                    mass_number: 170,
+                    mass: 169.9354702,
+                    abundance: 0.1491,
+                },
+            ],
+            Element::Tm => &[Isotope {
+                mass_number: 169,
+                mass: 168.9342179,
+                abundance: 1.,
+            }],
+            Element::Yb => &[
+                Isotope {
+                    mass_number: 168,
+                    mass: 167.9338896,
+                    abundance: 0.00123,
+                },
+                Isotope {
+                    mass_number: 170,
+                    mass: 169.9347664,
+                    abundance: 0.02982,
+                },
+                Isotope {
+                    mass_number: 171,
+                    mass: 170.9363302,
+                    abundance: 0.1409,
+                },
+                Isotope {
+                    mass_number: 172,
+                    mass: 171.9363859,
+                    abundance: 0.2168,
+                },
+                Isotope {
+                    mass_number: 173,
+                    mass: 172.9382151,
+                    abundance: 0.16103,
+                },
+                Isotope {
+                    mass_number: 174,
+                    mass: 173.9388664,
+                    abundance: 0.32026,
+                },
+                Isotope {
+                    mass_number: 176,
+                    mass: 175.9425764,
+                    abundance: 0.12996,
+                },
+            ],
+            Element::Lu => &[
+                Isotope {
+                    mass_number: 175,
+                    mass: 174.9407752,
+                    abundance: 0.97401,
+                },
+                Isotope {
+                    mass_number: 176,
+                    mass: 175.9426897,
+                    abundance: 0.02599,
+                },
+            ],
+            Element::Hf => &[
+                Isotope {
+                    mass_number: 174,
+                    mass: 173.9400461,
+                    abundance: 0.0016,
+                },
+                Isotope {
+                    mass_number: 176,
+                    mass: 175.9414076,
+                    abundance: 0.0526,
+                },
+                Isotope {
+                    mass_number: 177,
+                    mass: 176.9432277,
+                    abundance: 0.186,
+                },
+                Isotope {
+                    mass_number: 178,
+                    mass: 177.9437058,
+                    abundance: 0.2728,
+                },
+                Isotope {
+                    mass_number: 179,
+                    mass: 178.9458232,
+                    abundance: 0.1362,
+                },
+                Isotope {
+                    mass_number: 180,
+                    mass: 179.946557,
+                    abundance: 0.3508,
+                },
+            ],
+            Element::Ta => &[
+                Isotope {
+                    mass_number: 180,
+                    mass: 179.9474648,
+                    abundance: 0.0001201,
+                },
+                Isotope {
+                    mass_number: 181,
+                    mass: 180.9479958,
+                    abundance: 0.9998799,
+                },
+            ],
+            Element::W => &[
+                Isotope {
+                    mass_number: 180,
+                    mass: 179.9467108,
+                    abundance: 0.0012,
+                },
+                Isotope {
+                    mass_number: 182,
+                    mass: 181.94820394,
+                    abundance: 0.265,
+                },
+                Isotope {
+                    mass_number: 183,
+                    mass: 182.95022275,
+                    abundance: 0.1431,
+                },
+                Isotope {
+                    mass_number: 184,
+                    mass: 183.95093092,
+                    abundance: 0.3064,
+                },
+                Isotope {
+                    mass_number: 186,
+                    mass: 185.9543628,
+                    abundance: 0.2843,
+                },
+            ],
+            Element::Re => &[
+                Isotope {
+                    mass_number: 185,
+                    mass: 184.9529545,
+                    abundance: 0.374,
+                },
+                Isotope {
+                    mass_number: 187,
+                    mass: 186.9557501,
+                    abundance: 0.626,
+                },
+            ],
+            Element::Os => &[
+                Isotope {
+                    mass_number: 184,
+                    mass: 183.9524885,
+                    abundance: 0.0002,
+                },
+                Isotope {
+                    mass_number: 186,
+                    mass: 185.953835,
+                    abundance: 0.0159,
+                },
+                Isotope {
+                    mass_number: 187,
+                    mass: 186.9557474,
+                    abundance: 0.0196,
+                },
+                Isotope {
+                    mass_number: 188,
+                    mass: 187.9558352,
+                    abundance: 0.1324,
+                },
+                Isotope {
+                    mass_number: 189,
+                    mass: 188.9581442,
+                    abundance: 0.1615,
+                },
+                Isotope {
+                    mass_number: 190,
+                    mass: 189.9584437,
+                    abundance: 0.2626,
+                },
+                Isotope {
+                    mass_number: 192,
+                    mass: 191.961477,
+                    abundance: 0.4078,
+                },
+            ],
+            Element::Ir => &[
+                Isotope {
+                    mass_number: 191,
+                    mass: 190.9605893,
+                    abundance: 0.373,
+                },
+                Isotope {
+                    mass_number: 193,
+                    mass: 192.9629216,
+                    abundance: 0.627,
+                },
+            ],
+            Element::Pt => &[
+                Isotope {
+                    mass_number: 190,
+                    mass: 189.9599297,
+                    abundance: 0.00012,
+                },
+                Isotope {
+                    mass_number: 192,
+                    mass: 191.9610387,
+                    abundance: 0.00782,
+                },
+                Isotope {
+                    mass_number: 194,
+                    mass: 193.9626809,
+                    abundance: 0.3286,
+                },
+                Isotope {
+                    mass_number: 195,
+                    mass: 194.9647917,
+                    abundance: 0.3378,
+                },
+                Isotope {
+                    mass_number: 196,
+                    mass: 195.96495209,
+                    abundance: 0.2521,
+                },
+                Isotope {
+                    mass_number: 198,
+                    mass: 197.9678949,
+                    abundance: 0.07356,
+                },
+            ],
+            Element::Au => &[Isotope {
+                mass_number: 197,
+                mass: 196.96656879,
+                abundance: 1.,
+            }],
+            Element::Hg => &[
+                Isotope {
+                    mass_number: 196,
+                    mass: 195.9658326,
+                    abundance: 0.0015,
+                },
+                Isotope {
+                    mass_number: 198,
+                    mass: 197.9667686,
+                    abundance: 0.0997,
+                },
+                Isotope {
+                    mass_number: 199,
+                    mass: 198.96828064,
+                    abundance: 0.1687,
+                },
+                Isotope {
+                    mass_number: 200,
+                    mass: 199.96832659,
+                    abundance: 0.231,
+                },
+                Isotope {
+                    mass_number: 201,
+                    mass: 200.97030284,
+                    abundance: 0.1318,
+                },
+                Isotope {
+                    mass_number: 202,
+                    mass: 201.9706434,
+                    abundance: 0.2986,
+                },
+                Isotope {
+                    mass_number: 204,
+                    mass: 203.97349398,
+                    abundance: 0.0687,
+                },
+            ],
+            Element::Tl => &[
+                Isotope {
+                    mass_number: 203,
+                    mass: 202.9723446,
+                    abundance: 0.2952,
+                },
+                Isotope {
+                    mass_number: 205,
+                    mass: 204.9744278,
+                    abundance: 0.7048,
+                },
+            ],
+            Element::Pb => &[
+                Isotope {
+                    mass_number: 204,
+                    mass: 203.973044,
+                    abundance: 0.014,
+                },
+                Isotope {
+                    mass_number: 206,
+                    mass: 205.9744657,
+                    abundance: 0.241,
+                },
+                Isotope {
+                    mass_number: 207,
+                    mass: 206.9758973,
+                    abundance: 0.221,
+                },
+                Isotope {
+                    mass_number: 208,
+                    mass: 207.9766525,
+                    abundance: 0.524,
+                },
+            ],
+            Element::Bi => &[Isotope {
+                mass_number: 209,
+                mass: 208.9803991,
+                abundance: 1.,
+            }],
+            Element::Po => &[
+                Isotope {
+                    mass_number: 209,
+                    mass: 208.9824308,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 210,
+                    mass: 209.9828741,
+                    abundance: 0.,
+                },
+            ],
+            Element::At => &[
+                Isotope {
+                    mass_number: 210,
+                    mass: 209.9871479,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 211,
+                    mass: 210.9874966,
+                    abundance: 0.,
+                },
+            ],
+            Element::Rn => &[Isotope {
+                mass_number: 222,
+                mass: 222.0175782,
+                abundance: 0.,
+            }],
+            Element::Fr => &[Isotope {
+                mass_number: 223,
+                mass: 223.019736,
+                abundance: 0.,
+            }],
+            Element::Ra => &[Isotope {
+                mass_number: 226,
+                mass: 226.0254103,
+                abundance: 0.,
+            }],
+            Element::Ac => &[Isotope {
+                mass_number: 227,
+                mass: 227.0277523,
+                abundance: 0.,
+            }],
+            Element::Th => &[
+                Isotope {
+                    mass_number: 230,
+                    mass: 230.0331341,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 232,
+                    mass: 232.0380558,
+                    abundance: 1.,
+                },
+            ],
+            Element::Pa => &[Isotope {
+                mass_number: 231,
+                mass: 231.0358842,
+                abundance: 1.,
+            }],
+            Element::U => &[
+                Isotope {
+                    mass_number: 234,
+                    mass: 234.0409523,
+                    abundance: 0.000054,
+                },
+                Isotope {
+                    mass_number: 235,
+                    mass: 235.0439301,
+                    abundance: 0.007204,
+                },
+                Isotope {
+                    mass_number: 238,
+                    mass: 238.0507884,
+                    abundance: 0.992742,
+                },
+            ],
+            Element::Np => &[Isotope {
+                mass_number: 237,
+                mass: 237.0481736,
+                abundance: 0.,
+            }],
+            Element::Pu => &[
+                Isotope {
+                    mass_number: 238,
+                    mass: 238.0495601,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 239,
+                    mass: 239.0521636,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 244,
+                    mass: 244.0642053,
+                    abundance: 0.,
+                },
+            ],
+            Element::Am => &[
+                Isotope {
+                    mass_number: 241,
+                    mass: 241.0568293,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 243,
+                    mass: 243.0613813,
+                    abundance: 0.,
+                },
+            ],
+            Element::Cm => &[
+                Isotope {
+                    mass_number: 244,
+                    mass: 244.0627528,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 247,
+                    mass: 247.0703541,
+                    abundance: 0.,
+                },
+            ],
+            Element::Bk => &[Isotope {
+                mass_number: 247,
+                mass: 247.0703073,
+                abundance: 0.,
+            }],
+            Element::Cf => &[
+                Isotope {
+                    mass_number: 251,
+                    mass: 251.0795886,
+                    abundance: 0.,
+                },
+                Isotope {
+                    mass_number: 252,
+                    mass: 252.0816272,
+                    abundance: 0.,
+                },
+            ],
+            Element::Es => &[Isotope {
+                mass_number: 252,
+                mass: 252.08298,
+                abundance: 0.,
+            }],
+            Element::Fm => &[Isotope {
+                mass_number: 257,
+                mass: 257.0951061,
+                abundance: 0.,
+            }],
+            Element::Md => &[Isotope {
+                mass_number: 258,
+                mass: 258.0984315,
+                abundance: 0.,
+            }],
+            Element::No => &[Isotope {
+                mass_number: 259,
+                mass: 259.10103,
+                abundance: 0.,
+            }],
+            Element::Lr => &[Isotope {
+                mass_number: 266,
+                mass: 266.11983,
+                abundance: 0.,
+            }],
+            Element::Rf => &[Isotope {
+                mass_number: 267,
+                mass: 267.12179,
+                abundance: 0.,
+            }],
+            Element::Db => &[Isotope {
+                mass_number: 268,
+                mass: 268.12567,
+                abundance: 0.,
+            }],
+            Element::Sg => &[Isotope {
+                mass_number: 269,
+                mass: 269.12863,
+                abundance: 0.,
+            }],
+            Element::Bh => &[Isotope {
+                mass_number: 270,
+                mass: 270.13336,
+                abundance: 0.,
+            }],
+            Element::Hs => &[Isotope {
+                mass_number: 269,
+                mass: 269.13375,
+                abundance: 0.,
+            }],
+            Element::Mt => &[Isotope {
+                mass_number: 278,
+                mass: 278.15631,
+                abundance: 0.,
+            }],
+            Element::Ds => &[Isotope {
+                mass_number: 281,
+                mass: 281.16451,
+                abundance: 0.,
+            }],
+            Element::Rg => &[Isotope {
+                mass_number: 282,
+                mass: 282.16912,
+                abundance: 0.,
+            }],
+            Element::Cn => &[Isotope {
+                mass_number: 285,
+                mass: 285.17712,
+                abundance: 0.,
+            }],
+            Element::Nh => &[Isotope {
+                mass_number: 286,
+                mass: 286.18221,
+                abundance: 0.,
+            }],
+            Element::Fl => &[Isotope {
+                mass_number: 289,
+                mass: 289.19042,
+                abundance: 0.,
+            }],
+            Element::Mc => &[Isotope {
+                mass_number: 290,
+                mass: 290.19598,
+                abundance: 0.,
+            }],
+            Element::Lv => &[Isotope {
+                mass_number: 293,
+                mass: 293.20449,
+                abundance: 0.,
+            }],
+            Element::Ts => &[Isotope {
+                mass_number: 294,
+                mass: 294.21046,
+                abundance: 0.,
+            }],
+            Element::Og => &[Isotope {
+                mass_number: 294,
+                mass: 294.21392,
+                abundance: 0.,
+            }],
+        }
+    }
+
+    fn period_offset(&self) -> u8 {
+        let start = match self.period() {
+            1 => 1,
+            2 => 3,
+            3 => 11,
+            4 => 19,
+            5 => 37,
+            6 => 55,
+            _ => 87,
+        };
+
+        self.atomic_number() - start
+    }
+
+    fn period_length(&self) -> u8 {
+        match self.period() {
+            1 => 2,
+            2 | 3 => 8,
+            4 | 5 => 18,
+            _ => 32,
+        }
+    }
+
     fn core(&self) -> Option<Self> {
         if self.atomic_number() < 3 {
             None
@@ -375,3 +3041,334 @@ mod valence_electrons {
         assert_eq!(tennesine.valence_electrons(), 17 + 14)
     }
 }
+
+#[cfg(test)]
+mod symbol {
+    use super::*;
+
+    #[test]
+    fn carbon() {
+        assert_eq!(Element::C.symbol(), "C")
+    }
+
+    #[test]
+    fn chlorine() {
+        assert_eq!(Element::Cl.symbol(), "Cl")
+    }
+
+    #[test]
+    fn oganesson() {
+        assert_eq!(Element::Og.symbol(), "Og")
+    }
+}
+
+#[cfg(test)]
+mod from_str {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn unknown() {
+        assert_eq!("Xx".parse::<Element>(), Err(()))
+    }
+
+    #[test]
+    fn lowercase() {
+        assert_eq!("cl".parse::<Element>(), Err(()))
+    }
+
+    #[test]
+    fn chlorine() {
+        assert_eq!("Cl".parse::<Element>(), Ok(Element::Cl))
+    }
+}
+
+#[cfg(test)]
+mod from_atomic_number {
+    use super::*;
+
+    #[test]
+    fn zero() {
+        assert_eq!(Element::from_atomic_number(0), None)
+    }
+
+    #[test]
+    fn carbon() {
+        assert_eq!(Element::from_atomic_number(6), Some(Element::C))
+    }
+
+    #[test]
+    fn oganesson() {
+        assert_eq!(Element::from_atomic_number(118), Some(Element::Og))
+    }
+
+    #[test]
+    fn beyond_oganesson() {
+        assert_eq!(Element::from_atomic_number(119), None)
+    }
+
+    #[test]
+    fn round_trip() {
+        for number in 1..=118 {
+            let element = Element::from_atomic_number(number).unwrap();
+
+            assert_eq!(element.atomic_number(), number)
+        }
+    }
+}
+
+#[cfg(test)]
+mod name {
+    use super::*;
+
+    #[test]
+    fn carbon() {
+        assert_eq!(Element::C.name(), "Carbon")
+    }
+
+    #[test]
+    fn tennessine() {
+        assert_eq!(Element::Ts.name(), "Tennessine")
+    }
+}
+
+#[cfg(test)]
+mod display {
+    use super::*;
+
+    #[test]
+    fn chlorine() {
+        assert_eq!(Element::Cl.to_string(), "Cl")
+    }
+}
+
+#[cfg(test)]
+mod classification {
+    use super::*;
+
+    #[test]
+    fn hydrogen() {
+        assert_eq!(
+            (Element::H.period(), Element::H.group(), Element::H.block()),
+            (1, Some(1), Block::S)
+        )
+    }
+
+    #[test]
+    fn helium() {
+        assert_eq!(
+            (
+                Element::He.period(),
+                Element::He.group(),
+                Element::He.block()
+            ),
+            (1, Some(18), Block::S)
+        )
+    }
+
+    #[test]
+    fn carbon() {
+        assert_eq!(
+            (Element::C.period(), Element::C.group(), Element::C.block()),
+            (2, Some(14), Block::P)
+        )
+    }
+
+    #[test]
+    fn iron() {
+        assert_eq!(
+            (
+                Element::Fe.period(),
+                Element::Fe.group(),
+                Element::Fe.block()
+            ),
+            (4, Some(8), Block::D)
+        )
+    }
+
+    #[test]
+    fn lanthanum() {
+        assert_eq!(
+            (
+                Element::La.period(),
+                Element::La.group(),
+                Element::La.block()
+            ),
+            (6, Some(3), Block::D)
+        )
+    }
+
+    #[test]
+    fn cerium() {
+        assert_eq!(
+            (
+                Element::Ce.period(),
+                Element::Ce.group(),
+                Element::Ce.block()
+            ),
+            (6, None, Block::F)
+        )
+    }
+
+    #[test]
+    fn mercury() {
+        assert_eq!(
+            (
+                Element::Hg.period(),
+                Element::Hg.group(),
+                Element::Hg.block()
+            ),
+            (6, Some(12), Block::D)
+        )
+    }
+
+    #[test]
+    fn lead() {
+        assert_eq!(
+            (
+                Element::Pb.period(),
+                Element::Pb.group(),
+                Element::Pb.block()
+            ),
+            (6, Some(14), Block::P)
+        )
+    }
+
+    #[test]
+    fn oganesson() {
+        assert_eq!(
+            (
+                Element::Og.period(),
+                Element::Og.group(),
+                Element::Og.block()
+            ),
+            (7, Some(18), Block::P)
+        )
+    }
+}
+
+#[cfg(test)]
+mod masses {
+    use super::*;
+
+    #[test]
+    fn carbon_atomic_weight() {
+        assert_eq!(Element::C.atomic_weight(), 12.011)
+    }
+
+    #[test]
+    fn technetium_atomic_weight() {
+        assert_eq!(Element::Tc.atomic_weight(), 98.)
+    }
+
+    #[test]
+    fn carbon_monoisotopic_mass() {
+        assert_eq!(Element::C.monoisotopic_mass(), 12.)
+    }
+
+    #[test]
+    fn chlorine_monoisotopic_mass() {
+        assert_eq!(Element::Cl.monoisotopic_mass(), 34.968852682)
+    }
+
+    #[test]
+    fn technetium_monoisotopic_mass() {
+        assert_eq!(Element::Tc.monoisotopic_mass(), 97.9072124)
+    }
+
+    #[test]
+    fn every_element_has_monoisotopic_mass() {
+        for number in 1..=118 {
+            let element = Element::from_atomic_number(number).unwrap();
+
+            assert!(element.monoisotopic_mass() > 0.)
+        }
+    }
+}
+
+#[cfg(test)]
+mod isotope {
+    use super::*;
+
+    #[test]
+    fn deuterium() {
+        assert_eq!(
+            Element::H.isotope(2),
+            Some(&Isotope {
+                mass_number: 2,
+                mass: 2.01410177812,
+                abundance: 0.000115
+            })
+        )
+    }
+
+    #[test]
+    fn carbon_14() {
+        assert_eq!(Element::C.isotope(14).unwrap().abundance, 0.)
+    }
+
+    #[test]
+    fn carbon_100() {
+        assert_eq!(Element::C.isotope(100), None)
+    }
+
+    #[test]
+    fn natural_abundances_sum_to_one() {
+        for number in 1..=118 {
+            let element = Element::from_atomic_number(number).unwrap();
+            let sum = element
+                .isotopes()
+                .iter()
+                .map(|isotope| isotope.abundance)
+                .sum::<f64>();
+
+            assert!(sum == 0. || (sum - 1.).abs() < 1e-4, "{}", element)
+        }
+    }
+}
+
+#[cfg(test)]
+mod most_abundant_isotope {
+    use super::*;
+
+    #[test]
+    fn natural() {
+        assert_eq!(Element::C.most_abundant_isotope().mass_number, 12)
+    }
+
+    #[test]
+    fn synthetic() {
+        assert_eq!(Element::Tc.most_abundant_isotope().mass_number, 98)
+    }
+}
+
+#[cfg(test)]
+mod is_valid_mass_number {
+    use super::*;
+
+    #[test]
+    fn below_atomic_number() {
+        assert!(!Element::C.is_valid_mass_number(5))
+    }
+
+    #[test]
+    fn atomic_number() {
+        assert!(Element::H.is_valid_mass_number(1))
+    }
+
+    #[test]
+    fn radioisotope() {
+        assert!(Element::U.is_valid_mass_number(235))
+    }
+
+    #[test]
+    fn unknown_nuclide() {
+        assert!(!Element::C.is_valid_mass_number(60000))
+    }
+
+    #[test]
+    fn between_known_nuclides() {
+        assert!(!Element::Fe.is_valid_mass_number(55))
+    }
+}
@@ -5,6 +5,7 @@ pub enum Error {
     Valence(usize),
     Isotope(usize),
     Parity(usize),
+    Bond(usize, usize),
     Graph(GraphError),
 }
 
@@ -0,0 +1,9 @@
+/// A nuclide of an Element. `mass` is the exact mass in unified atomic
+/// mass units, and `abundance` is the natural mole fraction, which is zero
+/// for isotopes not found in nature.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Isotope {
+    pub mass_number: u16,
+    pub mass: f64,
+    pub abundance: f64,
+}
@@ -1,17 +1,26 @@
 mod atom;
+mod block;
 mod bond;
+mod configuration;
 mod default_molecule;
 mod element;
 mod error;
+mod isotope;
+#[allow(clippy::module_inception)]
 mod molecule;
+mod molecule_mut;
 mod node;
 mod parity;
 
 pub use atom::Atom;
+pub use block::Block;
 pub use bond::Bond;
+pub use configuration::Configuration;
 pub use default_molecule::DefaultMolecule;
 pub use element::Element;
 pub use error::Error;
+pub use isotope::Isotope;
 pub use molecule::Molecule;
+pub use molecule_mut::MoleculeMut;
 pub use node::Node;
 pub use parity::Parity;
@@ -1,6 +1,6 @@
 use gamma::graph::{Error, Graph};
 
-use super::Atom;
+use super::{Atom, Bond};
 
 pub trait Molecule: Graph {
     /// Returns the atomic attributes associated with id,
@@ -11,7 +11,25 @@ pub trait Molecule: Graph {
     /// or Error if id not found.
     fn charge(&self, id: usize) -> Result<f32, Error>;
 
+    /// Returns true if the atom associated with id was written as
+    /// aromatic in its source, or Error if id not found. See also
+    /// `Bond::aromatic`. Molecules that don't record their source return
+    /// false.
+    fn is_aromatic(&self, id: usize) -> Result<bool, Error> {
+        if self.has_id(id) {
+            Ok(false)
+        } else {
+            Err(Error::UnknownId(id))
+        }
+    }
+
     /// Returns the bond order computation associated with the source
     /// and target ids, or Error if either sid or tid not found.
     fn bond_order(&self, sid: usize, tid: usize) -> Result<f32, Error>;
+
+    /// Returns the bond attributes associated with the source and target
+    /// ids, None if no such bond exists, or Error if either sid or tid
+    /// not found. Implementors must store a Bond for each direction, as
+    /// one can't be borrowed from `bond_order` alone.
+    fn bond(&self, sid: usize, tid: usize) -> Result<Option<&Bond>, Error>;
 }
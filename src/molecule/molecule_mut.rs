@@ -0,0 +1,61 @@
+use super::{Atom, Error, Molecule};
+
+/// A Molecule that can be edited in place. Each bond is stored in both
+/// directions, and edits keep the two in step.
+///
+/// Charge is computed from the other attributes of an atom. Bond edits
+/// keep it unchanged by moving electrons between the bond and the
+/// nonbonding electrons of each end, failing if an end has too few to
+/// give. Joining two methyl radicals gives ethane, and breaking a bond
+/// leaves a radical at each end. Parities and configurations are relative
+/// to the neighbors of an atom, so any edit changing those neighbors
+/// clears the stereo descriptors of the atoms at each end, of the double
+/// bonds they take part in, and of any cumulene center they are joined to
+/// through double bonds.
+pub trait MoleculeMut: Molecule {
+    /// Adds atom, returning its id.
+    fn add_atom(&mut self, atom: Atom) -> usize;
+
+    /// Removes the atom at id together with its bonds, returning it, or
+    /// Error if id not found. Ids above id shift down by one.
+    fn remove_atom(&mut self, id: usize) -> Result<Atom, Error>;
+
+    /// Adds a bond with the given electrons between sid and tid, or
+    /// returns Error if either id is not found, the bond exists, sid and
+    /// tid are the same, electrons is zero or odd, or either atom lacks the
+    /// electrons.
+    fn add_bond(
+        &mut self,
+        sid: usize,
+        tid: usize,
+        electrons: u8,
+    ) -> Result<(), Error>;
+
+    /// Removes the bond between sid and tid, or returns Error if either id
+    /// is not found or the bond doesn't exist.
+    fn remove_bond(&mut self, sid: usize, tid: usize) -> Result<(), Error>;
+
+    /// Sets the electrons of the bond between sid and tid, or returns
+    /// Error if the bond doesn't exist, electrons is zero or odd, or either
+    /// atom lacks the electrons. Use `remove_bond` to break a bond.
+    /// A double bond losing its order loses its parity, as does any
+    /// cumulene center it belonged to.
+    fn set_bond_electrons(
+        &mut self,
+        sid: usize,
+        tid: usize,
+        electrons: u8,
+    ) -> Result<(), Error>;
+
+    /// Sets the charge of the atom at id by adjusting its nonbonding
+    /// electrons, or returns Error if that would leave too few or too many.
+    fn set_charge(&mut self, id: usize, charge: i8) -> Result<(), Error>;
+
+    /// number is not among `Element::isotopes`.
+    /// number is lower than the atomic number.
+    fn set_isotope(
+        &mut self,
+        id: usize,
+        isotope: Option<u16>,
+    ) -> Result<(), Error>;
+}
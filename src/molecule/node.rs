@@ -4,4 +4,5 @@ use super::{Atom, Bond};
 pub struct Node {
     pub atom: Atom,
     pub bonds: Vec<Bond>,
+    pub aromatic: bool,
 }
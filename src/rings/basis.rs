@@ -0,0 +1,103 @@
+/// Edge sets of cycles as bit vectors, reduced by Gaussian elimination
+/// over GF(2).
+#[derive(Clone)]
+pub struct Basis {
+    rows: Vec<(usize, Vec<u64>)>,
+}
+
+impl Basis {
+    pub fn new() -> Self {
+        Self { rows: Vec::new() }
+    }
+
+    /// Returns true if edges is not a sum of rows.
+    pub fn is_independent(&self, edges: &[u64]) -> bool {
+        self.reduce(edges).is_some()
+    }
+
+    /// Adds edges and returns true if they are independent of the existing
+    /// rows, otherwise returns false without change.
+    pub fn insert(&mut self, edges: &[u64]) -> bool {
+        match self.reduce(edges) {
+            Some(row) => {
+                self.rows.push(row);
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn reduce(&self, edges: &[u64]) -> Option<(usize, Vec<u64>)> {
+        let mut result = edges.to_vec();
+
+        for (pivot, row) in self.rows.iter() {
+            if test(&result, *pivot) {
+                for (word, other) in result.iter_mut().zip(row.iter()) {
+                    *word ^= other;
+                }
+            }
+        }
+
+        pivot(&result).map(|pivot| (pivot, result))
+    }
+}
+
+/// Returns an empty bit vector able to hold size bits.
+pub fn bits(size: usize) -> Vec<u64> {
+    vec![0; size.div_ceil(64)]
+}
+
+pub fn set(bits: &mut [u64], index: usize) {
+    bits[index / 64] |= 1 << (index % 64)
+}
+
+fn test(bits: &[u64], index: usize) -> bool {
+    bits[index / 64] & (1 << (index % 64)) != 0
+}
+
+fn pivot(bits: &[u64]) -> Option<usize> {
+    bits.iter()
+        .enumerate()
+        .find(|(_, &word)| word != 0)
+        .map(|(index, word)| index * 64 + word.trailing_zeros() as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vector(indices: &[usize]) -> Vec<u64> {
+        let mut result = bits(100);
+
+        for &index in indices {
+            set(&mut result, index)
+        }
+
+        result
+    }
+
+    #[test]
+    fn empty_is_dependent() {
+        assert!(!Basis::new().is_independent(&vector(&[])))
+    }
+
+    #[test]
+    fn sum_is_dependent() {
+        let mut basis = Basis::new();
+
+        assert!(basis.insert(&vector(&[0, 1, 2])));
+        assert!(basis.insert(&vector(&[2, 3, 70])));
+        assert!(!basis.insert(&vector(&[0, 1, 3, 70])))
+    }
+
+    #[test]
+    fn distinct_is_independent() {
+        let mut basis = Basis::new();
+
+        basis.insert(&vector(&[0, 1, 2]));
+        basis.insert(&vector(&[2, 3, 70]));
+
+        assert!(basis.is_independent(&vector(&[1, 3, 70])))
+    }
+}
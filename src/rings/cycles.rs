@@ -0,0 +1,364 @@
+use std::collections::{HashMap, VecDeque};
+
+use gamma::graph::{Error, Graph};
+
+use super::basis::{bits, set, Basis};
+
+type Cycle = Vec<usize>;
+
+/// Returns the relevant cycles of graph, and the subset forming a
+/// minimum cycle basis (SSSR). Cycles are given as atom ids in ring order
+/// and sorted by size.
+///
+/// Candidate cycles are generated by the algorithm of Vismara (Electron.
+/// J. Combin. 4, 1997): each cycle is built from shortest paths to its
+/// highest-ranked atom, where rank is position in `Graph::ids`. A cycle is
+/// relevant if it is not a sum of strictly smaller cycles.
+pub fn cycles<G: Graph>(graph: &G) -> Result<(Vec<Cycle>, Vec<Cycle>), Error> {
+    let table = Table::new(graph)?;
+    let mut prototypes = Vec::new();
+
+    for (root, cyclic) in table.core().into_iter().enumerate() {
+        if cyclic {
+            prototypes.append(&mut table.prototypes(root));
+        }
+    }
+
+    prototypes.sort_by_key(|prototype| prototype.len());
+
+    let mut relevant = Vec::new();
+    let mut sssr = Vec::new();
+    let mut smaller = Basis::new();
+    let mut basis = Basis::new();
+    let mut start = 0;
+
+    while start < prototypes.len() {
+        let size = prototypes[start].len();
+        let end = start
+            + prototypes[start..]
+                .iter()
+                .take_while(|prototype| prototype.len() == size)
+                .count();
+
+        for prototype in prototypes[start..end].iter() {
+            let edges = table.edges(&prototype.cycle());
+
+            if !smaller.is_independent(&edges) {
+                continue;
+            }
+
+            if basis.insert(&edges) {
+                sssr.push(table.to_ids(&prototype.cycle()));
+            }
+
+            for cycle in table.family(prototype) {
+                relevant.push(table.to_ids(&cycle));
+            }
+        }
+
+        smaller = basis.clone();
+        start = end;
+    }
+
+    Ok((relevant, sssr))
+}
+
+// Shortest paths from root through lower-ranked atoms, and the atoms
+// closing a cycle at the far end: either one atom adjacent to two
+// equidistant atoms (odd), or two atoms sharing one (even).
+struct Prototype {
+    root: usize,
+    left: Vec<usize>,
+    right: Vec<usize>,
+    apex: Option<usize>,
+    left_end: usize,
+    right_end: usize,
+}
+
+impl Prototype {
+    fn len(&self) -> usize {
+        self.left.len() + self.right.len() - 1 + self.apex.map_or(0, |_| 1)
+    }
+
+    fn cycle(&self) -> Vec<usize> {
+        join(&self.left, &self.right, self.apex)
+    }
+}
+
+// Joins two paths sharing their first atom, optionally through apex.
+fn join(left: &[usize], right: &[usize], apex: Option<usize>) -> Vec<usize> {
+    let mut result = left.to_vec();
+
+    if let Some(apex) = apex {
+        result.push(apex);
+    }
+
+    result.extend(right.iter().skip(1).rev());
+
+    result
+}
+
+struct Table {
+    ids: Vec<usize>,
+    neighbors: Vec<Vec<usize>>,
+    edge_indices: HashMap<(usize, usize), usize>,
+}
+
+impl Table {
+    fn new<G: Graph>(graph: &G) -> Result<Self, Error> {
+        let ids = graph.ids().collect::<Vec<_>>();
+        let indices = ids
+            .iter()
+            .enumerate()
+            .map(|(index, &id)| (id, index))
+            .collect::<HashMap<_, _>>();
+        let mut neighbors = Vec::new();
+        let mut edge_indices = HashMap::new();
+
+        for &id in ids.iter() {
+            let mut outs = Vec::new();
+
+            for neighbor in graph.neighbors(id)? {
+                match indices.get(&neighbor) {
+                    Some(&index) => outs.push(index),
+                    None => return Err(Error::UnknownId(neighbor)),
+                }
+            }
+
+            neighbors.push(outs);
+        }
+
+        for (sid, outs) in neighbors.iter().enumerate() {
+            for &tid in outs {
+                let next = edge_indices.len();
+
+                edge_indices.entry(key(sid, tid)).or_insert(next);
+            }
+        }
+
+        Ok(Self {
+            ids,
+            neighbors,
+            edge_indices,
+        })
+    }
+
+    // Atoms left after repeatedly removing those with fewer than two
+    // neighbors. No other atom can lie on a cycle.
+    fn core(&self) -> Vec<bool> {
+        let mut degrees = self
+            .neighbors
+            .iter()
+            .map(|outs| outs.len())
+            .collect::<Vec<_>>();
+        let mut result = vec![true; degrees.len()];
+        let mut stack = (0..degrees.len())
+            .filter(|&index| degrees[index] < 2)
+            .collect::<Vec<_>>();
+
+        while let Some(index) = stack.pop() {
+            if !result[index] {
+                continue;
+            }
+
+            result[index] = false;
+
+            for &neighbor in self.neighbors[index].iter() {
+                if result[neighbor] {
+                    degrees[neighbor] -= 1;
+
+                    if degrees[neighbor] < 2 {
+                        stack.push(neighbor);
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    fn to_ids(&self, cycle: &[usize]) -> Vec<usize> {
+        cycle.iter().map(|&index| self.ids[index]).collect()
+    }
+
+    fn edges(&self, cycle: &[usize]) -> Vec<u64> {
+        let mut result = bits(self.edge_indices.len());
+
+        for (i, &sid) in cycle.iter().enumerate() {
+            let tid = cycle[(i + 1) % cycle.len()];
+
+            set(&mut result, self.edge_indices[&key(sid, tid)])
+        }
+
+        result
+    }
+
+    // Shortest paths from root over lower-ranked atoms close odd cycles at
+    // edges joining equidistant atoms, and even cycles at atoms with two
+    // predecessors. Atoms whose distance exceeds that in the full graph
+    // are excluded.
+    fn prototypes(&self, root: usize) -> Vec<Prototype> {
+        let full = self.distances(root, self.ids.len());
+        let restricted = self.distances(root, root);
+        let reached = |index: usize| {
+            index <= root
+                && restricted[index].is_some()
+                && restricted[index] == full[index]
+        };
+        let mut order = (0..root)
+            .filter(|&index| reached(index))
+            .collect::<Vec<_>>();
+        let mut paths = HashMap::new();
+        let mut result = Vec::new();
+
+        order.sort_by_key(|&index| restricted[index]);
+        paths.insert(root, vec![root]);
+
+        for &y in order.iter() {
+            let distance = restricted[y].expect("distance");
+            let predecessor = self.neighbors[y]
+                .iter()
+                .find(|&&z| reached(z) && restricted[z] == Some(distance - 1))
+                .expect("predecessor");
+            let mut path = paths[predecessor].clone();
+
+            path.push(y);
+            paths.insert(y, path);
+        }
+
+        for &y in order.iter() {
+            let distance = restricted[y];
+            let mut predecessors = Vec::new();
+
+            for &z in self.neighbors[y].iter() {
+                if !reached(z) {
+                    continue;
+                }
+
+                if restricted[z].map(|other| other + 1) == distance {
+                    predecessors.push(z);
+                } else if restricted[z] == distance
+                    && z < y
+                    && disjoint(&paths[&z], &paths[&y])
+                {
+                    result.push(Prototype {
+                        root,
+                        left: paths[&z].clone(),
+                        right: paths[&y].clone(),
+                        apex: None,
+                        left_end: z,
+                        right_end: y,
+                    });
+                }
+            }
+
+            for (i, &p) in predecessors.iter().enumerate() {
+                for &q in predecessors.iter().skip(i + 1) {
+                    if disjoint(&paths[&p], &paths[&q]) {
+                        result.push(Prototype {
+                            root,
+                            left: paths[&p].clone(),
+                            right: paths[&q].clone(),
+                            apex: Some(y),
+                            left_end: p,
+                            right_end: q,
+                        })
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    // All cycles formed like prototype from alternative shortest paths.
+    fn family(&self, prototype: &Prototype) -> Vec<Vec<usize>> {
+        let root = prototype.root;
+        let restricted = self.distances(root, root);
+        let lefts = self.shortest_paths(root, prototype.left_end, &restricted);
+        let rights =
+            self.shortest_paths(root, prototype.right_end, &restricted);
+        let mut result = Vec::new();
+
+        for left in lefts.iter() {
+            for right in rights.iter() {
+                if disjoint(left, right) {
+                    result.push(join(left, right, prototype.apex))
+                }
+            }
+        }
+
+        result
+    }
+
+    // Shortest paths from root to target through atoms ranked below root.
+    fn shortest_paths(
+        &self,
+        root: usize,
+        target: usize,
+        distances: &[Option<usize>],
+    ) -> Vec<Vec<usize>> {
+        if target == root {
+            return vec![vec![root]];
+        }
+
+        let distance = distances[target].expect("distance");
+        let mut result = Vec::new();
+
+        for &predecessor in self.neighbors[target].iter() {
+            if predecessor > root {
+                continue;
+            }
+
+            if distances[predecessor] == Some(distance - 1) {
+                for mut path in
+                    self.shortest_paths(root, predecessor, distances)
+                {
+                    path.push(target);
+                    result.push(path);
+                }
+            }
+        }
+
+        result
+    }
+
+    // Distances from root over atoms ranked below limit, plus root.
+    fn distances(&self, root: usize, limit: usize) -> Vec<Option<usize>> {
+        let mut result = vec![None; self.ids.len()];
+        let mut queue = VecDeque::new();
+
+        result[root] = Some(0);
+        queue.push_back(root);
+
+        while let Some(index) = queue.pop_front() {
+            let distance = result[index].expect("distance");
+
+            for &neighbor in self.neighbors[index].iter() {
+                if neighbor >= limit && neighbor != root {
+                    continue;
+                }
+
+                if result[neighbor].is_none() {
+                    result[neighbor] = Some(distance + 1);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+fn key(sid: usize, tid: usize) -> (usize, usize) {
+    if sid < tid {
+        (sid, tid)
+    } else {
+        (tid, sid)
+    }
+}
+
+// Returns true if paths share only their first atom.
+fn disjoint(left: &[usize], right: &[usize]) -> bool {
+    !left.iter().skip(1).any(|index| right[1..].contains(index))
+}
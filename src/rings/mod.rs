@@ -0,0 +1,6 @@
+mod basis;
+mod cycles;
+#[allow(clippy::module_inception)]
+mod rings;
+
+pub use rings::Rings;
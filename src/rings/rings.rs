@@ -0,0 +1,230 @@
+use std::collections::HashSet;
+
+use gamma::graph::{Error, Graph};
+
+use super::cycles::cycles;
+
+/// Ring perception results for a Graph. The smallest set of smallest
+/// rings (SSSR) is a minimum cycle basis, and is not unique for some
+/// ring systems. The relevant cycles are the union of all minimum cycle
+/// bases, and are unique.
+///
+/// Rings are reported as atom ids in ring order, sorted by size.
+#[derive(Debug, PartialEq)]
+pub struct Rings {
+    sssr: Vec<Vec<usize>>,
+    relevant: Vec<Vec<usize>>,
+    bonds: HashSet<(usize, usize)>,
+}
+
+impl Rings {
+    /// Perceives the rings of graph.
+    pub fn new<G: Graph>(graph: &G) -> Result<Self, Error> {
+        let (relevant, sssr) = cycles(graph)?;
+        let mut bonds = HashSet::new();
+
+        for ring in sssr.iter() {
+            for (i, &sid) in ring.iter().enumerate() {
+                bonds.insert(key(sid, ring[(i + 1) % ring.len()]));
+            }
+        }
+
+        Ok(Self {
+            sssr,
+            relevant,
+            bonds,
+        })
+    }
+
+    pub fn sssr(&self) -> &[Vec<usize>] {
+        &self.sssr
+    }
+
+    pub fn relevant_cycles(&self) -> &[Vec<usize>] {
+        &self.relevant
+    }
+
+    /// Returns the number of rings in the SSSR, equal to the cyclomatic
+    /// number of the graph.
+    pub fn ring_count(&self) -> usize {
+        self.sssr.len()
+    }
+
+    /// Returns true if id belongs to any ring.
+    pub fn is_in_ring(&self, id: usize) -> bool {
+        self.sssr.iter().any(|ring| ring.contains(&id))
+    }
+
+    /// Returns true if the bond between sid and tid belongs to any ring.
+    pub fn is_bond_in_ring(&self, sid: usize, tid: usize) -> bool {
+        self.bonds.contains(&key(sid, tid))
+    }
+
+    /// Returns the sizes of SSSR rings containing id, smallest first.
+    pub fn ring_sizes_of(&self, id: usize) -> Vec<usize> {
+        self.sssr
+            .iter()
+            .filter(|ring| ring.contains(&id))
+            .map(|ring| ring.len())
+            .collect()
+    }
+
+    /// Returns the number of SSSR rings containing id.
+    pub fn ring_count_of(&self, id: usize) -> usize {
+        self.sssr.iter().filter(|ring| ring.contains(&id)).count()
+    }
+
+    /// Returns the size of the smallest ring containing id, or None if id
+    /// is acyclic. Unlike `ring_sizes_of`, the result does not depend on
+    /// the choice of SSSR.
+    pub fn smallest_ring_size_of(&self, id: usize) -> Option<usize> {
+        self.relevant
+            .iter()
+            .filter(|ring| ring.contains(&id))
+            .map(|ring| ring.len())
+            .min()
+    }
+}
+
+fn key(sid: usize, tid: usize) -> (usize, usize) {
+    if sid < tid {
+        (sid, tid)
+    } else {
+        (tid, sid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::read_smiles;
+
+    fn rings(smiles: &str) -> Rings {
+        Rings::new(&read_smiles(smiles, None).unwrap()).unwrap()
+    }
+
+    fn sizes(rings: &[Vec<usize>]) -> Vec<usize> {
+        rings.iter().map(|ring| ring.len()).collect()
+    }
+
+    #[test]
+    fn acyclic() {
+        let rings = rings("CCO");
+
+        assert_eq!(rings.ring_count(), 0);
+        assert_eq!(rings.relevant_cycles().len(), 0);
+        assert_eq!(rings.is_in_ring(0), false);
+        assert_eq!(rings.smallest_ring_size_of(0), None)
+    }
+
+    #[test]
+    fn cyclopropane() {
+        let rings = rings("C1CC1");
+        let mut ring = rings.sssr()[0].clone();
+
+        ring.sort();
+
+        assert_eq!(ring, vec![0, 1, 2]);
+        assert_eq!(rings.relevant_cycles().len(), 1)
+    }
+
+    #[test]
+    fn cyclohexane_ring_order() {
+        let rings = rings("C1CCCCC1");
+        let ring = &rings.sssr()[0];
+
+        for (i, &sid) in ring.iter().enumerate() {
+            let tid = ring[(i + 1) % ring.len()];
+
+            assert!(rings.is_bond_in_ring(sid, tid))
+        }
+
+        assert_eq!(ring.len(), 6)
+    }
+
+    #[test]
+    fn methylcyclohexane() {
+        let rings = rings("CC1CCCCC1");
+
+        assert_eq!(rings.is_in_ring(0), false);
+        assert_eq!(rings.is_in_ring(1), true);
+        assert_eq!(rings.is_bond_in_ring(0, 1), false);
+        assert_eq!(rings.is_bond_in_ring(1, 2), true);
+        assert_eq!(rings.ring_sizes_of(1), vec![6])
+    }
+
+    #[test]
+    fn naphthalene() {
+        let rings = rings("c1cccc2ccccc12");
+
+        assert_eq!(sizes(rings.sssr()), vec![6, 6]);
+        assert_eq!(sizes(rings.relevant_cycles()), vec![6, 6]);
+        assert_eq!(rings.ring_count_of(4), 2);
+        assert_eq!(rings.ring_count_of(0), 1)
+    }
+
+    #[test]
+    fn spiro() {
+        let rings = rings("C1CC11CCC1");
+
+        assert_eq!(sizes(rings.sssr()), vec![3, 4]);
+        assert_eq!(rings.ring_sizes_of(2), vec![3, 4])
+    }
+
+    #[test]
+    fn bicyclo_2_2_2_octane() {
+        let rings = rings("C1CC2CCC1CC2");
+
+        assert_eq!(sizes(rings.sssr()), vec![6, 6]);
+        assert_eq!(sizes(rings.relevant_cycles()), vec![6, 6, 6])
+    }
+
+    #[test]
+    fn norbornane() {
+        let rings = rings("C1CC2CCC1C2");
+
+        assert_eq!(sizes(rings.sssr()), vec![5, 5]);
+        assert_eq!(sizes(rings.relevant_cycles()), vec![5, 5])
+    }
+
+    #[test]
+    fn cubane() {
+        let rings = rings("C12C3C4C1C5C2C3C45");
+
+        assert_eq!(sizes(rings.sssr()), vec![4, 4, 4, 4, 4]);
+        assert_eq!(sizes(rings.relevant_cycles()), vec![4, 4, 4, 4, 4, 4])
+    }
+
+    #[test]
+    fn bicyclo_2_1_1_hexane() {
+        let rings = rings("C1CC2CC1C2");
+
+        assert_eq!(sizes(rings.sssr()), vec![4, 5]);
+        assert_eq!(rings.smallest_ring_size_of(0), Some(5));
+        assert_eq!(rings.smallest_ring_size_of(5), Some(4))
+    }
+
+    #[test]
+    fn macrocycle_with_bridge() {
+        let rings = rings("C1CCCCCCCCC2CCCCC12");
+
+        assert_eq!(sizes(rings.sssr()), vec![6, 11])
+    }
+
+    #[test]
+    fn disconnected() {
+        let rings = rings("C1CC1.C1CCC1");
+
+        assert_eq!(sizes(rings.sssr()), vec![3, 4])
+    }
+
+    #[test]
+    fn adamantane() {
+        let rings = rings("C1C2CC3CC1CC(C2)C3");
+
+        assert_eq!(sizes(rings.sssr()), vec![6, 6, 6]);
+        assert_eq!(sizes(rings.relevant_cycles()), vec![6, 6, 6, 6])
+    }
+}
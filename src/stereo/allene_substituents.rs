@@ -0,0 +1,69 @@
+use gamma::graph::Error;
+
+use crate::molecule::Molecule;
+
+type Substituents = Vec<(usize, Option<usize>)>;
+
+/// Returns the substituents of the terminal atoms of the cumulene centered
+/// on id, keyed by terminal, in the order an allene Configuration refers
+/// to them. An implicit hydrogen, given as None, leads its terminal's
+/// list. Returns None if id is not the center of a cumulene.
+pub(crate) fn allene_substituents<M: Molecule>(
+    id: usize,
+    molecule: &M,
+) -> Result<Option<Substituents>, Error> {
+    if !is_cumulated(id, molecule)? {
+        return Ok(None);
+    }
+
+    let mut result = Vec::new();
+
+    for first in molecule.neighbors(id)? {
+        let mut previous = id;
+        let mut current = first;
+
+        while is_cumulated(current, molecule)? {
+            let next = molecule
+                .neighbors(current)?
+                .find(|&tid| tid != previous)
+                .expect("next");
+
+            if next == id {
+                break;
+            }
+
+            previous = current;
+            current = next;
+        }
+
+        for _ in 0..molecule.atom(current)?.hydrogens {
+            result.push((current, None));
+        }
+
+        for neighbor in molecule.neighbors(current)? {
+            if neighbor != previous {
+                result.push((current, Some(neighbor)));
+            }
+        }
+    }
+
+    Ok(Some(result))
+}
+
+// Returns true if id has exactly two neighbors, both joined by double
+// bonds, and no hydrogens.
+fn is_cumulated<M: Molecule>(id: usize, molecule: &M) -> Result<bool, Error> {
+    if molecule.degree(id)? != 2 || molecule.atom(id)?.hydrogens > 0 {
+        return Ok(false);
+    }
+
+    for neighbor in molecule.neighbors(id)? {
+        match molecule.bond(id, neighbor)? {
+            Some(bond) if bond.electrons == 4 => (),
+            Some(_) => return Ok(false),
+            None => return Err(Error::MissingEdge(id, neighbor)),
+        }
+    }
+
+    Ok(true)
+}
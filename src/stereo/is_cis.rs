@@ -0,0 +1,124 @@
+use gamma::graph::Error;
+
+use crate::molecule::{Molecule, Parity};
+
+/// Returns true if left, a neighbor of sid, and right, a neighbor of tid,
+/// lie on the same side of the double bond joining sid and tid. Returns
+/// None if the bond has no parity, or Error if any of the bonds is missing.
+pub fn is_cis<M: Molecule>(
+    molecule: &M,
+    sid: usize,
+    tid: usize,
+    left: usize,
+    right: usize,
+) -> Result<Option<bool>, Error> {
+    let parity = match molecule.bond(sid, tid)? {
+        Some(bond) => match &bond.parity {
+            Some(parity) => parity,
+            None => return Ok(None),
+        },
+        None => return Err(Error::MissingEdge(sid, tid)),
+    };
+    let (left_first, left_even) = reference(molecule, sid, tid, left)?;
+    let (right_first, right_even) = reference(molecule, tid, sid, right)?;
+    // Parity is relative to the position of each double bond partner
+    // within the bond lists of its ends. See trigonal_parity.
+    let cis = (parity == &Parity::Negative) == (left_even == right_even);
+
+    Ok(Some(cis == (left_first == right_first)))
+}
+
+// Returns whether neighbor is the first neighbor of id other than partner,
+// and whether partner does not occupy the second position among the
+// neighbors of id.
+fn reference<M: Molecule>(
+    molecule: &M,
+    id: usize,
+    partner: usize,
+    neighbor: usize,
+) -> Result<(bool, bool), Error> {
+    let neighbors = molecule.neighbors(id)?.collect::<Vec<_>>();
+
+    if neighbor == partner || !neighbors.contains(&neighbor) {
+        return Err(Error::MissingEdge(id, neighbor));
+    }
+
+    let first = neighbors.iter().find(|&&other| other != partner);
+    let even = neighbors.get(1) != Some(&partner);
+
+    Ok((first == Some(&neighbor), even))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::read_smiles;
+
+    #[test]
+    fn unknown_id() {
+        let molecule = read_smiles("C/C=C/C", None).unwrap();
+
+        assert_eq!(is_cis(&molecule, 1, 4, 0, 3), Err(Error::UnknownId(4)))
+    }
+
+    #[test]
+    fn missing_double() {
+        let molecule = read_smiles("C/C=C/C", None).unwrap();
+
+        assert_eq!(is_cis(&molecule, 0, 2, 1, 3), Err(Error::MissingEdge(0, 2)))
+    }
+
+    #[test]
+    fn missing_substituent() {
+        let molecule = read_smiles("C/C=C/C", None).unwrap();
+
+        assert_eq!(is_cis(&molecule, 1, 2, 3, 0), Err(Error::MissingEdge(1, 3)))
+    }
+
+    #[test]
+    fn no_parity() {
+        let molecule = read_smiles("CC=CC", None).unwrap();
+
+        assert_eq!(is_cis(&molecule, 1, 2, 0, 3), Ok(None))
+    }
+
+    #[test]
+    fn trans_butene() {
+        let molecule = read_smiles("C/C=C/C", None).unwrap();
+
+        assert_eq!(is_cis(&molecule, 1, 2, 0, 3), Ok(Some(false)))
+    }
+
+    #[test]
+    fn cis_butene() {
+        let molecule = read_smiles("C/C=C\\C", None).unwrap();
+
+        assert_eq!(is_cis(&molecule, 1, 2, 0, 3), Ok(Some(true)))
+    }
+
+    #[test]
+    fn cis_butene_reversed() {
+        let molecule = read_smiles("C/C=C\\C", None).unwrap();
+
+        assert_eq!(is_cis(&molecule, 2, 1, 3, 0), Ok(Some(true)))
+    }
+
+    #[test]
+    fn branched() {
+        let molecule = read_smiles("F/C(Cl)=C/F", None).unwrap();
+
+        assert_eq!(
+            (is_cis(&molecule, 1, 3, 0, 4), is_cis(&molecule, 1, 3, 2, 4)),
+            (Ok(Some(false)), Ok(Some(true)))
+        )
+    }
+
+    #[test]
+    fn partner_second() {
+        let molecule = read_smiles("C(/F)=C/F", None).unwrap();
+
+        assert_eq!(is_cis(&molecule, 0, 2, 1, 3), Ok(Some(true)))
+    }
+}
@@ -0,0 +1,15 @@
+mod allene_substituents;
+mod is_cis;
+mod stereo_group;
+mod stereo_kind;
+mod stereocenters;
+mod stereoisomers;
+mod strip_parities;
+
+pub(crate) use allene_substituents::allene_substituents;
+pub use is_cis::is_cis;
+pub use stereo_group::StereoGroup;
+pub use stereo_kind::StereoKind;
+pub use stereocenters::Stereocenters;
+pub use stereoisomers::stereoisomers;
+pub use strip_parities::strip_parities;
@@ -0,0 +1,21 @@
+/// An enhanced stereo group, qualifying the parities of its atoms.
+/// Absolute centers have the configuration given. Or groups have either
+/// the configuration given or its inverse, but not both (relative
+/// stereo). And groups are mixtures of both (racemic). Or and And groups
+/// carry the number given in the source.
+#[derive(Debug, PartialEq, Clone)]
+pub enum StereoGroup {
+    Absolute(Vec<usize>),
+    Or(usize, Vec<usize>),
+    And(usize, Vec<usize>),
+}
+
+impl StereoGroup {
+    pub fn atoms(&self) -> &[usize] {
+        match self {
+            StereoGroup::Absolute(atoms) => atoms,
+            StereoGroup::Or(_, atoms) => atoms,
+            StereoGroup::And(_, atoms) => atoms,
+        }
+    }
+}
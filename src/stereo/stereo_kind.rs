@@ -0,0 +1,8 @@
+/// The kind of a stereogenic atom or double bond. A true stereocenter
+/// bears four different ligands. A para-stereocenter bears two ligands
+/// that differ only in the configurations of other stereogenic elements.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum StereoKind {
+    True,
+    Para,
+}
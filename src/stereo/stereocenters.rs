@@ -0,0 +1,567 @@
+use std::collections::{HashMap, HashSet};
+
+use gamma::graph::Error;
+
+use super::StereoKind;
+use crate::canon::symmetry_classes;
+use crate::molecule::Molecule;
+use crate::rings::Rings;
+
+// Double bonds in rings smaller than this can only be cis.
+const MIN_STEREO_RING: usize = 8;
+
+/// The stereogenic atoms and double bonds of a Molecule, found from its
+/// constitution alone.
+///
+/// A tetrahedral stereocenter has four ligands (counting one implicit
+/// hydrogen), and a stereogenic double bond one or two ligands at each end
+/// besides its partner. The element is true if the ligands at each center
+/// fall into different symmetry classes. If exactly two ligands at a
+/// center are equivalent, the element may still be a para-stereocenter:
+/// if both ligands lie in a ring with another such element, as in
+/// 1,4-dimethylcyclohexane, or if they lead to other stereogenic elements,
+/// as at C3 of 2,3,4-trihydroxyglutaric acid.
+#[derive(Debug, PartialEq)]
+pub struct Stereocenters {
+    atoms: HashMap<usize, StereoKind>,
+    bonds: HashMap<(usize, usize), StereoKind>,
+    unspecified_atoms: Vec<usize>,
+    unspecified_bonds: Vec<(usize, usize)>,
+}
+
+impl Stereocenters {
+    pub fn new<M: Molecule>(molecule: &M) -> Result<Self, Error> {
+        let classes = symmetry_classes(molecule)?;
+        let rings = Rings::new(molecule)?;
+        let mut candidates = Vec::new();
+
+        for id in molecule.ids() {
+            if let Some(candidate) = tetrahedral(id, molecule, &classes)? {
+                candidates.push(candidate);
+            }
+        }
+
+        for (sid, tid) in molecule.edges() {
+            if let Some(candidate) =
+                double(sid, tid, molecule, &classes, &rings)?
+            {
+                candidates.push(candidate);
+            }
+        }
+
+        let mut kinds = candidates
+            .iter()
+            .map(|candidate| {
+                if candidate.pairs.is_empty() {
+                    Some(StereoKind::True)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        loop {
+            let mut changed = false;
+
+            for index in 0..candidates.len() {
+                if kinds[index].is_none()
+                    && is_para(index, &candidates, &kinds, molecule, &rings)?
+                {
+                    kinds[index] = Some(StereoKind::Para);
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        let mut result = Self {
+            atoms: HashMap::new(),
+            bonds: HashMap::new(),
+            unspecified_atoms: Vec::new(),
+            unspecified_bonds: Vec::new(),
+        };
+
+        for (candidate, kind) in candidates.iter().zip(kinds) {
+            let kind = match kind {
+                Some(kind) => kind,
+                None => continue,
+            };
+
+            match candidate.unit {
+                Unit::Atom(id) => {
+                    if molecule.atom(id)?.parity.is_none() {
+                        result.unspecified_atoms.push(id);
+                    }
+
+                    result.atoms.insert(id, kind);
+                }
+                Unit::Bond(sid, tid) => {
+                    let specified = match molecule.bond(sid, tid)? {
+                        Some(bond) => bond.parity.is_some(),
+                        None => return Err(Error::MissingEdge(sid, tid)),
+                    };
+
+                    if !specified {
+                        result.unspecified_bonds.push((sid, tid));
+                    }
+
+                    result.bonds.insert((sid, tid), kind);
+                }
+            }
+        }
+
+        result.unspecified_atoms.sort_unstable();
+        result.unspecified_bonds.sort_unstable();
+
+        Ok(result)
+    }
+
+    /// Returns the kind of stereocenter at id, if any.
+    pub fn atom(&self, id: usize) -> Option<&StereoKind> {
+        self.atoms.get(&id)
+    }
+
+    /// Returns the kind of stereogenic double bond joining sid and tid, if
+    /// any.
+    pub fn bond(&self, sid: usize, tid: usize) -> Option<&StereoKind> {
+        self.bonds.get(&key(sid, tid))
+    }
+
+    /// Returns the stereocenter ids in ascending order.
+    pub fn atoms(&self) -> Vec<usize> {
+        let mut result = self.atoms.keys().cloned().collect::<Vec<_>>();
+
+        result.sort_unstable();
+
+        result
+    }
+
+    /// Returns the stereogenic double bonds in ascending order, each with
+    /// its lower id first.
+    pub fn bonds(&self) -> Vec<(usize, usize)> {
+        let mut result = self.bonds.keys().cloned().collect::<Vec<_>>();
+
+        result.sort_unstable();
+
+        result
+    }
+
+    /// Returns the stereocenters without a parity, in ascending order.
+    pub fn unspecified_atoms(&self) -> &[usize] {
+        &self.unspecified_atoms
+    }
+
+    /// Returns the stereogenic double bonds without a parity.
+    pub fn unspecified_bonds(&self) -> &[(usize, usize)] {
+        &self.unspecified_bonds
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Unit {
+    Atom(usize),
+    Bond(usize, usize),
+}
+
+// A possible stereogenic unit, and for each center with two equivalent
+// ligands, the center and those ligands.
+struct Candidate {
+    unit: Unit,
+    pairs: Vec<(usize, usize, usize)>,
+}
+
+impl Candidate {
+    fn atoms(&self) -> Vec<usize> {
+        match self.unit {
+            Unit::Atom(id) => vec![id],
+            Unit::Bond(sid, tid) => vec![sid, tid],
+        }
+    }
+}
+
+enum Ligands {
+    Distinct,
+    Pair(usize, usize),
+    Tied,
+}
+
+fn tetrahedral<M: Molecule>(
+    id: usize,
+    molecule: &M,
+    classes: &HashMap<usize, usize>,
+) -> Result<Option<Candidate>, Error> {
+    let hydrogens = molecule.atom(id)?.hydrogens as usize;
+    let neighbors = molecule.neighbors(id)?.collect::<Vec<_>>();
+
+    if hydrogens > 1 || hydrogens + neighbors.len() != 4 {
+        return Ok(None);
+    }
+
+    Ok(match ligands(&neighbors, hydrogens, classes) {
+        Ligands::Distinct => Some(Candidate {
+            unit: Unit::Atom(id),
+            pairs: vec![],
+        }),
+        Ligands::Pair(left, right) => Some(Candidate {
+            unit: Unit::Atom(id),
+            pairs: vec![(id, left, right)],
+        }),
+        Ligands::Tied => None,
+    })
+}
+
+fn double<M: Molecule>(
+    sid: usize,
+    tid: usize,
+    molecule: &M,
+    classes: &HashMap<usize, usize>,
+    rings: &Rings,
+) -> Result<Option<Candidate>, Error> {
+    match molecule.bond(sid, tid)? {
+        Some(bond) if bond.electrons == 4 => (),
+        Some(_) => return Ok(None),
+        None => return Err(Error::MissingEdge(sid, tid)),
+    }
+
+    let in_small_ring = rings.relevant_cycles().iter().any(|cycle| {
+        cycle.len() < MIN_STEREO_RING && is_cycle_bond(cycle, sid, tid)
+    });
+
+    if in_small_ring {
+        return Ok(None);
+    }
+
+    let mut pairs = Vec::new();
+
+    for &(id, partner) in [(sid, tid), (tid, sid)].iter() {
+        let hydrogens = molecule.atom(id)?.hydrogens as usize;
+        let neighbors = molecule
+            .neighbors(id)?
+            .filter(|&neighbor| neighbor != partner)
+            .collect::<Vec<_>>();
+
+        if hydrogens + neighbors.len() == 0 || hydrogens + neighbors.len() > 2 {
+            return Ok(None);
+        }
+
+        for &neighbor in neighbors.iter() {
+            match molecule.bond(id, neighbor)? {
+                Some(bond) if bond.electrons == 2 => (),
+                Some(_) => return Ok(None),
+                None => return Err(Error::MissingEdge(id, neighbor)),
+            }
+        }
+
+        match ligands(&neighbors, hydrogens, classes) {
+            Ligands::Distinct => (),
+            Ligands::Pair(left, right) => pairs.push((id, left, right)),
+            Ligands::Tied => return Ok(None),
+        }
+    }
+
+    Ok(Some(Candidate {
+        unit: Unit::Bond(sid.min(tid), sid.max(tid)),
+        pairs,
+    }))
+}
+
+// Classifies the ligands of a center, counting hydrogens as one class.
+fn ligands(
+    neighbors: &[usize],
+    hydrogens: usize,
+    classes: &HashMap<usize, usize>,
+) -> Ligands {
+    if hydrogens > 1 {
+        return Ligands::Tied;
+    }
+
+    let mut pair = None;
+
+    for (i, &left) in neighbors.iter().enumerate() {
+        for &right in neighbors.iter().skip(i + 1) {
+            if classes[&left] == classes[&right] {
+                if pair.is_some() {
+                    return Ligands::Tied;
+                }
+
+                pair = Some((left, right));
+            }
+        }
+    }
+
+    match pair {
+        Some((left, right)) => Ligands::Pair(left, right),
+        None => Ligands::Distinct,
+    }
+}
+
+// A candidate with equivalent ligands is a para-stereocenter if, for each
+// such pair, the ligands share a ring with the center and another
+// candidate, or lead to a stereogenic unit.
+fn is_para<M: Molecule>(
+    index: usize,
+    candidates: &[Candidate],
+    kinds: &[Option<StereoKind>],
+    molecule: &M,
+    rings: &Rings,
+) -> Result<bool, Error> {
+    let own = candidates[index].atoms();
+    let others = candidates
+        .iter()
+        .enumerate()
+        .filter(|(other, _)| *other != index)
+        .flat_map(|(_, candidate)| candidate.atoms())
+        .filter(|id| !own.contains(id))
+        .collect::<HashSet<_>>();
+
+    for &(center, left, right) in candidates[index].pairs.iter() {
+        let cyclic = rings.is_bond_in_ring(center, left)
+            && rings.is_bond_in_ring(center, right)
+            && ring_reaches(left, right, center, &others, molecule, rings)?;
+
+        if cyclic {
+            continue;
+        }
+
+        let stereogenic = candidates
+            .iter()
+            .zip(kinds.iter())
+            .filter(|(_, kind)| kind.is_some())
+            .flat_map(|(candidate, _)| candidate.atoms())
+            .filter(|id| !own.contains(id))
+            .collect::<HashSet<_>>();
+
+        if !reaches(left, center, &stereogenic, molecule)? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+// Returns true if any of targets can be reached from start without
+// passing through center.
+fn reaches<M: Molecule>(
+    start: usize,
+    center: usize,
+    targets: &HashSet<usize>,
+    molecule: &M,
+) -> Result<bool, Error> {
+    let mut visited = HashSet::new();
+    let mut stack = vec![start];
+
+    visited.insert(center);
+    visited.insert(start);
+
+    while let Some(id) = stack.pop() {
+        if targets.contains(&id) {
+            return Ok(true);
+        }
+
+        for neighbor in molecule.neighbors(id)? {
+            if visited.insert(neighbor) {
+                stack.push(neighbor);
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+// Returns true if right and any of targets can be reached from left
+// through ring bonds without passing through center. Such paths close
+// cycles through center that need not be relevant cycles, as across the
+// bridgeheads of decalin.
+fn ring_reaches<M: Molecule>(
+    left: usize,
+    right: usize,
+    center: usize,
+    targets: &HashSet<usize>,
+    molecule: &M,
+    rings: &Rings,
+) -> Result<bool, Error> {
+    let mut visited = HashSet::new();
+    let mut stack = vec![left];
+
+    visited.insert(center);
+    visited.insert(left);
+
+    while let Some(id) = stack.pop() {
+        for neighbor in molecule.neighbors(id)? {
+            if rings.is_bond_in_ring(id, neighbor) && visited.insert(neighbor) {
+                stack.push(neighbor);
+            }
+        }
+    }
+
+    Ok(visited.contains(&right)
+        && targets.iter().any(|id| visited.contains(id)))
+}
+
+fn is_cycle_bond(cycle: &[usize], sid: usize, tid: usize) -> bool {
+    (0..cycle.len()).any(|i| {
+        let next = cycle[(i + 1) % cycle.len()];
+
+        (cycle[i] == sid && next == tid) || (cycle[i] == tid && next == sid)
+    })
+}
+
+fn key(sid: usize, tid: usize) -> (usize, usize) {
+    if sid < tid {
+        (sid, tid)
+    } else {
+        (tid, sid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::read_smiles;
+
+    fn perceive(smiles: &str) -> Stereocenters {
+        Stereocenters::new(&read_smiles(smiles, None).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn butan_2_ol() {
+        let stereocenters = perceive("C[C@H](O)CC");
+
+        assert_eq!(stereocenters.atoms(), vec![1]);
+        assert_eq!(stereocenters.atom(1), Some(&StereoKind::True));
+        assert_eq!(stereocenters.unspecified_atoms(), &[] as &[usize])
+    }
+
+    #[test]
+    fn unspecified() {
+        assert_eq!(perceive("CC(O)CC").unspecified_atoms(), &[1])
+    }
+
+    #[test]
+    fn equivalent_ligands() {
+        assert_eq!(perceive("C[C@H](C)O").atoms(), vec![])
+    }
+
+    #[test]
+    fn quaternary() {
+        assert_eq!(perceive("CC(F)(Cl)Br").atoms(), vec![1])
+    }
+
+    #[test]
+    fn methylcyclohexane() {
+        assert_eq!(perceive("CC1CCCCC1").atoms(), vec![])
+    }
+
+    #[test]
+    fn dimethylcyclohexane_1_4() {
+        let stereocenters = perceive("C[C@H]1CC[C@@H](C)CC1");
+
+        assert_eq!(stereocenters.atoms(), vec![1, 4]);
+        assert_eq!(stereocenters.atom(1), Some(&StereoKind::Para))
+    }
+
+    #[test]
+    fn dimethylcyclohexane_1_3() {
+        let stereocenters = perceive("CC1CC(C)CCC1");
+
+        assert_eq!(stereocenters.atoms(), vec![1, 3]);
+        assert_eq!(stereocenters.atom(1), Some(&StereoKind::True))
+    }
+
+    #[test]
+    fn cis_decalin() {
+        let stereocenters = perceive("C1CC[C@H]2CCCC[C@@H]2C1");
+
+        assert_eq!(stereocenters.atoms(), vec![3, 8]);
+        assert_eq!(stereocenters.atom(3), Some(&StereoKind::Para));
+        assert_eq!(stereocenters.atom(8), Some(&StereoKind::Para))
+    }
+
+    #[test]
+    fn trans_decalin() {
+        let stereocenters = perceive("C1CC[C@H]2CCCC[C@H]2C1");
+
+        assert_eq!(stereocenters.atoms(), vec![3, 8]);
+        assert_eq!(stereocenters.unspecified_atoms(), &[] as &[usize])
+    }
+
+    #[test]
+    fn trihydroxyglutaric_acid() {
+        let stereocenters = perceive("OC(=O)C(O)C(O)C(O)C(=O)O");
+
+        assert_eq!(stereocenters.atoms(), vec![3, 5, 7]);
+        assert_eq!(stereocenters.atom(3), Some(&StereoKind::True));
+        assert_eq!(stereocenters.atom(5), Some(&StereoKind::Para))
+    }
+
+    #[test]
+    fn glycerol() {
+        assert_eq!(perceive("OCC(O)CO").atoms(), vec![])
+    }
+
+    #[test]
+    fn butene() {
+        let stereocenters = perceive("CC=CC");
+
+        assert_eq!(stereocenters.bonds(), vec![(1, 2)]);
+        assert_eq!(stereocenters.unspecified_bonds(), &[(1, 2)])
+    }
+
+    #[test]
+    fn specified_butene() {
+        let stereocenters = perceive("C/C=C/C");
+
+        assert_eq!(stereocenters.bond(2, 1), Some(&StereoKind::True));
+        assert_eq!(stereocenters.unspecified_bonds(), &[] as &[(usize, usize)])
+    }
+
+    #[test]
+    fn isobutene() {
+        assert_eq!(perceive("CC(C)=CC").bonds(), vec![])
+    }
+
+    #[test]
+    fn terminal_alkene() {
+        assert_eq!(perceive("C=CC").bonds(), vec![])
+    }
+
+    #[test]
+    fn cyclohexene() {
+        assert_eq!(perceive("C1CCC=CC1").bonds(), vec![])
+    }
+
+    #[test]
+    fn cyclooctene() {
+        assert_eq!(perceive("C1CCCC=CCC1").bonds(), vec![(4, 5)])
+    }
+
+    #[test]
+    fn oxime() {
+        assert_eq!(perceive("CC(F)=NO").bonds(), vec![(1, 3)])
+    }
+
+    #[test]
+    fn symmetric_oxime() {
+        assert_eq!(perceive("CC(C)=NO").bonds(), vec![])
+    }
+
+    #[test]
+    fn ethylidene_methylcyclohexane() {
+        let stereocenters = perceive("CC=C1CCC(C)CC1");
+
+        assert_eq!(stereocenters.bond(1, 2), Some(&StereoKind::Para));
+        assert_eq!(stereocenters.atom(5), Some(&StereoKind::Para))
+    }
+
+    #[test]
+    fn benzene() {
+        let stereocenters = perceive("C1=CC=CC=C1");
+
+        assert_eq!(stereocenters.bonds(), vec![])
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::{HashMap, HashSet};
+
+use gamma::graph::Error;
+
+use super::{strip_parities, Stereocenters};
+use crate::daylight::{write_canonical_smiles, SmilesOutputError};
+use crate::molecule::{Bond, DefaultMolecule, Molecule, Node, Parity};
+
+/// Returns up to limit stereoisomers of molecule, one for each distinct
+/// assignment of parities to its unspecified stereocenters and
+/// stereogenic double bonds. Specified elements are kept, and parities
+/// that are not stereogenic are removed as by `strip_parities`.
+/// Assignments giving the same canonical SMILES, such as the two readings
+/// of a meso form, are reported once. An isomer with no canonical SMILES
+/// is always reported.
+///
+/// Atom ids are renumbered from zero in the order reported by molecule.
+/// A molecule without unspecified elements yields itself alone.
+pub fn stereoisomers<M: Molecule>(
+    molecule: &M,
+    limit: usize,
+) -> Result<Vec<DefaultMolecule>, Error> {
+    let base = strip_parities(molecule)?;
+    let stereocenters = Stereocenters::new(&base)?;
+    let atoms = stereocenters.unspecified_atoms();
+    let bonds = stereocenters.unspecified_bonds();
+    let mut parities = vec![Parity::Positive; atoms.len() + bonds.len()];
+    let mut seen = HashSet::new();
+    let mut result = Vec::new();
+
+    while result.len() < limit {
+        let isomer = assign(
+            &base,
+            atoms
+                .iter()
+                .cloned()
+                .zip(parities.iter().cloned())
+                .collect(),
+            bonds
+                .iter()
+                .cloned()
+                .zip(parities.iter().skip(atoms.len()).cloned())
+                .collect(),
+        )?;
+
+        let unseen = match write_canonical_smiles(&isomer) {
+            Ok(smiles) => seen.insert(smiles),
+            Err(SmilesOutputError::Graph(error)) => return Err(error),
+            Err(_) => true,
+        };
+
+        if unseen {
+            result.push(isomer);
+        }
+
+        if !advance(&mut parities) {
+            break;
+        }
+    }
+
+    Ok(result)
+}
+
+// Steps parities to the next combination, returning false once all have
+// been visited.
+fn advance(parities: &mut [Parity]) -> bool {
+    for parity in parities.iter_mut() {
+        if parity == &Parity::Positive {
+            *parity = Parity::Negative;
+
+            return true;
+        }
+
+        *parity = Parity::Positive;
+    }
+
+    false
+}
+
+// Returns a copy of molecule with the parities given. Both directions of
+// each double bond receive the same parity.
+fn assign<M: Molecule>(
+    molecule: &M,
+    atoms: HashMap<usize, Parity>,
+    bonds: HashMap<(usize, usize), Parity>,
+) -> Result<DefaultMolecule, Error> {
+    let mut nodes = Vec::new();
+
+    for id in molecule.ids() {
+        let mut atom = molecule.atom(id)?.clone();
+        let mut outs = Vec::new();
+
+        if let Some(parity) = atoms.get(&id) {
+            atom.parity = Some(parity.clone());
+        }
+
+        for tid in molecule.neighbors(id)? {
+            let bond = match molecule.bond(id, tid)? {
+                Some(bond) => bond,
+                None => return Err(Error::MissingEdge(id, tid)),
+            };
+            let parity = match bonds.get(&(id.min(tid), id.max(tid))) {
+                Some(parity) => Some(parity.clone()),
+                None => bond.parity.clone(),
+            };
+
+            outs.push(Bond {
+                electrons: bond.electrons,
+                parity,
+                tid,
+                aromatic: bond.aromatic,
+            })
+        }
+
+        nodes.push(Node {
+            atom,
+            bonds: outs,
+            aromatic: molecule.is_aromatic(id)?,
+        })
+    }
+
+    Ok(DefaultMolecule::new(nodes))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::read_smiles;
+
+    fn enumerate(smiles: &str, limit: usize) -> Vec<String> {
+        let molecule = read_smiles(smiles, None).unwrap();
+        let mut result = stereoisomers(&molecule, limit)
+            .unwrap()
+            .iter()
+            .map(|isomer| write_canonical_smiles(isomer).unwrap())
+            .collect::<Vec<_>>();
+
+        result.sort();
+
+        result
+    }
+
+    fn canonical(smiles: &[&str]) -> Vec<String> {
+        let mut result = smiles
+            .iter()
+            .map(|smiles| {
+                write_canonical_smiles(&read_smiles(smiles, None).unwrap())
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        result.sort();
+
+        result
+    }
+
+    #[test]
+    fn achiral() {
+        assert_eq!(enumerate("CCO", 10), canonical(&["CCO"]))
+    }
+
+    #[test]
+    fn meaningless_parity() {
+        assert_eq!(enumerate("C[C@H](C)O", 10), canonical(&["CC(C)O"]))
+    }
+
+    #[test]
+    fn one_center() {
+        assert_eq!(
+            enumerate("CC(O)CC", 10),
+            canonical(&["C[C@H](O)CC", "C[C@@H](O)CC"])
+        )
+    }
+
+    #[test]
+    fn specified_center_kept() {
+        assert_eq!(
+            enumerate("C[C@H](O)C(O)C", 10),
+            canonical(&["C[C@H](O)[C@H](O)C", "C[C@H](O)[C@@H](O)C"])
+        )
+    }
+
+    #[test]
+    fn meso() {
+        assert_eq!(
+            enumerate("CC(O)C(O)C", 10),
+            canonical(&[
+                "C[C@H](O)[C@H](C)O",
+                "C[C@@H](O)[C@@H](C)O",
+                "C[C@H](O)[C@@H](C)O",
+            ])
+        )
+    }
+
+    #[test]
+    fn para_stereocenter() {
+        assert_eq!(enumerate("OC(=O)C(O)C(O)C(O)C(=O)O", 10).len(), 4)
+    }
+
+    #[test]
+    fn double_bond() {
+        assert_eq!(enumerate("CC=CC", 10), canonical(&["C/C=C/C", "C/C=C\\C"]))
+    }
+
+    #[test]
+    fn dimethylcyclohexane() {
+        assert_eq!(
+            enumerate("CC1CCC(C)CC1", 10),
+            canonical(&["C[C@H]1CC[C@@H](C)CC1", "C[C@H]1CC[C@H](C)CC1"])
+        )
+    }
+
+    #[test]
+    fn decalin() {
+        assert_eq!(
+            enumerate("C1CCC2CCCCC2C1", 10),
+            canonical(&["C1CC[C@H]2CCCC[C@@H]2C1", "C1CC[C@H]2CCCC[C@H]2C1"])
+        )
+    }
+
+    #[test]
+    fn mixed() {
+        assert_eq!(enumerate("CC=CC(C)O", 10).len(), 4)
+    }
+
+    #[test]
+    fn limit() {
+        assert_eq!(enumerate("CC(O)C(F)C(Cl)C(Br)C", 5).len(), 5)
+    }
+
+    #[test]
+    fn zero_limit() {
+        assert_eq!(enumerate("CC(O)CC", 0), Vec::<String>::new())
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use gamma::graph::Error;
+
+use super::Stereocenters;
+use crate::molecule::{Bond, DefaultMolecule, Molecule, Node};
+
+/// Returns a copy of molecule without parities on atoms and bonds that
+/// are not stereogenic, as perceived by Stereocenters. Atom ids are
+/// renumbered from zero in the order reported by molecule.
+pub fn strip_parities<M: Molecule>(
+    molecule: &M,
+) -> Result<DefaultMolecule, Error> {
+    let stereocenters = Stereocenters::new(molecule)?;
+    let indices = molecule
+        .ids()
+        .enumerate()
+        .map(|(index, id)| (id, index))
+        .collect::<HashMap<_, _>>();
+    let mut nodes = Vec::new();
+
+    for id in molecule.ids() {
+        let mut atom = molecule.atom(id)?.clone();
+        let mut bonds = Vec::new();
+
+        if stereocenters.atom(id).is_none() {
+            atom.parity = None;
+        }
+
+        for tid in molecule.neighbors(id)? {
+            let bond = match molecule.bond(id, tid)? {
+                Some(bond) => bond,
+                None => return Err(Error::MissingEdge(id, tid)),
+            };
+            let parity = match stereocenters.bond(id, tid) {
+                Some(_) => bond.parity.clone(),
+                None => None,
+            };
+
+            bonds.push(Bond {
+                electrons: bond.electrons,
+                parity,
+                tid: indices[&tid],
+                aromatic: bond.aromatic,
+            })
+        }
+
+        nodes.push(Node {
+            atom,
+            bonds,
+            aromatic: molecule.is_aromatic(id)?,
+        })
+    }
+
+    Ok(DefaultMolecule::new(nodes))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::read_smiles;
+    use crate::molecule::Parity;
+
+    #[test]
+    fn meaningless_atom_parity() {
+        let molecule = read_smiles("C[C@H](C)O", None).unwrap();
+        let stripped = strip_parities(&molecule).unwrap();
+
+        assert_eq!(stripped.atom(1).unwrap().parity, None)
+    }
+
+    #[test]
+    fn meaningful_atom_parity() {
+        let molecule = read_smiles("C[C@H](O)CC", None).unwrap();
+        let stripped = strip_parities(&molecule).unwrap();
+
+        assert_eq!(stripped, molecule)
+    }
+
+    #[test]
+    fn meaningless_bond_parity() {
+        let molecule = read_smiles("C/C(C)=C/C", None).unwrap();
+        let stripped = strip_parities(&molecule).unwrap();
+
+        assert_eq!(stripped.bond(1, 3).unwrap().unwrap().parity, None);
+        assert_eq!(stripped.bond(3, 1).unwrap().unwrap().parity, None)
+    }
+
+    #[test]
+    fn meaningful_bond_parity() {
+        let molecule = read_smiles("C/C=C/C", None).unwrap();
+        let stripped = strip_parities(&molecule).unwrap();
+
+        assert!(stripped.bond(1, 2).unwrap().unwrap().parity.is_some());
+        assert_eq!(stripped, molecule)
+    }
+
+    #[test]
+    fn para_stereocenters() {
+        let molecule = read_smiles("C[C@H]1CC[C@@H](C)CC1", None).unwrap();
+        let stripped = strip_parities(&molecule).unwrap();
+
+        assert_eq!(stripped.atom(1).unwrap().parity, Some(Parity::Positive));
+        assert_eq!(stripped, molecule)
+    }
+}
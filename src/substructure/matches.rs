@@ -0,0 +1,303 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use gamma::graph::Error;
+
+use super::{Query, Target};
+use crate::molecule::Molecule;
+
+/// Returns every mapping of query atom ids onto distinct atom ids of
+/// molecule under which each query atom and bond matches its image. The
+/// search follows VF2: query atoms are taken in breadth-first order, each
+/// tried against the unmapped neighbors of its parent's image, and pruned
+/// as soon as an atom, bond or degree fails to match.
+///
+/// Mappings differing only by a symmetry of the query are all reported.
+/// An empty query yields one empty mapping.
+pub fn substructure_matches<Q: Query, M: Molecule>(
+    query: &Q,
+    molecule: &M,
+) -> Result<Vec<HashMap<usize, usize>>, Error> {
+    let target = Target::new(molecule)?;
+    let mut search = Search::new(query, &target, false, None)?;
+
+    search.extend(0)?;
+
+    Ok(search.results)
+}
+
+/// Returns the first mapping found by `substructure_matches`, or None if
+/// query doesn't occur in molecule.
+pub fn substructure_match<Q: Query, M: Molecule>(
+    query: &Q,
+    molecule: &M,
+) -> Result<Option<HashMap<usize, usize>>, Error> {
+    let target = Target::new(molecule)?;
+    let mut search = Search::new(query, &target, true, None)?;
+
+    search.extend(0)?;
+
+    Ok(search.results.pop())
+}
+
+/// Returns true if query matches target with its first atom mapped to the
+/// target atom at id, as required by recursive SMARTS.
+pub(crate) fn anchored_match<Q: Query, M: Molecule>(
+    query: &Q,
+    target: &Target<M>,
+    id: usize,
+) -> Result<bool, Error> {
+    let mut search = Search::new(query, target, true, Some(id))?;
+
+    search.extend(0)?;
+
+    Ok(!search.results.is_empty())
+}
+
+struct Search<'a, 'b, Q: Query, M: Molecule> {
+    query: &'a Q,
+    target: &'a Target<'b, M>,
+    // query ids with the previously ordered neighbor, if any
+    order: Vec<(usize, Option<usize>)>,
+    mapping: HashMap<usize, usize>,
+    used: HashSet<usize>,
+    results: Vec<HashMap<usize, usize>>,
+    first: bool,
+    anchor: Option<usize>,
+}
+
+impl<'a, 'b, Q: Query, M: Molecule> Search<'a, 'b, Q, M> {
+    fn new(
+        query: &'a Q,
+        target: &'a Target<'b, M>,
+        first: bool,
+        anchor: Option<usize>,
+    ) -> Result<Self, Error> {
+        let mut order = Vec::new();
+        let mut visited = HashSet::new();
+
+        for root in query.ids() {
+            if !visited.insert(root) {
+                continue;
+            }
+
+            let mut queue = VecDeque::new();
+
+            order.push((root, None));
+            queue.push_back(root);
+
+            while let Some(id) = queue.pop_front() {
+                for neighbor in query.neighbors(id)? {
+                    if visited.insert(neighbor) {
+                        order.push((neighbor, Some(id)));
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            query,
+            target,
+            order,
+            mapping: HashMap::new(),
+            used: HashSet::new(),
+            results: Vec::new(),
+            first,
+            anchor,
+        })
+    }
+
+    // Maps the query atom at depth and beyond, returning true once the
+    // search should stop.
+    fn extend(&mut self, depth: usize) -> Result<bool, Error> {
+        if depth == self.order.len() {
+            if self.query.mapping_matches(self.target, &self.mapping)? {
+                self.results.push(self.mapping.clone());
+
+                return Ok(self.first);
+            }
+
+            return Ok(false);
+        }
+
+        let (id, parent) = self.order[depth];
+        let molecule = self.target.molecule();
+        let candidates = match parent {
+            Some(parent) => molecule
+                .neighbors(self.mapping[&parent])?
+                .collect::<Vec<_>>(),
+            None => match self.anchor {
+                Some(anchor) if depth == 0 => vec![anchor],
+                _ => molecule.ids().collect::<Vec<_>>(),
+            },
+        };
+
+        for candidate in candidates {
+            if self.used.contains(&candidate)
+                || !self.feasible(id, candidate)?
+            {
+                continue;
+            }
+
+            self.mapping.insert(id, candidate);
+            self.used.insert(candidate);
+
+            if self.extend(depth + 1)? {
+                return Ok(true);
+            }
+
+            self.mapping.remove(&id);
+            self.used.remove(&candidate);
+        }
+
+        Ok(false)
+    }
+
+    fn feasible(&self, id: usize, candidate: usize) -> Result<bool, Error> {
+        let molecule = self.target.molecule();
+
+        if molecule.degree(candidate)? < self.query.degree(id)?
+            || !self.query.atom_matches(id, self.target, candidate)?
+        {
+            return Ok(false);
+        }
+
+        for neighbor in self.query.neighbors(id)? {
+            let image = match self.mapping.get(&neighbor) {
+                Some(&image) => image,
+                None => continue,
+            };
+
+            if !molecule.has_edge(candidate, image)?
+                || !self.query.bond_matches(
+                    id,
+                    neighbor,
+                    self.target,
+                    candidate,
+                    image,
+                )?
+            {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::read_smiles;
+    use crate::molecule::DefaultMolecule;
+    use crate::substructure::MoleculeQuery;
+
+    fn count(query: &str, target: &str) -> usize {
+        let query = read_smiles(query, None).unwrap();
+        let target = read_smiles(target, None).unwrap();
+
+        substructure_matches(
+            &MoleculeQuery::new(&query, false).unwrap(),
+            &target,
+        )
+        .unwrap()
+        .len()
+    }
+
+    #[test]
+    fn empty_query() {
+        let query = DefaultMolecule::new(vec![]);
+        let target = read_smiles("CCO", None).unwrap();
+        let query = MoleculeQuery::new(&query, false).unwrap();
+
+        assert_eq!(
+            substructure_matches(&query, &target).unwrap(),
+            vec![HashMap::new()]
+        )
+    }
+
+    #[test]
+    fn absent() {
+        assert_eq!(count("N", "CCO"), 0)
+    }
+
+    #[test]
+    fn single_atom() {
+        assert_eq!(count("C", "CCO"), 2)
+    }
+
+    #[test]
+    fn symmetric_query() {
+        // each orientation of the fragment is a separate mapping
+        assert_eq!(count("CC", "CCO"), 2)
+    }
+
+    #[test]
+    fn hydroxyl() {
+        assert_eq!(count("CO", "CCO"), 1)
+    }
+
+    #[test]
+    fn bond_order() {
+        assert_eq!(count("C=O", "CCO"), 0);
+        assert_eq!(count("C=O", "CC=O"), 1)
+    }
+
+    #[test]
+    fn charge() {
+        assert_eq!(count("C[O-]", "CCO"), 0);
+        assert_eq!(count("C[O-]", "CC[O-]"), 1)
+    }
+
+    #[test]
+    fn query_hydrogens_bound_target() {
+        // a query hydrogen may be substituted, but not added
+        assert_eq!(count("[CH2]", "CC"), 0);
+        assert_eq!(count("C(C)(C)C", "CC(C)C"), 6)
+    }
+
+    #[test]
+    fn ring() {
+        assert_eq!(count("C1CCCCC1", "C1CCCCC1C"), 12)
+    }
+
+    #[test]
+    fn kekule_benzene() {
+        // aromatic bonds match whatever their Kekulé assignment
+        assert_eq!(count("C1=CC=CC=C1", "C1=CC=CC(C)=C1"), 12);
+        assert_eq!(count("c1ccccc1", "C1=CC=CC(C)=C1"), 12)
+    }
+
+    #[test]
+    fn aliphatic_bond_in_aromatic_ring() {
+        assert_eq!(count("C=CC=C", "C1=CC=CC=C1"), 0)
+    }
+
+    #[test]
+    fn disconnected_query() {
+        assert_eq!(count("O.O", "OCCO"), 2)
+    }
+
+    #[test]
+    fn first_match() {
+        let query = read_smiles("CO", None).unwrap();
+        let target = read_smiles("CCO", None).unwrap();
+        let query = MoleculeQuery::new(&query, false).unwrap();
+
+        assert_eq!(
+            substructure_match(&query, &target).unwrap(),
+            Some(vec![(0, 1), (1, 2)].into_iter().collect())
+        )
+    }
+
+    #[test]
+    fn no_first_match() {
+        let query = read_smiles("N", None).unwrap();
+        let target = read_smiles("CCO", None).unwrap();
+        let query = MoleculeQuery::new(&query, false).unwrap();
+
+        assert_eq!(substructure_match(&query, &target).unwrap(), None)
+    }
+}
@@ -0,0 +1,12 @@
+mod matches;
+mod molecule_query;
+mod parity_matches;
+mod query;
+mod target;
+
+pub(crate) use matches::anchored_match;
+pub use matches::{substructure_match, substructure_matches};
+pub use molecule_query::MoleculeQuery;
+pub(crate) use parity_matches::parity_matches;
+pub use query::Query;
+pub use target::Target;
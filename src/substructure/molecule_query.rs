@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+
+use gamma::graph::{Error, Graph};
+
+use super::{parity_matches, Query, Target};
+use crate::aromaticity::{Aromaticity, Model};
+use crate::molecule::Molecule;
+use crate::stereo::is_cis;
+
+/// A Molecule used as a substructure query.
+///
+/// A query atom matches a target atom of the same element, isotope (if
+/// given) and charge, bearing no more hydrogens than the query atom: each
+/// query hydrogen may be kept or replaced by a substituent. Atoms without
+/// an element match any atom. Bonds perceived as aromatic under the
+/// Daylight model match only each other, regardless of Kekulé form, and
+/// other bonds match those with the same electrons.
+///
+/// If parity is true, query atoms and double bonds with a parity match
+/// only target atoms and bonds with the same configuration.
+pub struct MoleculeQuery<'a, M: Molecule> {
+    molecule: &'a M,
+    aromaticity: Aromaticity,
+    parity: bool,
+}
+
+impl<'a, M: Molecule> MoleculeQuery<'a, M> {
+    pub fn new(molecule: &'a M, parity: bool) -> Result<Self, Error> {
+        Ok(Self {
+            molecule,
+            aromaticity: Aromaticity::new(molecule, &Model::Daylight)?,
+            parity,
+        })
+    }
+
+    fn tetrahedral_matches<T: Molecule>(
+        &self,
+        id: usize,
+        target: &Target<T>,
+        mapping: &HashMap<usize, usize>,
+    ) -> Result<bool, Error> {
+        let atom = self.molecule.atom(id)?;
+        let parity = match &atom.parity {
+            Some(parity) => parity,
+            None => return Ok(true),
+        };
+        let mut order = vec![None; atom.hydrogens as usize];
+
+        for neighbor in self.molecule.neighbors(id)? {
+            order.push(Some(mapping[&neighbor]));
+        }
+
+        parity_matches(parity, &order, target.molecule(), mapping[&id])
+    }
+
+    fn double_matches<T: Molecule>(
+        &self,
+        sid: usize,
+        tid: usize,
+        target: &Target<T>,
+        mapping: &HashMap<usize, usize>,
+    ) -> Result<bool, Error> {
+        let left = self.molecule.neighbors(sid)?.find(|&id| id != tid);
+        let right = self.molecule.neighbors(tid)?.find(|&id| id != sid);
+        let (left, right) = match (left, right) {
+            (Some(left), Some(right)) => (left, right),
+            _ => return Ok(true),
+        };
+        let cis = match is_cis(self.molecule, sid, tid, left, right)? {
+            Some(cis) => cis,
+            None => return Ok(true),
+        };
+
+        Ok(is_cis(
+            target.molecule(),
+            mapping[&sid],
+            mapping[&tid],
+            mapping[&left],
+            mapping[&right],
+        )? == Some(cis))
+    }
+}
+
+impl<'a, M: Molecule> Query for MoleculeQuery<'a, M> {
+    fn atom_matches<T: Molecule>(
+        &self,
+        id: usize,
+        target: &Target<T>,
+        tid: usize,
+    ) -> Result<bool, Error> {
+        let atom = self.molecule.atom(id)?;
+        let other = target.molecule().atom(tid)?;
+
+        if atom.element.is_none() {
+            return Ok(true);
+        } else if atom.element != other.element
+            || (atom.isotope.is_some() && atom.isotope != other.isotope)
+        {
+            return Ok(false);
+        }
+
+        Ok(other.hydrogens <= atom.hydrogens
+            && (self.molecule.charge(id)? - target.molecule().charge(tid)?)
+                .abs()
+                < f32::EPSILON)
+    }
+
+    fn bond_matches<T: Molecule>(
+        &self,
+        sid: usize,
+        tid: usize,
+        target: &Target<T>,
+        source: usize,
+        destination: usize,
+    ) -> Result<bool, Error> {
+        let aromatic = self.aromaticity.is_aromatic_bond(sid, tid);
+
+        if aromatic
+            != target.aromaticity().is_aromatic_bond(source, destination)
+        {
+            return Ok(false);
+        } else if aromatic {
+            return Ok(true);
+        }
+
+        let electrons = match self.molecule.bond(sid, tid)? {
+            Some(bond) => bond.electrons,
+            None => return Err(Error::MissingEdge(sid, tid)),
+        };
+
+        Ok(match target.molecule().bond(source, destination)? {
+            Some(bond) => bond.electrons == electrons,
+            None => false,
+        })
+    }
+
+    fn mapping_matches<T: Molecule>(
+        &self,
+        target: &Target<T>,
+        mapping: &HashMap<usize, usize>,
+    ) -> Result<bool, Error> {
+        if !self.parity {
+            return Ok(true);
+        }
+
+        for id in self.molecule.ids() {
+            if !self.tetrahedral_matches(id, target, mapping)? {
+                return Ok(false);
+            }
+        }
+
+        for (sid, tid) in self.molecule.edges() {
+            let has_parity = match self.molecule.bond(sid, tid)? {
+                Some(bond) => bond.parity.is_some(),
+                None => return Err(Error::MissingEdge(sid, tid)),
+            };
+
+            if has_parity && !self.double_matches(sid, tid, target, mapping)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+impl<'a, M: Molecule> Graph for MoleculeQuery<'a, M> {
+    fn is_empty(&self) -> bool {
+        self.molecule.is_empty()
+    }
+
+    fn order(&self) -> usize {
+        self.molecule.order()
+    }
+
+    fn size(&self) -> usize {
+        self.molecule.size()
+    }
+
+    fn ids(&self) -> Box<dyn Iterator<Item = usize> + '_> {
+        self.molecule.ids()
+    }
+
+    fn neighbors(
+        &self,
+        id: usize,
+    ) -> Result<Box<dyn Iterator<Item = usize> + '_>, Error> {
+        self.molecule.neighbors(id)
+    }
+
+    fn has_id(&self, id: usize) -> bool {
+        self.molecule.has_id(id)
+    }
+
+    fn degree(&self, id: usize) -> Result<usize, Error> {
+        self.molecule.degree(id)
+    }
+
+    fn edges(&self) -> Box<dyn Iterator<Item = (usize, usize)> + '_> {
+        self.molecule.edges()
+    }
+
+    fn has_edge(&self, sid: usize, tid: usize) -> Result<bool, Error> {
+        self.molecule.has_edge(sid, tid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::daylight::read_smiles;
+    use crate::substructure::substructure_matches;
+
+    fn count(query: &str, target: &str) -> usize {
+        let query = read_smiles(query, None).unwrap();
+        let target = read_smiles(target, None).unwrap();
+
+        substructure_matches(
+            &MoleculeQuery::new(&query, true).unwrap(),
+            &target,
+        )
+        .unwrap()
+        .len()
+    }
+
+    #[test]
+    fn isotope() {
+        assert_eq!(count("[13CH4]", "CC"), 0);
+        assert_eq!(count("[13CH4]", "[13CH3]C"), 1);
+        assert_eq!(count("C", "[13CH3]C"), 2)
+    }
+
+    #[test]
+    fn wildcard() {
+        assert_eq!(count("*O", "CCO"), 1)
+    }
+
+    #[test]
+    fn same_center() {
+        assert_eq!(count("N[C@@H](C)C(=O)O", "N[C@@H](C)C(=O)O"), 1)
+    }
+
+    #[test]
+    fn enantiomer() {
+        assert_eq!(count("N[C@@H](C)C(=O)O", "N[C@H](C)C(=O)O"), 0)
+    }
+
+    #[test]
+    fn reordered_center() {
+        assert_eq!(count("N[C@@H](C)C(=O)O", "C[C@H](N)C(=O)O"), 1)
+    }
+
+    #[test]
+    fn substituted_hydrogen() {
+        // the query hydrogen maps to the target's methyl
+        assert_eq!(count("N[C@@H](F)Cl", "N[C@@](C)(F)Cl"), 1);
+        assert_eq!(count("N[C@@H](F)Cl", "N[C@](C)(F)Cl"), 0)
+    }
+
+    #[test]
+    fn unspecified_target() {
+        assert_eq!(count("N[C@@H](C)C(=O)O", "NC(C)C(=O)O"), 0)
+    }
+
+    #[test]
+    fn unspecified_query() {
+        assert_eq!(count("NC(C)C(=O)O", "N[C@H](C)C(=O)O"), 1)
+    }
+
+    #[test]
+    fn trans() {
+        assert_eq!(count("C/C=C/C", "C/C=C/CC"), 2);
+        assert_eq!(count("C/C=C/C", "C/C=C\\CC"), 0)
+    }
+
+    #[test]
+    fn parity_ignored() {
+        let query = read_smiles("N[C@@H](C)C(=O)O", None).unwrap();
+        let target = read_smiles("N[C@H](C)C(=O)O", None).unwrap();
+        let query = MoleculeQuery::new(&query, false).unwrap();
+
+        assert_eq!(substructure_matches(&query, &target).unwrap().len(), 1)
+    }
+}
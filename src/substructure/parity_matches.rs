@@ -0,0 +1,64 @@
+use gamma::graph::Error;
+
+use crate::molecule::{Molecule, Parity};
+
+/// Returns true if parity, given relative to order, describes the same
+/// configuration as the parity of the atom at id. Order lists neighbors
+/// of id, with at most one None standing for whichever ligand is left
+/// over: an implicit hydrogen, or a neighbor the query doesn't mention.
+/// Returns false if the atom has no parity or order doesn't fit its
+/// ligands.
+pub fn parity_matches<M: Molecule>(
+    parity: &Parity,
+    order: &[Option<usize>],
+    molecule: &M,
+    id: usize,
+) -> Result<bool, Error> {
+    let target = match &molecule.atom(id)?.parity {
+        Some(target) => target,
+        None => return Ok(false),
+    };
+    let mut reference = vec![None; molecule.atom(id)?.hydrogens as usize];
+
+    reference.extend(molecule.neighbors(id)?.map(Some));
+
+    if order.len() != reference.len() {
+        return Ok(false);
+    }
+
+    let mut order = order.to_vec();
+
+    if let Some(index) = order.iter().position(Option::is_none) {
+        order[index] = match reference
+            .iter()
+            .find(|ligand| ligand.is_some() && !order.contains(ligand))
+        {
+            Some(&ligand) => ligand,
+            None => None,
+        };
+    }
+
+    let positions = order
+        .iter()
+        .map(|ligand| reference.iter().position(|other| other == ligand))
+        .collect::<Option<Vec<_>>>();
+    let positions = match positions {
+        Some(positions) => positions,
+        None => return Ok(false),
+    };
+    let mut swaps = 0;
+
+    for (i, left) in positions.iter().enumerate() {
+        for right in positions.iter().skip(i + 1) {
+            if left > right {
+                swaps += 1;
+            }
+        }
+    }
+
+    Ok(if swaps % 2 == 0 {
+        parity == target
+    } else {
+        &parity.negate() == target
+    })
+}
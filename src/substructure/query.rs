@@ -0,0 +1,43 @@
+use std::collections::HashMap;
+
+use gamma::graph::{Error, Graph};
+
+use super::Target;
+use crate::molecule::Molecule;
+
+/// A pattern for substructure search. The atoms and bonds of the pattern
+/// are those of its Graph, each tested against atoms and bonds of a
+/// Target. Tests that depend on the mapping as a whole, such as stereo,
+/// are made once every atom has been mapped.
+pub trait Query: Graph {
+    /// Returns true if the query atom at id may map to the target atom at
+    /// tid, or Error if either id is not found.
+    fn atom_matches<M: Molecule>(
+        &self,
+        id: usize,
+        target: &Target<M>,
+        tid: usize,
+    ) -> Result<bool, Error>;
+
+    /// Returns true if the query bond joining sid and tid may map to the
+    /// target bond joining source and destination, or Error if any id is
+    /// not found.
+    fn bond_matches<M: Molecule>(
+        &self,
+        sid: usize,
+        tid: usize,
+        target: &Target<M>,
+        source: usize,
+        destination: usize,
+    ) -> Result<bool, Error>;
+
+    /// Returns true if a complete mapping from query ids to target ids is
+    /// accepted. The default accepts every mapping.
+    fn mapping_matches<M: Molecule>(
+        &self,
+        _target: &Target<M>,
+        _mapping: &HashMap<usize, usize>,
+    ) -> Result<bool, Error> {
+        Ok(true)
+    }
+}
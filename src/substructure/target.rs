@@ -0,0 +1,36 @@
+use gamma::graph::Error;
+
+use crate::aromaticity::{Aromaticity, Model};
+use crate::molecule::Molecule;
+use crate::rings::Rings;
+
+/// A Molecule prepared for substructure search, with its rings and
+/// Daylight-model aromaticity perceived once for use by every query atom
+/// and bond.
+pub struct Target<'a, M: Molecule> {
+    molecule: &'a M,
+    rings: Rings,
+    aromaticity: Aromaticity,
+}
+
+impl<'a, M: Molecule> Target<'a, M> {
+    pub fn new(molecule: &'a M) -> Result<Self, Error> {
+        Ok(Self {
+            molecule,
+            rings: Rings::new(molecule)?,
+            aromaticity: Aromaticity::new(molecule, &Model::Daylight)?,
+        })
+    }
+
+    pub fn molecule(&self) -> &M {
+        self.molecule
+    }
+
+    pub fn rings(&self) -> &Rings {
+        &self.rings
+    }
+
+    pub fn aromaticity(&self) -> &Aromaticity {
+        &self.aromaticity
+    }
+}